@@ -13,11 +13,11 @@ fn main() {
     if is_git_repo {
         println!("cargo:rerun-if-changed=.git/HEAD");
 
-        if let Ok(head) = std::fs::read_to_string(".git/HEAD") {
-            if head.starts_with("ref: ") {
-                let head_ref = head.trim_start_matches("ref: ").trim();
-                println!("cargo:rerun-if-changed=.git/{}", head_ref);
-            }
+        if let Ok(head) = std::fs::read_to_string(".git/HEAD")
+            && head.starts_with("ref: ")
+        {
+            let head_ref = head.trim_start_matches("ref: ").trim();
+            println!("cargo:rerun-if-changed=.git/{}", head_ref);
         }
 
         println!("cargo:rerun-if-changed=.git/index");
@@ -25,17 +25,14 @@ fn main() {
 
     let mut git_hash = "unknown".to_string();
 
-    if is_git_repo {
-        if let Ok(output) = Command::new("git")
+    if is_git_repo
+        && let Ok(output) = Command::new("git")
             .args(["rev-parse", "--short", "HEAD"])
             .output()
-        {
-            if output.status.success() {
-                if let Ok(hash) = String::from_utf8(output.stdout) {
-                    git_hash = hash.trim().to_string();
-                }
-            }
-        }
+        && output.status.success()
+        && let Ok(hash) = String::from_utf8(output.stdout)
+    {
+        git_hash = hash.trim().to_string();
     }
 
     println!("cargo:rustc-env=CARGO_GIT_COMMIT={}", git_hash);
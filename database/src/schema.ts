@@ -103,14 +103,17 @@ export const organizationSubusers = pgTable('organization_subusers', {
 
 export const webhooks = pgTable('webhooks', {
 	id: serial('id').primaryKey().notNull(),
-	organizationId: integer('organization_id').references(() => organizations.id, { onDelete: 'restrict' }),
+	organizationId: integer('organization_id').references(() => organizations.id, { onDelete: 'cascade' }),
 
 	label: varchar('label', { length: 255 }),
 	url: varchar('url', { length: 255 }).notNull(),
+	secret: char('secret', { length: 64 }),
 	types: jsonb('types').$type<ServerType[]>().default([...types]).notNull(),
 	enabled: boolean('enabled').default(true).notNull(),
 	successful: integer('successful').default(0).notNull(),
-	failed: integer('failed').default(0).notNull()
+	failed: integer('failed').default(0).notNull(),
+
+	created: timestamp('created').default(sql`now()`).notNull()
 }, (webhooks) => [
 	index('webhooks_organization_idx').on(webhooks.organizationId).where(isNotNull(webhooks.organizationId)),
 	index('webhooks_enabled_idx').on(webhooks.enabled)
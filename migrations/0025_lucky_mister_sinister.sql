@@ -0,0 +1,7 @@
+ALTER TABLE "webhooks" DROP CONSTRAINT "webhooks_organization_id_organizations_id_fk";
+--> statement-breakpoint
+ALTER TABLE "webhooks" ADD COLUMN "secret" char(64);--> statement-breakpoint
+UPDATE "webhooks" SET "secret" = replace(gen_random_uuid()::text || gen_random_uuid()::text, '-', '') WHERE "secret" IS NULL;--> statement-breakpoint
+ALTER TABLE "webhooks" ALTER COLUMN "secret" SET NOT NULL;--> statement-breakpoint
+ALTER TABLE "webhooks" ADD COLUMN "created" timestamp DEFAULT now() NOT NULL;--> statement-breakpoint
+ALTER TABLE "webhooks" ADD CONSTRAINT "webhooks_organization_id_organizations_id_fk" FOREIGN KEY ("organization_id") REFERENCES "public"."organizations"("id") ON DELETE cascade ON UPDATE no action;
\ No newline at end of file
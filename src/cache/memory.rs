@@ -0,0 +1,320 @@
+use super::{CacheBackend, CacheError};
+use futures_util::{StreamExt, stream::BoxStream};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+enum Value {
+    String(String),
+    Set(HashSet<String>),
+}
+
+struct Item {
+    value: Value,
+    expires: Option<Instant>,
+}
+
+impl Item {
+    fn expired(&self) -> bool {
+        self.expires
+            .is_some_and(|expires| expires <= Instant::now())
+    }
+}
+
+type Items = Mutex<HashMap<String, Item>>;
+
+pub struct MemoryBackend {
+    items: Arc<Items>,
+    channel: tokio::sync::broadcast::Sender<(String, String)>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        let items: Arc<Items> = Arc::new(Mutex::new(HashMap::new()));
+        let weak: Weak<Items> = Arc::downgrade(&items);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(SWEEP_INTERVAL).await;
+
+                match weak.upgrade() {
+                    Some(items) => items.lock().unwrap().retain(|_, item| !item.expired()),
+                    None => break,
+                }
+            }
+        });
+
+        Self {
+            items,
+            channel: tokio::sync::broadcast::channel(1024).0,
+        }
+    }
+
+    fn with_item<R>(&self, key: &str, f: impl FnOnce(Option<&mut Item>) -> R) -> R {
+        let mut items = self.items.lock().unwrap();
+
+        if items.get(key).is_some_and(|item| item.expired()) {
+            items.remove(key);
+        }
+
+        f(items.get_mut(key))
+    }
+
+    fn expiry(ttl: u64) -> Option<Instant> {
+        Some(Instant::now() + Duration::from_secs(ttl))
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for MemoryBackend {
+    async fn describe(&self) -> String {
+        "memory".to_string()
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        self.with_item(key, |item| match item.map(|item| &item.value) {
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(Value::Set(_)) => Err(CacheError::Backend(format!("{} is not a string", key))),
+            None => Ok(None),
+        })
+    }
+
+    async fn set(&self, key: &str, value: String, ttl: Option<u64>) -> Result<(), CacheError> {
+        self.items.lock().unwrap().insert(
+            key.to_string(),
+            Item {
+                value: Value::String(value),
+                expires: ttl.and_then(Self::expiry),
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn set_nx(&self, key: &str, value: String, ttl: u64) -> Result<bool, CacheError> {
+        let mut items = self.items.lock().unwrap();
+
+        if items.get(key).is_some_and(|item| !item.expired()) {
+            return Ok(false);
+        }
+
+        items.insert(
+            key.to_string(),
+            Item {
+                value: Value::String(value),
+                expires: Self::expiry(ttl),
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn incr(&self, key: &str) -> Result<i64, CacheError> {
+        let mut items = self.items.lock().unwrap();
+
+        let (count, expires) = match items.get(key) {
+            Some(item) if item.expired() => (1, None),
+            Some(Item {
+                value: Value::String(value),
+                expires,
+            }) => match value.parse::<i64>() {
+                Ok(count) => (count + 1, *expires),
+                Err(_) => {
+                    return Err(CacheError::Backend(format!("{} is not an integer", key)));
+                }
+            },
+            Some(_) => return Err(CacheError::Backend(format!("{} is not an integer", key))),
+            None => (1, None),
+        };
+
+        items.insert(
+            key.to_string(),
+            Item {
+                value: Value::String(count.to_string()),
+                expires,
+            },
+        );
+
+        Ok(count)
+    }
+
+    async fn expire(&self, key: &str, ttl: u64) -> Result<(), CacheError> {
+        self.with_item(key, |item| {
+            if let Some(item) = item {
+                item.expires = Self::expiry(ttl);
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn del(&self, keys: Vec<String>) -> Result<(), CacheError> {
+        let mut items = self.items.lock().unwrap();
+
+        for key in keys {
+            items.remove(&key);
+        }
+
+        Ok(())
+    }
+
+    async fn tag(&self, tag: &str, key: &str, ttl: u64) -> Result<(), CacheError> {
+        let mut items = self.items.lock().unwrap();
+        let expires = Instant::now() + Duration::from_secs(ttl);
+
+        let item = items
+            .entry(tag.to_string())
+            .and_modify(|item| {
+                if item.expired() {
+                    item.value = Value::Set(HashSet::new());
+                    item.expires = None;
+                }
+            })
+            .or_insert_with(|| Item {
+                value: Value::Set(HashSet::new()),
+                expires: None,
+            });
+
+        match &mut item.value {
+            Value::Set(keys) => {
+                keys.insert(key.to_string());
+            }
+            Value::String(_) => return Err(CacheError::Backend(format!("{} is not a set", tag))),
+        }
+
+        if item.expires.is_none_or(|current| current < expires) {
+            item.expires = Some(expires);
+        }
+
+        Ok(())
+    }
+
+    async fn tagged(&self, tag: &str) -> Result<Vec<String>, CacheError> {
+        self.with_item(tag, |item| match item.map(|item| &item.value) {
+            Some(Value::Set(keys)) => Ok(keys.iter().cloned().collect()),
+            Some(Value::String(_)) => Err(CacheError::Backend(format!("{} is not a set", tag))),
+            None => Ok(Vec::new()),
+        })
+    }
+
+    async fn publish(&self, channel: &str, message: String) -> Result<(), CacheError> {
+        let _ = self.channel.send((channel.to_string(), message));
+
+        Ok(())
+    }
+
+    async fn subscribe(&self, channel: &str) -> Result<BoxStream<'static, String>, CacheError> {
+        let channel = channel.to_string();
+        let receiver = self.channel.subscribe();
+
+        Ok(
+            futures_util::stream::unfold(receiver, |mut receiver| async move {
+                receiver
+                    .recv()
+                    .await
+                    .ok()
+                    .map(|message| (message, receiver))
+            })
+            .filter_map(move |(name, message)| {
+                let matches = name == channel;
+
+                async move { matches.then_some(message) }
+            })
+            .boxed(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheBackend, MemoryBackend};
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn values_expire_after_their_ttl() {
+        let backend = MemoryBackend::new();
+
+        backend
+            .set("short", "1".to_string(), Some(1))
+            .await
+            .unwrap();
+        backend.set("forever", "2".to_string(), None).await.unwrap();
+
+        assert_eq!(backend.get("short").await.unwrap().as_deref(), Some("1"));
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        assert_eq!(backend.get("short").await.unwrap(), None);
+        assert_eq!(backend.get("forever").await.unwrap().as_deref(), Some("2"));
+    }
+
+    #[tokio::test]
+    async fn set_nx_only_sets_missing_or_expired_keys() {
+        let backend = MemoryBackend::new();
+
+        assert!(backend.set_nx("lock", "1".to_string(), 1).await.unwrap());
+        assert!(!backend.set_nx("lock", "2".to_string(), 1).await.unwrap());
+        assert_eq!(backend.get("lock").await.unwrap().as_deref(), Some("1"));
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        assert!(backend.set_nx("lock", "3".to_string(), 1).await.unwrap());
+        assert_eq!(backend.get("lock").await.unwrap().as_deref(), Some("3"));
+    }
+
+    #[tokio::test]
+    async fn del_removes_values_and_tags() {
+        let backend = MemoryBackend::new();
+
+        backend.set("value", "1".to_string(), None).await.unwrap();
+        backend.tag("tag", "value", 60).await.unwrap();
+        backend
+            .del(vec![
+                "value".to_string(),
+                "tag".to_string(),
+                "missing".to_string(),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(backend.get("value").await.unwrap(), None);
+        assert!(backend.tagged("tag").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn incr_counts_and_keeps_the_expiry() {
+        let backend = MemoryBackend::new();
+
+        assert_eq!(backend.incr("count").await.unwrap(), 1);
+        backend.expire("count", 1).await.unwrap();
+        assert_eq!(backend.incr("count").await.unwrap(), 2);
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+
+        assert_eq!(backend.incr("count").await.unwrap(), 1);
+        backend.tag("set", "value", 60).await.unwrap();
+        assert!(backend.incr("set").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn subscribers_only_receive_their_channel() {
+        let backend = MemoryBackend::new();
+        let mut messages = backend.subscribe("channel").await.unwrap();
+
+        backend
+            .publish("other", "ignored".to_string())
+            .await
+            .unwrap();
+        backend
+            .publish("channel", "received".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(messages.next().await.as_deref(), Some("received"));
+    }
+}
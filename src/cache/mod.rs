@@ -0,0 +1,782 @@
+use crate::{database::DatabaseError, env::RedisMode};
+use colored::Colorize;
+use futures_util::{StreamExt, stream::BoxStream};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::HashMap;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod memory;
+mod redis;
+
+pub use memory::MemoryBackend;
+pub use redis::RedisBackend;
+
+const LOCAL_CAPACITY: usize = 4096;
+const LOCAL_MAX_TTL: u64 = 60;
+const LOCAL_MAX_SIZE: usize = 64 * 1024;
+const INVALIDATE_CHANNEL: &str = "cache::invalidate";
+const RESUBSCRIBE_MIN_BACKOFF: Duration = Duration::from_secs(1);
+const RESUBSCRIBE_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const STALE_MAX_TTL: u64 = 600;
+const LOCK_TTL: u64 = 30;
+const LOCK_WAIT_ATTEMPTS: usize = 50;
+const LOCK_WAIT_INTERVAL: Duration = Duration::from_millis(100);
+
+type LocalCache = Mutex<lru::LruCache<String, (Instant, String)>>;
+type Flights = Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>;
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    expires: i64,
+    value: T,
+}
+
+struct Flight<'a> {
+    flights: &'a Flights,
+    key: &'a str,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<'a> Flight<'a> {
+    fn new(flights: &'a Flights, key: &'a str) -> Self {
+        let lock = flights
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+
+        Self { flights, key, lock }
+    }
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        let mut flights = self.flights.lock().unwrap();
+
+        if Arc::strong_count(&self.lock) <= 2 {
+            flights.remove(self.key);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CacheError {
+    Redis(rustis::Error),
+    Serialization(serde_json::Error),
+    Backend(String),
+}
+
+impl std::fmt::Display for CacheError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CacheError::Redis(err) => write!(f, "redis error: {}", err),
+            CacheError::Serialization(err) => write!(f, "serialization error: {}", err),
+            CacheError::Backend(err) => write!(f, "backend error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CacheError {}
+
+impl From<rustis::Error> for CacheError {
+    fn from(err: rustis::Error) -> Self {
+        CacheError::Redis(err)
+    }
+}
+
+impl From<serde_json::Error> for CacheError {
+    fn from(err: serde_json::Error) -> Self {
+        CacheError::Serialization(err)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait CacheBackend: Send + Sync {
+    async fn describe(&self) -> String;
+
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError>;
+    async fn set(&self, key: &str, value: String, ttl: Option<u64>) -> Result<(), CacheError>;
+    async fn set_nx(&self, key: &str, value: String, ttl: u64) -> Result<bool, CacheError>;
+    async fn incr(&self, key: &str) -> Result<i64, CacheError>;
+    async fn expire(&self, key: &str, ttl: u64) -> Result<(), CacheError>;
+    async fn del(&self, keys: Vec<String>) -> Result<(), CacheError>;
+
+    async fn tag(&self, tag: &str, key: &str, ttl: u64) -> Result<(), CacheError>;
+    async fn tagged(&self, tag: &str) -> Result<Vec<String>, CacheError>;
+
+    async fn publish(&self, channel: &str, message: String) -> Result<(), CacheError>;
+    async fn subscribe(&self, channel: &str) -> Result<BoxStream<'static, String>, CacheError>;
+}
+
+#[derive(Clone)]
+pub struct Cache {
+    pub backend: Arc<dyn CacheBackend>,
+    local: Arc<LocalCache>,
+    flights: Arc<Flights>,
+    lock: bool,
+}
+
+impl Cache {
+    async fn backend(env: &crate::env::Env) -> Result<Arc<dyn CacheBackend>, CacheError> {
+        Ok(match env.redis_mode {
+            RedisMode::Redis => {
+                Arc::new(RedisBackend::connect(env.redis_url.as_ref().unwrap().clone()).await?)
+            }
+            RedisMode::Sentinel => {
+                Arc::new(RedisBackend::sentinel(env.redis_sentinels.as_ref().unwrap()).await?)
+            }
+            RedisMode::Memory => Arc::new(MemoryBackend::new()),
+        })
+    }
+
+    pub async fn new(env: Arc<crate::env::Env>) -> Result<Self, CacheError> {
+        let start = std::time::Instant::now();
+
+        let instance = Self::with_backend(Self::backend(&env).await?, env.cache_lock).await;
+
+        crate::logger::log(
+            crate::logger::LoggerLevel::Info,
+            format!(
+                "{} connected {}",
+                "cache".bright_yellow(),
+                format!(
+                    "({}, {}ms)",
+                    instance.backend.describe().await,
+                    start.elapsed().as_millis()
+                )
+                .bright_black()
+            ),
+        );
+
+        Ok(instance)
+    }
+
+    pub async fn with_backend(backend: Arc<dyn CacheBackend>, lock: bool) -> Self {
+        let instance = Self {
+            backend,
+            local: Arc::new(Mutex::new(lru::LruCache::new(
+                NonZeroUsize::new(LOCAL_CAPACITY).unwrap(),
+            ))),
+            flights: Arc::new(Mutex::new(HashMap::new())),
+            lock,
+        };
+
+        let mut invalidations = match instance.backend.subscribe(INVALIDATE_CHANNEL).await {
+            Ok(invalidations) => Some(invalidations),
+            Err(err) => {
+                Self::report(&err);
+
+                None
+            }
+        };
+        let backend = Arc::clone(&instance.backend);
+        let local = Arc::clone(&instance.local);
+
+        tokio::spawn(async move {
+            loop {
+                if let Some(mut invalidations) = invalidations.take() {
+                    while let Some(message) = invalidations.next().await {
+                        let mut local = local.lock().unwrap();
+
+                        for key in message.lines() {
+                            local.pop(key);
+                        }
+                    }
+
+                    local.lock().unwrap().clear();
+                }
+
+                let mut backoff = RESUBSCRIBE_MIN_BACKOFF;
+
+                invalidations = Some(loop {
+                    tokio::time::sleep(backoff).await;
+
+                    match backend.subscribe(INVALIDATE_CHANNEL).await {
+                        Ok(invalidations) => break invalidations,
+                        Err(err) => {
+                            Self::report(&err);
+
+                            backoff = (backoff * 2).min(RESUBSCRIBE_MAX_BACKOFF);
+                        }
+                    }
+                });
+
+                local.lock().unwrap().clear();
+
+                crate::logger::log(
+                    crate::logger::LoggerLevel::Info,
+                    format!(
+                        "{} resubscribed to {}",
+                        "cache".bright_yellow(),
+                        INVALIDATE_CHANNEL.cyan()
+                    ),
+                );
+            }
+        });
+
+        instance
+    }
+
+    fn local_get(&self, key: &str) -> Option<String> {
+        let mut local = self.local.lock().unwrap();
+
+        match local.get(key) {
+            Some((expires, value)) if *expires > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                local.pop(key);
+
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn local_set(&self, key: &str, ttl: u64, value: &str) {
+        if value.len() > LOCAL_MAX_SIZE {
+            return;
+        }
+
+        self.local.lock().unwrap().put(
+            key.to_string(),
+            (
+                Instant::now() + Duration::from_secs(ttl.min(LOCAL_MAX_TTL)),
+                value.to_string(),
+            ),
+        );
+    }
+
+    pub fn report(err: &CacheError) {
+        crate::logger::log(
+            crate::logger::LoggerLevel::Error,
+            format!("{} {}", "cache".bright_yellow(), err.to_string().red()),
+        );
+    }
+
+    fn tag_key(tag: &str) -> String {
+        format!("cache::tag::{}", tag)
+    }
+
+    async fn tag(&self, key: &str, ttl: u64, tags: Vec<String>) -> Result<(), CacheError> {
+        for tag in tags {
+            self.backend.tag(&Self::tag_key(&tag), key, ttl).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn invalidate_tag(&self, tag: &str) -> Result<(), CacheError> {
+        let tag = Self::tag_key(tag);
+        let mut keys = self.backend.tagged(&tag).await?;
+        keys.push(tag);
+
+        self.invalidate(keys).await
+    }
+
+    pub async fn invalidate(&self, keys: Vec<String>) -> Result<(), CacheError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        {
+            let mut local = self.local.lock().unwrap();
+
+            for key in &keys {
+                local.pop(key);
+            }
+        }
+
+        let payload = keys.join("\n");
+
+        self.backend.del(keys).await?;
+        self.backend.publish(INVALIDATE_CHANNEL, payload).await?;
+
+        Ok(())
+    }
+
+    async fn remote_get<T: DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<(String, Entry<T>)>, CacheError> {
+        let value = self.backend.get(key).await?;
+
+        Ok(value.and_then(|value| {
+            serde_json::from_str(&value)
+                .ok()
+                .map(|entry| (value, entry))
+        }))
+    }
+
+    async fn acquire(&self, key: &str) -> Result<bool, CacheError> {
+        if !self.lock {
+            return Ok(true);
+        }
+
+        self.backend
+            .set_nx(&format!("cache::lock::{}", key), "1".to_string(), LOCK_TTL)
+            .await
+    }
+
+    async fn release(&self, key: &str) {
+        if self.lock
+            && let Err(err) = self
+                .backend
+                .del(vec![format!("cache::lock::{}", key)])
+                .await
+        {
+            Self::report(&err);
+        }
+    }
+
+    async fn store<T: Serialize>(
+        &self,
+        key: &str,
+        ttl: u64,
+        value: &T,
+        tags: Vec<String>,
+    ) -> Result<(), CacheError> {
+        let serialized = serde_json::to_string(&Entry {
+            expires: chrono::Utc::now().timestamp() + ttl as i64,
+            value,
+        })?;
+        self.local_set(key, ttl, &serialized);
+        self.backend
+            .set(key, serialized, Some(ttl + ttl.min(STALE_MAX_TTL)))
+            .await?;
+
+        self.tag(key, ttl + ttl.min(STALE_MAX_TTL), tags).await
+    }
+
+    async fn compute<T, G, F, Fut>(
+        &self,
+        key: &str,
+        ttl: u64,
+        fn_tags: G,
+        fn_compute: F,
+    ) -> Result<T, DatabaseError>
+    where
+        T: Serialize + DeserializeOwned,
+        G: FnOnce(&T) -> Vec<String>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, DatabaseError>>,
+    {
+        let result = fn_compute().await?;
+        let tags = fn_tags(&result);
+
+        if let Err(err) = self.store(key, ttl, &result, tags).await {
+            Self::report(&err);
+        }
+
+        Ok(result)
+    }
+
+    async fn fresh<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, CacheError> {
+        if let Some(value) = self.local_get(key)
+            && let Ok(entry) = serde_json::from_str::<Entry<T>>(&value)
+        {
+            return Ok(Some(entry.value));
+        }
+
+        let (value, entry) = match self.remote_get::<T>(key).await? {
+            Some(remote) => remote,
+            None => return Ok(None),
+        };
+        let remaining = entry.expires - chrono::Utc::now().timestamp();
+
+        if remaining > 0 {
+            self.local_set(key, remaining as u64, &value);
+
+            Ok(Some(entry.value))
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline(always)]
+    pub async fn cached<T, F, Fut>(
+        &self,
+        key: &str,
+        ttl: u64,
+        fn_compute: F,
+    ) -> Result<T, DatabaseError>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, DatabaseError>> + Send + 'static,
+    {
+        self.cached_tagged(key, ttl, |_| Vec::new(), fn_compute)
+            .await
+    }
+
+    pub async fn cached_tagged<T, G, F, Fut>(
+        &self,
+        key: &str,
+        ttl: u64,
+        fn_tags: G,
+        fn_compute: F,
+    ) -> Result<T, DatabaseError>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + 'static,
+        G: FnOnce(&T) -> Vec<String> + Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, DatabaseError>> + Send + 'static,
+    {
+        if let Some(value) = self.local_get(key)
+            && let Ok(entry) = serde_json::from_str::<Entry<T>>(&value)
+        {
+            return Ok(entry.value);
+        }
+
+        let flight = Flight::new(&self.flights, key);
+
+        let remote = match self.remote_get::<T>(key).await {
+            Ok(remote) => remote,
+            Err(err) => {
+                Self::report(&err);
+
+                return fn_compute().await;
+            }
+        };
+
+        match remote {
+            Some((value, entry)) => {
+                let remaining = entry.expires - chrono::Utc::now().timestamp();
+                if remaining > 0 {
+                    self.local_set(key, remaining as u64, &value);
+
+                    return Ok(entry.value);
+                }
+
+                let guard = match flight.lock.clone().try_lock_owned() {
+                    Ok(guard) => guard,
+                    Err(_) => return Ok(entry.value),
+                };
+
+                match self.acquire(key).await {
+                    Ok(true) => {}
+                    Ok(false) => return Ok(entry.value),
+                    Err(err) => {
+                        Self::report(&err);
+
+                        return Ok(entry.value);
+                    }
+                }
+
+                let cache = self.clone();
+                let key = key.to_string();
+
+                tokio::spawn(async move {
+                    let _flight = Flight::new(&cache.flights, &key);
+
+                    if let Err(err) = cache.compute(&key, ttl, fn_tags, fn_compute).await {
+                        err.report();
+                    }
+
+                    cache.release(&key).await;
+                    drop(guard);
+                });
+
+                Ok(entry.value)
+            }
+            None => {
+                let _guard = flight.lock.lock().await;
+
+                match self.fresh(key).await {
+                    Ok(Some(value)) => return Ok(value),
+                    Ok(None) => {}
+                    Err(err) => {
+                        Self::report(&err);
+
+                        return fn_compute().await;
+                    }
+                }
+
+                match self.acquire(key).await {
+                    Ok(true) => {
+                        let result = self.compute(key, ttl, fn_tags, fn_compute).await;
+                        self.release(key).await;
+
+                        return result;
+                    }
+                    Ok(false) => {}
+                    Err(err) => {
+                        Self::report(&err);
+
+                        return fn_compute().await;
+                    }
+                }
+
+                for _ in 0..LOCK_WAIT_ATTEMPTS {
+                    tokio::time::sleep(LOCK_WAIT_INTERVAL).await;
+
+                    match self.remote_get::<T>(key).await {
+                        Ok(Some((value, entry))) => {
+                            let remaining = entry.expires - chrono::Utc::now().timestamp();
+                            if remaining > 0 {
+                                self.local_set(key, remaining as u64, &value);
+                            }
+
+                            return Ok(entry.value);
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            Self::report(&err);
+
+                            break;
+                        }
+                    }
+                }
+
+                self.compute(key, ttl, fn_tags, fn_compute).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Cache, CacheBackend, Entry, INVALIDATE_CHANNEL, LOCK_WAIT_ATTEMPTS, LOCK_WAIT_INTERVAL,
+        MemoryBackend, RESUBSCRIBE_MIN_BACKOFF,
+    };
+    use std::sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    };
+    use std::time::Duration;
+
+    async fn cache() -> Cache {
+        Cache::with_backend(Arc::new(MemoryBackend::new()), true).await
+    }
+
+    fn entry(expires: i64, value: u32) -> String {
+        serde_json::to_string(&Entry {
+            expires: chrono::Utc::now().timestamp() + expires,
+            value,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_compute_once() {
+        let cache = cache().await;
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let results = futures_util::future::join_all((0..16).map(|_| {
+            let calls = Arc::clone(&calls);
+
+            cache.cached("key", 60, move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+
+                Ok(42u32)
+            })
+        }))
+        .await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results.into_iter().all(|result| result.unwrap() == 42));
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_served_while_one_refresh_runs() {
+        let cache = cache().await;
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        cache
+            .backend
+            .set("key", entry(-10, 1), Some(600))
+            .await
+            .unwrap();
+
+        let results = futures_util::future::join_all((0..8).map(|_| {
+            let calls = Arc::clone(&calls);
+
+            cache.cached("key", 60, move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+
+                Ok(2u32)
+            })
+        }))
+        .await;
+
+        assert!(results.into_iter().all(|result| result.unwrap() == 1));
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            cache
+                .cached("key", 60, || async { Ok(3u32) })
+                .await
+                .unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn lock_waiters_read_the_value_stored_by_the_holder() {
+        let cache = cache().await;
+
+        cache
+            .backend
+            .set_nx("cache::lock::key", "1".to_string(), 30)
+            .await
+            .unwrap();
+
+        let backend = Arc::clone(&cache.backend);
+        tokio::spawn(async move {
+            tokio::time::sleep(LOCK_WAIT_INTERVAL * 2).await;
+
+            backend.set("key", entry(60, 1), Some(120)).await.unwrap();
+        });
+
+        assert_eq!(
+            cache
+                .cached("key", 60, || async { Ok(2u32) })
+                .await
+                .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn lock_waiters_compute_when_the_holder_never_stores() {
+        let cache = cache().await;
+
+        cache
+            .backend
+            .set_nx("cache::lock::key", "1".to_string(), 30)
+            .await
+            .unwrap();
+
+        let start = std::time::Instant::now();
+
+        assert_eq!(
+            cache
+                .cached("key", 60, || async { Ok(2u32) })
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(start.elapsed() >= LOCK_WAIT_INTERVAL * LOCK_WAIT_ATTEMPTS as u32);
+    }
+
+    #[tokio::test]
+    async fn lock_waiters_keep_the_remote_expiry_locally() {
+        let cache = cache().await;
+
+        cache
+            .backend
+            .set_nx("cache::lock::key", "1".to_string(), 30)
+            .await
+            .unwrap();
+
+        let backend = Arc::clone(&cache.backend);
+        tokio::spawn(async move {
+            tokio::time::sleep(LOCK_WAIT_INTERVAL * 2).await;
+
+            backend.set("key", entry(2, 1), Some(120)).await.unwrap();
+        });
+
+        assert_eq!(
+            cache
+                .cached("key", 3600, || async { Ok(2u32) })
+                .await
+                .unwrap(),
+            1
+        );
+        assert!(cache.local_get("key").is_some());
+
+        tokio::time::sleep(Duration::from_millis(2100)).await;
+
+        assert!(cache.local_get("key").is_none());
+    }
+
+    #[tokio::test]
+    async fn invalidate_tag_drops_every_tagged_key() {
+        let cache = cache().await;
+
+        for key in ["first", "second"] {
+            cache
+                .cached_tagged(key, 60, |_| vec!["tag".to_string()], || async { Ok(1u32) })
+                .await
+                .unwrap();
+        }
+        cache
+            .cached("untagged", 60, || async { Ok(1u32) })
+            .await
+            .unwrap();
+
+        cache.invalidate_tag("tag").await.unwrap();
+
+        for key in ["first", "second"] {
+            assert!(cache.local_get(key).is_none());
+            assert!(cache.backend.get(key).await.unwrap().is_none());
+            assert_eq!(
+                cache
+                    .cached_tagged(key, 60, |_| vec!["tag".to_string()], || async { Ok(2u32) })
+                    .await
+                    .unwrap(),
+                2
+            );
+        }
+
+        assert!(cache.backend.get("untagged").await.unwrap().is_some());
+        assert_eq!(
+            cache.backend.tagged("cache::tag::tag").await.unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn published_invalidations_clear_other_local_tiers() {
+        let backend: Arc<dyn CacheBackend> = Arc::new(MemoryBackend::new());
+        let first = Cache::with_backend(Arc::clone(&backend), true).await;
+        let second = Cache::with_backend(backend, true).await;
+
+        first
+            .cached("key", 60, || async { Ok(1u32) })
+            .await
+            .unwrap();
+        assert!(first.local_get("key").is_some());
+
+        second.invalidate(vec!["key".to_string()]).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(first.local_get("key").is_none());
+    }
+
+    #[tokio::test]
+    async fn local_tier_is_flushed_when_the_subscription_drops() {
+        let cache = cache().await;
+
+        cache.local_set("key", 60, "1");
+
+        for _ in 0..2048 {
+            cache.backend.publish("other", String::new()).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(cache.local_get("key").is_none());
+
+        cache.local_set("key", 60, "1");
+        tokio::time::sleep(RESUBSCRIBE_MIN_BACKOFF + Duration::from_millis(200)).await;
+
+        assert!(cache.local_get("key").is_none());
+
+        cache.local_set("key", 60, "1");
+        cache
+            .backend
+            .publish(INVALIDATE_CHANNEL, "key".to_string())
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(cache.local_get("key").is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use super::{CacheBackend, CacheError};
+use futures_util::{StreamExt, stream::BoxStream};
+use rustis::{
+    client::Client,
+    commands::{
+        ExpireOption, GenericCommands, PubSubCommands, SetCommands, SetCondition, SetExpiration,
+        StringCommands,
+    },
+    resp::cmd,
+};
+
+pub struct RedisBackend {
+    client: Client,
+    url: String,
+}
+
+impl RedisBackend {
+    pub async fn connect(url: String) -> Result<Self, CacheError> {
+        Ok(Self {
+            client: Client::connect(url.as_str()).await?,
+            url,
+        })
+    }
+
+    pub async fn sentinel(sentinels: &[String]) -> Result<Self, CacheError> {
+        Self::connect(format!(
+            "redis-sentinel://{}/mymaster/0",
+            sentinels.join(",")
+        ))
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl CacheBackend for RedisBackend {
+    async fn describe(&self) -> String {
+        let info = match self.client.send(cmd("INFO"), None).await {
+            Ok(info) => String::from_utf8_lossy(info.as_bytes()).to_string(),
+            Err(_) => return "redis".to_string(),
+        };
+
+        match info
+            .lines()
+            .find_map(|line| line.strip_prefix("redis_version:"))
+        {
+            Some(version) => format!("redis@{}", version),
+            None => "redis".to_string(),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        Ok(self.client.get(key).await?)
+    }
+
+    async fn set(&self, key: &str, value: String, ttl: Option<u64>) -> Result<(), CacheError> {
+        self.client
+            .set_with_options(
+                key,
+                value,
+                SetCondition::None,
+                match ttl {
+                    Some(ttl) => SetExpiration::Ex(ttl),
+                    None => SetExpiration::None,
+                },
+                false,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn set_nx(&self, key: &str, value: String, ttl: u64) -> Result<bool, CacheError> {
+        Ok(self
+            .client
+            .set_with_options(key, value, SetCondition::NX, SetExpiration::Ex(ttl), false)
+            .await?)
+    }
+
+    async fn incr(&self, key: &str) -> Result<i64, CacheError> {
+        Ok(self.client.incr(key).await?)
+    }
+
+    async fn expire(&self, key: &str, ttl: u64) -> Result<(), CacheError> {
+        self.client.expire(key, ttl, ExpireOption::None).await?;
+
+        Ok(())
+    }
+
+    async fn del(&self, keys: Vec<String>) -> Result<(), CacheError> {
+        if !keys.is_empty() {
+            self.client.del(keys).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn tag(&self, tag: &str, key: &str, ttl: u64) -> Result<(), CacheError> {
+        self.client.sadd(tag, key).await?;
+        if self.client.ttl(tag).await? < ttl as i64 {
+            self.client.expire(tag, ttl, ExpireOption::None).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn tagged(&self, tag: &str) -> Result<Vec<String>, CacheError> {
+        Ok(self.client.smembers(tag).await?)
+    }
+
+    async fn publish(&self, channel: &str, message: String) -> Result<(), CacheError> {
+        self.client.publish(channel, message).await?;
+
+        Ok(())
+    }
+
+    async fn subscribe(&self, channel: &str) -> Result<BoxStream<'static, String>, CacheError> {
+        let stream = Client::connect(self.url.as_str())
+            .await?
+            .subscribe(channel.to_string())
+            .await?;
+
+        Ok(stream
+            .take_while(|message| std::future::ready(message.is_ok()))
+            .filter_map(|message| async move {
+                message
+                    .ok()
+                    .map(|message| String::from_utf8_lossy(&message.payload).to_string())
+            })
+            .boxed())
+    }
+}
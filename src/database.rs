@@ -1,13 +1,97 @@
+use axum::http::StatusCode;
 use colored::Colorize;
 use sqlx::postgres::PgPoolOptions;
 use std::sync::Arc;
 
+#[derive(Debug)]
+pub enum DatabaseError {
+    Unavailable(sqlx::Error),
+    Conflict(sqlx::Error),
+    Internal(sqlx::Error),
+}
+
+impl DatabaseError {
+    pub fn report(&self) {
+        crate::logger::log(
+            crate::logger::LoggerLevel::Error,
+            format!("{} {}", "database".bright_cyan(), self.to_string().red()),
+        );
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            DatabaseError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            DatabaseError::Conflict(_) => StatusCode::CONFLICT,
+            DatabaseError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            DatabaseError::Unavailable(_) => "database unavailable",
+            DatabaseError::Conflict(_) => "resource conflicts with an existing entry",
+            DatabaseError::Internal(_) => "database error",
+        }
+    }
+}
+
+impl std::fmt::Display for DatabaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DatabaseError::Unavailable(err) => write!(f, "database unavailable: {}", err),
+            DatabaseError::Conflict(err) => write!(f, "database conflict: {}", err),
+            DatabaseError::Internal(err) => write!(f, "database error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DatabaseError {}
+
+impl From<sqlx::Error> for DatabaseError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::WorkerCrashed => DatabaseError::Unavailable(err),
+            sqlx::Error::Database(database)
+                if database
+                    .code()
+                    .is_some_and(|code| code.starts_with("08") || code.starts_with("57P")) =>
+            {
+                DatabaseError::Unavailable(err)
+            }
+            sqlx::Error::Database(database)
+                if database.is_unique_violation()
+                    || database.is_foreign_key_violation()
+                    || database.is_check_violation() =>
+            {
+                DatabaseError::Conflict(err)
+            }
+            _ => DatabaseError::Internal(err),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Database {
     write: sqlx::PgPool,
     read: Option<sqlx::PgPool>,
 }
 
 impl Database {
+    #[cfg(test)]
+    pub fn lazy(url: &str) -> Self {
+        Self {
+            write: PgPoolOptions::new()
+                .acquire_timeout(std::time::Duration::from_secs(1))
+                .connect_lazy(url)
+                .unwrap(),
+            read: None,
+        }
+    }
+
     pub async fn new(env: Arc<crate::env::Env>) -> Self {
         let start = std::time::Instant::now();
 
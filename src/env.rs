@@ -4,6 +4,7 @@ use dotenvy::dotenv;
 pub enum RedisMode {
     Redis,
     Sentinel,
+    Memory,
 }
 
 #[derive(Clone)]
@@ -11,6 +12,7 @@ pub struct Env {
     pub redis_url: Option<String>,
     pub redis_sentinels: Option<Vec<String>>,
     pub redis_mode: RedisMode,
+    pub cache_lock: bool,
 
     pub sentry_url: Option<String>,
     pub database_migrate: bool,
@@ -18,6 +20,13 @@ pub struct Env {
     pub database_url: String,
     pub database_url_primary: Option<String>,
 
+    pub fetchers_enabled: bool,
+    pub fetchers_vanilla_url: String,
+    pub fetchers_paper_url: String,
+    pub fetchers_purpur_url: String,
+    pub fetchers_fabric_url: String,
+    pub fetchers_legacy_fabric_url: String,
+
     pub github_client_id: String,
     pub github_client_secret: String,
 
@@ -48,6 +57,7 @@ impl Env {
         {
             "redis" => RedisMode::Redis,
             "sentinel" => RedisMode::Sentinel,
+            "memory" => RedisMode::Memory,
             _ => panic!("Invalid REDIS_MODE"),
         };
 
@@ -59,10 +69,10 @@ impl Env {
                         .trim_matches('"')
                         .to_string(),
                 ),
-                RedisMode::Sentinel => None,
+                RedisMode::Sentinel | RedisMode::Memory => None,
             },
             redis_sentinels: match redis_mode {
-                RedisMode::Redis => None,
+                RedisMode::Redis | RedisMode::Memory => None,
                 RedisMode::Sentinel => Some(
                     std::env::var("REDIS_SENTINELS")
                         .expect("REDIS_SENTINELS is required")
@@ -73,6 +83,11 @@ impl Env {
                 ),
             },
             redis_mode,
+            cache_lock: std::env::var("CACHE_LOCK")
+                .unwrap_or("false".to_string())
+                .trim_matches('"')
+                .parse()
+                .unwrap(),
 
             sentry_url: std::env::var("SENTRY_URL")
                 .ok()
@@ -95,6 +110,32 @@ impl Env {
                 .ok()
                 .map(|s| s.trim_matches('"').to_string()),
 
+            fetchers_enabled: std::env::var("FETCHERS_ENABLED")
+                .unwrap_or("false".to_string())
+                .trim_matches('"')
+                .parse()
+                .unwrap(),
+            fetchers_vanilla_url: std::env::var("FETCHERS_VANILLA_URL")
+                .unwrap_or("https://piston-meta.mojang.com".to_string())
+                .trim_matches('"')
+                .to_string(),
+            fetchers_paper_url: std::env::var("FETCHERS_PAPER_URL")
+                .unwrap_or("https://api.papermc.io".to_string())
+                .trim_matches('"')
+                .to_string(),
+            fetchers_purpur_url: std::env::var("FETCHERS_PURPUR_URL")
+                .unwrap_or("https://api.purpurmc.org".to_string())
+                .trim_matches('"')
+                .to_string(),
+            fetchers_fabric_url: std::env::var("FETCHERS_FABRIC_URL")
+                .unwrap_or("https://meta.fabricmc.net".to_string())
+                .trim_matches('"')
+                .to_string(),
+            fetchers_legacy_fabric_url: std::env::var("FETCHERS_LEGACY_FABRIC_URL")
+                .unwrap_or("https://meta.legacyfabric.net".to_string())
+                .trim_matches('"')
+                .to_string(),
+
             github_client_id: std::env::var("GITHUB_CLIENT_ID")
                 .unwrap_or("".to_string())
                 .trim_matches('"')
@@ -0,0 +1,119 @@
+use super::{Fetched, Fetcher, Known, jar_installation};
+use crate::models::{build::Build, r#type::ServerType};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct MetaVersion {
+    version: String,
+    stable: bool,
+}
+
+fn loader_build_number(version: &str) -> Option<i32> {
+    let mut parts = version.split('+').next()?.split('.');
+    let major: i32 = parts.next()?.parse().ok()?;
+    let minor: i32 = parts.next()?.parse().ok()?;
+    let patch: i32 = parts.next().unwrap_or("0").parse().ok()?;
+
+    if parts.next().is_some() || !(0..1000).contains(&minor) || !(0..1000).contains(&patch) {
+        return None;
+    }
+
+    major
+        .checked_mul(1_000_000)?
+        .checked_add(minor * 1000 + patch)
+}
+
+pub struct FabricFetcher {
+    base_url: String,
+    r#type: ServerType,
+}
+
+impl FabricFetcher {
+    pub fn new(base_url: &str, r#type: ServerType) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            r#type,
+        }
+    }
+
+    async fn versions(
+        &self,
+        client: &reqwest::Client,
+        kind: &str,
+    ) -> Result<Vec<MetaVersion>, reqwest::Error> {
+        client
+            .get(format!("{}/v2/versions/{}", self.base_url, kind))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetcher for FabricFetcher {
+    fn r#type(&self) -> ServerType {
+        self.r#type
+    }
+
+    async fn fetch(
+        &self,
+        client: &reqwest::Client,
+        known: &Known,
+    ) -> Result<Fetched, reqwest::Error> {
+        let games = self.versions(client, "game").await?;
+        let loaders: Vec<MetaVersion> = self
+            .versions(client, "loader")
+            .await?
+            .into_iter()
+            .filter(|loader| loader.stable)
+            .collect();
+        let installer = self
+            .versions(client, "installer")
+            .await?
+            .into_iter()
+            .find(|installer| installer.stable);
+
+        let mut fetched = Fetched::default();
+
+        let (loader, installer) = match (loaders.first(), installer) {
+            (Some(loader), Some(installer)) => (loader, installer),
+            _ => return Ok(fetched),
+        };
+        let build_number = match loader_build_number(&loader.version) {
+            Some(build_number) => build_number,
+            None => return Ok(fetched),
+        };
+
+        for game in games.into_iter().filter(|game| game.stable) {
+            if known.contains_pair(&game.version, &loader.version) {
+                continue;
+            }
+
+            let url = format!(
+                "{}/v2/versions/loader/{}/{}/{}/server/jar",
+                self.base_url, game.version, loader.version, installer.version
+            );
+
+            fetched.builds.push(Build {
+                id: 0,
+                version_id: Some(game.version),
+                project_version_id: Some(loader.version.clone()),
+                r#type: self.r#type,
+                experimental: false,
+                name: String::new(),
+                build_number,
+                jar_url: Some(url.clone()),
+                jar_size: None,
+                zip_url: None,
+                zip_size: None,
+                installation: jar_installation(&url, 0),
+                changes: Vec::new(),
+                created: Some(chrono::Utc::now().naive_utc()),
+            });
+        }
+
+        Ok(fetched)
+    }
+}
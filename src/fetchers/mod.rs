@@ -0,0 +1,582 @@
+use crate::models::{
+    build::{Build, InstallationStep, InstallationStepDownload},
+    r#type::ServerType,
+    version::MinecraftVersion,
+};
+use colored::Colorize;
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+mod fabric;
+mod paper;
+mod purpur;
+mod vanilla;
+
+const LOCK_KEY: &str = "mcjars_api::fetchers::lock";
+const LOCK_TTL: u64 = 900;
+
+#[derive(Default)]
+pub struct Fetched {
+    pub versions: Vec<MinecraftVersion>,
+    pub builds: Vec<Build>,
+    pub failed: Vec<(String, reqwest::Error)>,
+}
+
+#[derive(Default)]
+pub struct Known {
+    builds: HashSet<(String, i32)>,
+    pairs: HashSet<(String, String)>,
+}
+
+impl Known {
+    pub fn new(rows: Vec<(Option<String>, Option<String>, i32)>) -> Self {
+        let mut known = Self::default();
+
+        for (version_id, project_version_id, build_number) in rows {
+            known.insert(version_id, project_version_id, build_number);
+        }
+
+        known
+    }
+
+    fn insert(
+        &mut self,
+        version_id: Option<String>,
+        project_version_id: Option<String>,
+        build_number: i32,
+    ) {
+        if let (Some(version_id), Some(project_version_id)) = (&version_id, &project_version_id) {
+            self.pairs
+                .insert((version_id.clone(), project_version_id.clone()));
+        }
+
+        if let Some(identifier) = version_id.or(project_version_id) {
+            self.builds.insert((identifier, build_number));
+        }
+    }
+
+    pub fn contains(&self, identifier: &str, build_number: i32) -> bool {
+        self.builds
+            .contains(&(identifier.to_string(), build_number))
+    }
+
+    pub fn contains_pair(&self, version_id: &str, project_version_id: &str) -> bool {
+        self.pairs
+            .contains(&(version_id.to_string(), project_version_id.to_string()))
+    }
+
+    fn contains_build(&self, build: &Build) -> bool {
+        match (&build.version_id, &build.project_version_id) {
+            (Some(version_id), Some(project_version_id)) => {
+                self.contains_pair(version_id, project_version_id)
+            }
+            (version_id, project_version_id) => version_id
+                .as_ref()
+                .or(project_version_id.as_ref())
+                .is_some_and(|identifier| self.contains(identifier, build.build_number)),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+pub trait Fetcher: Send + Sync {
+    fn r#type(&self) -> ServerType;
+
+    async fn fetch(
+        &self,
+        client: &reqwest::Client,
+        known: &Known,
+    ) -> Result<Fetched, reqwest::Error>;
+}
+
+pub fn jar_installation(url: &str, size: u64) -> Vec<Vec<InstallationStep>> {
+    vec![vec![InstallationStep::Download(InstallationStepDownload {
+        url: url.to_string(),
+        file: "server.jar".to_string(),
+        size,
+        sha256: None,
+    })]]
+}
+
+pub struct FetcherManager {
+    database: Arc<crate::database::Database>,
+    cache: Arc<crate::cache::Cache>,
+    client: reqwest::Client,
+    fetchers: Vec<Box<dyn Fetcher>>,
+}
+
+impl FetcherManager {
+    pub fn new(
+        env: &crate::env::Env,
+        database: Arc<crate::database::Database>,
+        cache: Arc<crate::cache::Cache>,
+    ) -> Self {
+        Self {
+            database,
+            cache,
+            client: reqwest::Client::builder()
+                .user_agent("MCJars API https://mcjars.app")
+                .timeout(Duration::from_secs(300))
+                .build()
+                .unwrap(),
+            fetchers: vec![
+                Box::new(vanilla::VanillaFetcher::new(&env.fetchers_vanilla_url)),
+                Box::new(paper::PaperFetcher::new(
+                    &env.fetchers_paper_url,
+                    ServerType::Paper,
+                    "paper",
+                )),
+                Box::new(paper::PaperFetcher::new(
+                    &env.fetchers_paper_url,
+                    ServerType::Folia,
+                    "folia",
+                )),
+                Box::new(paper::PaperFetcher::new(
+                    &env.fetchers_paper_url,
+                    ServerType::Velocity,
+                    "velocity",
+                )),
+                Box::new(paper::PaperFetcher::new(
+                    &env.fetchers_paper_url,
+                    ServerType::Waterfall,
+                    "waterfall",
+                )),
+                Box::new(purpur::PurpurFetcher::new(&env.fetchers_purpur_url)),
+                Box::new(fabric::FabricFetcher::new(
+                    &env.fetchers_fabric_url,
+                    ServerType::Fabric,
+                )),
+                Box::new(fabric::FabricFetcher::new(
+                    &env.fetchers_legacy_fabric_url,
+                    ServerType::LegacyFabric,
+                )),
+            ],
+        }
+    }
+
+    pub async fn process(&self) {
+        let locked = self
+            .cache
+            .backend
+            .set_nx(LOCK_KEY, "1".to_string(), LOCK_TTL)
+            .await;
+        match locked {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(err) => {
+                crate::cache::Cache::report(&err);
+
+                return;
+            }
+        }
+
+        let cache = self.cache.clone();
+        let renewal = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(LOCK_TTL / 3)).await;
+
+                if let Err(err) = cache.backend.expire(LOCK_KEY, LOCK_TTL).await {
+                    crate::cache::Cache::report(&err);
+                }
+            }
+        });
+
+        for fetcher in self.fetchers.iter() {
+            let r#type = fetcher.r#type();
+            let mut known = match Build::all_identifiers_for_type(&self.database, r#type).await {
+                Ok(rows) => Known::new(rows),
+                Err(err) => {
+                    err.report();
+
+                    continue;
+                }
+            };
+
+            let fetched = match fetcher.fetch(&self.client, &known).await {
+                Ok(fetched) => fetched,
+                Err(err) => {
+                    crate::logger::log(
+                        crate::logger::LoggerLevel::Error,
+                        format!(
+                            "{} failed to fetch {}: {}",
+                            "fetchers".bright_blue(),
+                            r#type.to_string().cyan(),
+                            err
+                        ),
+                    );
+
+                    continue;
+                }
+            };
+
+            for (version, err) in fetched.failed {
+                crate::logger::log(
+                    crate::logger::LoggerLevel::Error,
+                    format!(
+                        "{} failed to fetch {} {}: {}",
+                        "fetchers".bright_blue(),
+                        r#type.to_string().cyan(),
+                        version.cyan(),
+                        err
+                    ),
+                );
+            }
+
+            let mut versions = match MinecraftVersion::all_ids(&self.database).await {
+                Ok(versions) => versions,
+                Err(err) => {
+                    err.report();
+
+                    continue;
+                }
+            };
+            for version in fetched.versions {
+                if versions.contains(&version.id) {
+                    continue;
+                }
+
+                if let Err(err) = version.save(&self.database).await {
+                    err.report();
+
+                    continue;
+                }
+                if let Err(err) = version.invalidate_cache(&self.cache).await {
+                    crate::cache::Cache::report(&err);
+                }
+                versions.insert(version.id);
+            }
+
+            let mut created = 0;
+            for build in fetched.builds {
+                if build.version_id.is_none() && build.project_version_id.is_none()
+                    || known.contains_build(&build)
+                {
+                    continue;
+                }
+
+                if let Some(version_id) = &build.version_id
+                    && !versions.contains(version_id)
+                {
+                    continue;
+                }
+
+                let mut build = build;
+                let hashes = match build.hash_artifacts(&self.client, &[]).await {
+                    Ok(hashes) => hashes,
+                    Err(err) => {
+                        crate::logger::log(
+                            crate::logger::LoggerLevel::Error,
+                            format!(
+                                "{} failed to hash {} build: {}",
+                                "fetchers".bright_blue(),
+                                r#type.to_string().cyan(),
+                                err
+                            ),
+                        );
+
+                        continue;
+                    }
+                };
+
+                let build = match Build::new(&self.database, &build, &hashes).await {
+                    Ok(build) => build,
+                    Err(err) => {
+                        err.report();
+
+                        continue;
+                    }
+                };
+                if let Err(err) = build.invalidate_cache(&self.cache).await {
+                    crate::cache::Cache::report(&err);
+                }
+
+                known.insert(
+                    build.version_id,
+                    build.project_version_id,
+                    build.build_number,
+                );
+                created += 1;
+            }
+
+            if created > 0 {
+                crate::logger::log(
+                    crate::logger::LoggerLevel::Info,
+                    format!(
+                        "{} discovered {} new {} builds",
+                        "fetchers".bright_blue(),
+                        created.to_string().cyan(),
+                        r#type.to_string().cyan()
+                    ),
+                );
+            }
+        }
+
+        renewal.abort();
+
+        if let Err(err) = self.cache.backend.del(vec![LOCK_KEY.to_string()]).await {
+            crate::cache::Cache::report(&err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fetcher, Known, fabric, paper, purpur, vanilla};
+    use crate::models::{r#type::ServerType, version::VersionType};
+    use axum::{Json, Router, routing::get};
+    use serde_json::json;
+
+    async fn serve(router: impl FnOnce(String) -> Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let router = router(url.clone());
+
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        url
+    }
+
+    #[tokio::test]
+    async fn vanilla_fetches_releases_and_snapshots() {
+        let url = serve(|url| {
+            Router::new()
+                .route(
+                    "/mc/game/version_manifest_v2.json",
+                    get(move || async move {
+                        Json(json!({
+                            "versions": [
+                                {
+                                    "id": "1.21.4",
+                                    "type": "release",
+                                    "url": format!("{}/v1/1.21.4.json", url),
+                                    "releaseTime": "2024-12-03T10:12:57+00:00",
+                                },
+                                {
+                                    "id": "24w46a",
+                                    "type": "snapshot",
+                                    "url": format!("{}/v1/24w46a.json", url),
+                                    "releaseTime": "2024-11-13T13:09:12+00:00",
+                                },
+                                {
+                                    "id": "1.21.3",
+                                    "type": "release",
+                                    "url": format!("{}/v1/1.21.3.json", url),
+                                    "releaseTime": "2024-10-23T12:28:15+00:00",
+                                },
+                                {
+                                    "id": "b1.7.3",
+                                    "type": "old_beta",
+                                    "url": format!("{}/v1/b1.7.3.json", url),
+                                    "releaseTime": "2011-07-07T22:00:00+00:00",
+                                },
+                            ]
+                        }))
+                    }),
+                )
+                .route(
+                    "/v1/1.21.4.json",
+                    get(|| async {
+                        Json(json!({
+                            "downloads": {
+                                "server": { "url": "https://example.com/server.jar", "size": 1024 },
+                            },
+                            "javaVersion": { "majorVersion": 21 },
+                        }))
+                    }),
+                )
+                .route(
+                    "/v1/24w46a.json",
+                    get(|| async {
+                        Json(json!({
+                            "downloads": {},
+                            "javaVersion": { "majorVersion": 21 },
+                        }))
+                    }),
+                )
+        })
+        .await;
+
+        let fetched = vanilla::VanillaFetcher::new(&url)
+            .fetch(&reqwest::Client::new(), &Known::default())
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.versions.len(), 2);
+        assert_eq!(fetched.versions[0].java, 21);
+        assert!(fetched.versions[0].supported);
+        assert!(fetched.versions[1].r#type == VersionType::Snapshot);
+        assert!(!fetched.versions[1].supported);
+        assert_eq!(fetched.versions[1].java, 21);
+        assert_eq!(fetched.failed.len(), 1);
+        assert_eq!(fetched.failed[0].0, "1.21.3");
+
+        assert_eq!(fetched.builds.len(), 1);
+        assert_eq!(fetched.builds[0].version_id.as_deref(), Some("1.21.4"));
+        assert_eq!(fetched.builds[0].jar_size, Some(1024));
+        assert!(!fetched.builds[0].experimental);
+    }
+
+    #[tokio::test]
+    async fn paper_skips_known_builds() {
+        let url = serve(|_| {
+            Router::new()
+                .route(
+                    "/v2/projects/paper",
+                    get(|| async { Json(json!({ "versions": ["1.21.4"] })) }),
+                )
+                .route(
+                    "/v2/projects/paper/versions/1.21.4/builds",
+                    get(|| async {
+                        Json(json!({
+                            "builds": [
+                                {
+                                    "build": 1,
+                                    "time": "2024-12-03T10:12:57Z",
+                                    "channel": "experimental",
+                                    "changes": [],
+                                    "downloads": {
+                                        "application": { "name": "paper-1.21.4-1.jar" },
+                                    },
+                                },
+                                {
+                                    "build": 2,
+                                    "time": "2024-12-04T10:12:57Z",
+                                    "channel": "default",
+                                    "changes": [{ "summary": "Fix things" }],
+                                    "downloads": {
+                                        "application": { "name": "paper-1.21.4-2.jar" },
+                                    },
+                                },
+                            ]
+                        }))
+                    }),
+                )
+        })
+        .await;
+
+        let fetched = paper::PaperFetcher::new(&url, ServerType::Paper, "paper")
+            .fetch(
+                &reqwest::Client::new(),
+                &Known::new(vec![(Some("1.21.4".to_string()), None, 1)]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.builds.len(), 1);
+        assert_eq!(fetched.builds[0].build_number, 2);
+        assert_eq!(fetched.builds[0].changes, vec!["Fix things".to_string()]);
+        assert_eq!(
+            fetched.builds[0].jar_url,
+            Some(format!(
+                "{}/v2/projects/paper/versions/1.21.4/builds/2/downloads/paper-1.21.4-2.jar",
+                url
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn purpur_skips_failed_builds() {
+        let url = serve(|_| {
+            Router::new()
+                .route(
+                    "/v2/purpur",
+                    get(|| async { Json(json!({ "versions": ["1.21.4"] })) }),
+                )
+                .route(
+                    "/v2/purpur/1.21.4",
+                    get(|| async {
+                        Json(json!({
+                            "builds": {
+                                "all": [
+                                    { "build": "2380", "result": "FAILURE", "timestamp": 1733234567000i64 },
+                                    {
+                                        "build": "2381",
+                                        "result": "SUCCESS",
+                                        "timestamp": 1733234567000i64,
+                                        "commits": [{ "description": "Update upstream" }],
+                                    },
+                                ]
+                            }
+                        }))
+                    }),
+                )
+        })
+        .await;
+
+        let fetched = purpur::PurpurFetcher::new(&url)
+            .fetch(&reqwest::Client::new(), &Known::default())
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.builds.len(), 1);
+        assert_eq!(fetched.builds[0].build_number, 2381);
+        assert_eq!(
+            fetched.builds[0].changes,
+            vec!["Update upstream".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn fabric_keys_builds_on_the_game_and_loader_versions() {
+        let url = serve(|_| {
+            Router::new()
+                .route(
+                    "/v2/versions/game",
+                    get(|| async {
+                        Json(json!([
+                            { "version": "1.21.4", "stable": true },
+                            { "version": "24w46a", "stable": false },
+                        ]))
+                    }),
+                )
+                .route(
+                    "/v2/versions/loader",
+                    get(|| async {
+                        Json(json!([
+                            { "version": "0.16.10", "stable": true },
+                            { "version": "0.16.9", "stable": true },
+                        ]))
+                    }),
+                )
+                .route(
+                    "/v2/versions/installer",
+                    get(|| async { Json(json!([{ "version": "1.0.1", "stable": true }])) }),
+                )
+        })
+        .await;
+
+        let fetcher = fabric::FabricFetcher::new(&url, ServerType::Fabric);
+
+        let fetched = fetcher
+            .fetch(&reqwest::Client::new(), &Known::default())
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.builds.len(), 1);
+        assert_eq!(fetched.builds[0].build_number, 16010);
+        assert_eq!(
+            fetched.builds[0].project_version_id.as_deref(),
+            Some("0.16.10")
+        );
+        assert_eq!(
+            fetched.builds[0].jar_url,
+            Some(format!(
+                "{}/v2/versions/loader/1.21.4/0.16.10/1.0.1/server/jar",
+                url
+            ))
+        );
+
+        let fetched = fetcher
+            .fetch(
+                &reqwest::Client::new(),
+                &Known::new(vec![(
+                    Some("1.21.4".to_string()),
+                    Some("0.16.10".to_string()),
+                    42,
+                )]),
+            )
+            .await
+            .unwrap();
+
+        assert!(fetched.builds.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use super::{Fetched, Fetcher, Known, jar_installation};
+use crate::models::{
+    build::Build,
+    r#type::{SERVER_TYPES_WITH_PROJECT_AS_IDENTIFIER, ServerType},
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Project {
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Builds {
+    builds: Vec<ProjectBuild>,
+}
+
+#[derive(Deserialize)]
+struct ProjectBuild {
+    build: i32,
+    time: DateTime<Utc>,
+    channel: String,
+    changes: Vec<ProjectBuildChange>,
+    downloads: HashMap<String, ProjectBuildDownload>,
+}
+
+#[derive(Deserialize)]
+struct ProjectBuildChange {
+    summary: String,
+}
+
+#[derive(Deserialize)]
+struct ProjectBuildDownload {
+    name: String,
+}
+
+pub struct PaperFetcher {
+    base_url: String,
+    r#type: ServerType,
+    project: String,
+}
+
+impl PaperFetcher {
+    pub fn new(base_url: &str, r#type: ServerType, project: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            r#type,
+            project: project.to_string(),
+        }
+    }
+
+    async fn builds(
+        &self,
+        client: &reqwest::Client,
+        version: &str,
+    ) -> Result<Builds, reqwest::Error> {
+        client
+            .get(format!(
+                "{}/v2/projects/{}/versions/{}/builds",
+                self.base_url, self.project, version
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetcher for PaperFetcher {
+    fn r#type(&self) -> ServerType {
+        self.r#type
+    }
+
+    async fn fetch(
+        &self,
+        client: &reqwest::Client,
+        known: &Known,
+    ) -> Result<Fetched, reqwest::Error> {
+        let project: Project = client
+            .get(format!("{}/v2/projects/{}", self.base_url, self.project))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut fetched = Fetched::default();
+
+        for version in project.versions {
+            let builds: Builds = match self.builds(client, &version).await {
+                Ok(builds) => builds,
+                Err(err) => {
+                    fetched.failed.push((version, err));
+
+                    continue;
+                }
+            };
+
+            for build in builds.builds {
+                if known.contains(&version, build.build) {
+                    continue;
+                }
+
+                let download = match build.downloads.get("application") {
+                    Some(download) => download,
+                    None => continue,
+                };
+
+                let url = format!(
+                    "{}/v2/projects/{}/versions/{}/builds/{}/downloads/{}",
+                    self.base_url, self.project, version, build.build, download.name
+                );
+                let project_identifier =
+                    SERVER_TYPES_WITH_PROJECT_AS_IDENTIFIER.contains(&self.r#type);
+
+                fetched.builds.push(Build {
+                    id: 0,
+                    version_id: (!project_identifier).then(|| version.clone()),
+                    project_version_id: project_identifier.then(|| version.clone()),
+                    r#type: self.r#type,
+                    experimental: build.channel == "experimental",
+                    name: String::new(),
+                    build_number: build.build,
+                    jar_url: Some(url.clone()),
+                    jar_size: None,
+                    zip_url: None,
+                    zip_size: None,
+                    installation: jar_installation(&url, 0),
+                    changes: build
+                        .changes
+                        .into_iter()
+                        .map(|change| change.summary)
+                        .collect(),
+                    created: Some(build.time.naive_utc()),
+                });
+            }
+        }
+
+        Ok(fetched)
+    }
+}
@@ -0,0 +1,133 @@
+use super::{Fetched, Fetcher, Known, jar_installation};
+use crate::models::{build::Build, r#type::ServerType};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Project {
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    builds: VersionBuilds,
+}
+
+#[derive(Deserialize)]
+struct VersionBuilds {
+    all: Vec<VersionBuild>,
+}
+
+#[derive(Deserialize)]
+struct VersionBuild {
+    build: String,
+    result: String,
+    timestamp: i64,
+    #[serde(default)]
+    commits: Vec<VersionBuildCommit>,
+}
+
+#[derive(Deserialize)]
+struct VersionBuildCommit {
+    description: String,
+}
+
+pub struct PurpurFetcher {
+    base_url: String,
+}
+
+impl PurpurFetcher {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn details(
+        &self,
+        client: &reqwest::Client,
+        version: &str,
+    ) -> Result<Version, reqwest::Error> {
+        client
+            .get(format!("{}/v2/purpur/{}", self.base_url, version))
+            .query(&[("detailed", "true")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetcher for PurpurFetcher {
+    fn r#type(&self) -> ServerType {
+        ServerType::Purpur
+    }
+
+    async fn fetch(
+        &self,
+        client: &reqwest::Client,
+        known: &Known,
+    ) -> Result<Fetched, reqwest::Error> {
+        let project: Project = client
+            .get(format!("{}/v2/purpur", self.base_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut fetched = Fetched::default();
+
+        for version in project.versions {
+            let details: Version = match self.details(client, &version).await {
+                Ok(details) => details,
+                Err(err) => {
+                    fetched.failed.push((version, err));
+
+                    continue;
+                }
+            };
+
+            for build in details.builds.all {
+                let build_number = match build.build.parse::<i32>() {
+                    Ok(build_number) => build_number,
+                    Err(_) => continue,
+                };
+
+                if build.result != "SUCCESS" || known.contains(&version, build_number) {
+                    continue;
+                }
+
+                let url = format!(
+                    "{}/v2/purpur/{}/{}/download",
+                    self.base_url, version, build_number
+                );
+
+                fetched.builds.push(Build {
+                    id: 0,
+                    version_id: Some(version.clone()),
+                    project_version_id: None,
+                    r#type: ServerType::Purpur,
+                    experimental: false,
+                    name: String::new(),
+                    build_number,
+                    jar_url: Some(url.clone()),
+                    jar_size: None,
+                    zip_url: None,
+                    zip_size: None,
+                    installation: jar_installation(&url, 0),
+                    changes: build
+                        .commits
+                        .into_iter()
+                        .map(|commit| commit.description)
+                        .collect(),
+                    created: chrono::DateTime::from_timestamp_millis(build.timestamp)
+                        .map(|created| created.naive_utc()),
+                });
+            }
+        }
+
+        Ok(fetched)
+    }
+}
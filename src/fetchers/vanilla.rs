@@ -0,0 +1,147 @@
+use super::{Fetched, Fetcher, Known, jar_installation};
+use crate::models::{
+    build::Build,
+    r#type::ServerType,
+    version::{MinecraftVersion, VersionType},
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Manifest {
+    versions: Vec<ManifestVersion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestVersion {
+    id: String,
+    r#type: String,
+    url: String,
+    release_time: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionDetails {
+    downloads: VersionDownloads,
+    java_version: VersionJava,
+}
+
+#[derive(Deserialize)]
+struct VersionDownloads {
+    server: Option<VersionDownload>,
+}
+
+#[derive(Deserialize)]
+struct VersionDownload {
+    url: String,
+    size: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionJava {
+    major_version: i16,
+}
+
+pub struct VanillaFetcher {
+    base_url: String,
+}
+
+impl VanillaFetcher {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn details(
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<VersionDetails, reqwest::Error> {
+        client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl Fetcher for VanillaFetcher {
+    fn r#type(&self) -> ServerType {
+        ServerType::Vanilla
+    }
+
+    async fn fetch(
+        &self,
+        client: &reqwest::Client,
+        known: &Known,
+    ) -> Result<Fetched, reqwest::Error> {
+        let manifest: Manifest = client
+            .get(format!(
+                "{}/mc/game/version_manifest_v2.json",
+                self.base_url
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut fetched = Fetched::default();
+
+        for version in manifest.versions {
+            let r#type = match version.r#type.as_str() {
+                "release" => VersionType::Release,
+                "snapshot" => VersionType::Snapshot,
+                _ => continue,
+            };
+
+            if known.contains(&version.id, 1) {
+                continue;
+            }
+
+            let details = match Self::details(client, &version.url).await {
+                Ok(details) => details,
+                Err(err) => {
+                    fetched.failed.push((version.id, err));
+
+                    continue;
+                }
+            };
+
+            fetched.versions.push(MinecraftVersion {
+                id: version.id.clone(),
+                r#type,
+                supported: details.downloads.server.is_some(),
+                java: details.java_version.major_version,
+                created: version.release_time.naive_utc(),
+            });
+
+            if let Some(server) = details.downloads.server {
+                fetched.builds.push(Build {
+                    id: 0,
+                    version_id: Some(version.id),
+                    project_version_id: None,
+                    r#type: ServerType::Vanilla,
+                    experimental: r#type == VersionType::Snapshot,
+                    name: String::new(),
+                    build_number: 1,
+                    jar_url: Some(server.url.clone()),
+                    jar_size: i32::try_from(server.size).ok(),
+                    zip_url: None,
+                    zip_size: None,
+                    installation: jar_installation(&server.url, server.size),
+                    changes: Vec::new(),
+                    created: Some(version.release_time.naive_utc()),
+                });
+            }
+        }
+
+        Ok(fetched)
+    }
+}
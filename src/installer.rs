@@ -0,0 +1,621 @@
+use crate::models::build::{Build, InstallationStep, InstallationStepDownload, is_safe_path};
+use futures_util::future::try_join_all;
+use sha2::Digest;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+pub enum Progress<'a> {
+    Step {
+        group: usize,
+        step: &'a InstallationStep,
+    },
+    Download {
+        file: &'a str,
+        downloaded: u64,
+        total: u64,
+    },
+    Done,
+}
+
+type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+
+pub struct Installer {
+    client: reqwest::Client,
+    directory: PathBuf,
+    java: PathBuf,
+    version: Option<i16>,
+    progress: Option<ProgressCallback>,
+}
+
+impl Installer {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent("MCJars API https://mcjars.app")
+                .build()
+                .unwrap(),
+            directory: directory.into(),
+            java: PathBuf::from("java"),
+            version: None,
+            progress: None,
+        }
+    }
+
+    pub fn java(mut self, binary: impl Into<PathBuf>, version: i16) -> Self {
+        self.java = binary.into();
+        self.version = Some(version);
+
+        self
+    }
+
+    pub fn on_progress(mut self, callback: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(callback));
+
+        self
+    }
+
+    fn report(&self, progress: Progress) {
+        if let Some(callback) = &self.progress {
+            callback(progress);
+        }
+    }
+
+    fn path(&self, path: &str) -> Result<PathBuf, String> {
+        if !is_safe_path(path) {
+            return Err(format!("path {} is not allowed", path));
+        }
+
+        Ok(self.directory.join(path))
+    }
+
+    fn java_version(output: &str) -> Option<i16> {
+        let version = output.lines().next()?.split('"').nth(1)?;
+        let mut parts = version.split(['.', '_', '-', '+']);
+
+        match parts.next()?.parse().ok()? {
+            1 => parts.next()?.parse().ok(),
+            major => Some(major),
+        }
+    }
+
+    async fn ensure_java(&self) -> Result<(), String> {
+        let Some(required) = self.version else {
+            return Ok(());
+        };
+
+        let output = tokio::process::Command::new(&self.java)
+            .arg("-version")
+            .output()
+            .await
+            .map_err(|err| format!("failed to run {}: {}", self.java.display(), err))?;
+
+        match Self::java_version(&String::from_utf8_lossy(&output.stderr)) {
+            Some(found) if found >= required => Ok(()),
+            Some(found) => Err(format!(
+                "java {} or newer is required, {} is java {}",
+                required,
+                self.java.display(),
+                found
+            )),
+            None => Err(format!(
+                "java {} or newer is required, unable to detect the version of {}",
+                required,
+                self.java.display()
+            )),
+        }
+    }
+
+    async fn ensure_parent(path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|err| format!("failed to create {}: {}", parent.display(), err))?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn install(&self, installation: &[Vec<InstallationStep>]) -> Result<(), String> {
+        Build::validate_installation(installation)?;
+
+        if installation
+            .iter()
+            .flatten()
+            .any(|step| matches!(step, InstallationStep::RunJava(_)))
+        {
+            self.ensure_java().await?;
+        }
+
+        tokio::fs::create_dir_all(&self.directory)
+            .await
+            .map_err(|err| format!("failed to create {}: {}", self.directory.display(), err))?;
+
+        for (group, combined) in installation.iter().enumerate() {
+            try_join_all(combined.iter().map(|step| async move {
+                self.report(Progress::Step { group, step });
+
+                self.run(step).await
+            }))
+            .await?;
+        }
+
+        self.report(Progress::Done);
+
+        Ok(())
+    }
+
+    async fn run(&self, step: &InstallationStep) -> Result<(), String> {
+        match step {
+            InstallationStep::Download(step) => self.download(step).await,
+            InstallationStep::Unzip(step) => {
+                let file = self.path(&step.file)?;
+                let location = self.path(&step.location)?;
+
+                tokio::task::spawn_blocking(move || {
+                    let archive = std::fs::File::open(&file)
+                        .map_err(|err| format!("failed to open {}: {}", file.display(), err))?;
+
+                    zip::ZipArchive::new(archive)
+                        .and_then(|mut archive| archive.extract(&location))
+                        .map_err(|err| format!("failed to unzip {}: {}", file.display(), err))
+                })
+                .await
+                .unwrap()
+            }
+            InstallationStep::Extract(step) => {
+                let file = self.path(&step.file)?;
+                let location = self.path(&step.location)?;
+
+                tokio::task::spawn_blocking(move || {
+                    let archive = std::fs::File::open(&file)
+                        .map_err(|err| format!("failed to open {}: {}", file.display(), err))?;
+
+                    std::fs::create_dir_all(&location).map_err(|err| {
+                        format!("failed to create {}: {}", location.display(), err)
+                    })?;
+
+                    tar::Archive::new(flate2::read::GzDecoder::new(archive))
+                        .unpack(&location)
+                        .map_err(|err| format!("failed to extract {}: {}", file.display(), err))
+                })
+                .await
+                .unwrap()
+            }
+            InstallationStep::Remove(step) => {
+                let location = self.path(&step.location)?;
+
+                let result = match tokio::fs::metadata(&location).await {
+                    Ok(metadata) if metadata.is_dir() => tokio::fs::remove_dir_all(&location).await,
+                    Ok(_) => tokio::fs::remove_file(&location).await,
+                    Err(_) => Ok(()),
+                };
+
+                result.map_err(|err| format!("failed to remove {}: {}", location.display(), err))
+            }
+            InstallationStep::Move(step) => {
+                let from = self.path(&step.from)?;
+                let to = self.path(&step.to)?;
+
+                Self::ensure_parent(&to).await?;
+
+                tokio::fs::rename(&from, &to)
+                    .await
+                    .map_err(|err| format!("failed to move {}: {}", from.display(), err))
+            }
+            InstallationStep::WriteFile(step) => {
+                let file = self.path(&step.file)?;
+
+                Self::ensure_parent(&file).await?;
+
+                tokio::fs::write(&file, &step.content)
+                    .await
+                    .map_err(|err| format!("failed to write {}: {}", file.display(), err))
+            }
+            InstallationStep::Chmod(step) => {
+                let location = self.path(&step.location)?;
+                let mode = u32::from_str_radix(&step.mode, 8)
+                    .map_err(|_| format!("invalid mode {}", step.mode))?;
+
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+
+                    tokio::fs::set_permissions(&location, std::fs::Permissions::from_mode(mode))
+                        .await
+                        .map_err(|err| format!("failed to chmod {}: {}", location.display(), err))
+                }
+                #[cfg(not(unix))]
+                {
+                    let _ = (location, mode);
+
+                    Ok(())
+                }
+            }
+            InstallationStep::RunJava(step) => {
+                self.path(&step.file)?;
+
+                let status = tokio::process::Command::new(&self.java)
+                    .arg("-jar")
+                    .arg(&step.file)
+                    .args(&step.arguments)
+                    .current_dir(&self.directory)
+                    .status()
+                    .await
+                    .map_err(|err| format!("failed to run {}: {}", self.java.display(), err))?;
+
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} -jar {} exited with {}",
+                        self.java.display(),
+                        step.file,
+                        status
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn checksum(file: &Path) -> Result<String, String> {
+        let mut reader = tokio::fs::File::open(file)
+            .await
+            .map_err(|err| format!("failed to open {}: {}", file.display(), err))?;
+        let mut hasher = sha2::Sha256::new();
+        let mut buffer = vec![0; 64 * 1024];
+
+        loop {
+            let read = reader
+                .read(&mut buffer)
+                .await
+                .map_err(|err| format!("failed to read {}: {}", file.display(), err))?;
+
+            if read == 0 {
+                break;
+            }
+
+            hasher.update(&buffer[..read]);
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    async fn verify(&self, step: &InstallationStepDownload, file: &Path) -> Result<(), String> {
+        let size = tokio::fs::metadata(file)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        if step.size > 0 && size != step.size {
+            return Err(format!(
+                "size mismatch for {}: expected {} bytes, got {}",
+                step.file, step.size, size
+            ));
+        }
+
+        if let Some(sha256) = &step.sha256
+            && !Self::checksum(file).await?.eq_ignore_ascii_case(sha256)
+        {
+            return Err(format!("checksum mismatch for {}", step.file));
+        }
+
+        Ok(())
+    }
+
+    async fn fetch(
+        &self,
+        step: &InstallationStepDownload,
+        offset: u64,
+    ) -> Result<reqwest::Response, String> {
+        let mut request = self.client.get(&step.url);
+        if offset > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+        }
+
+        request
+            .send()
+            .await
+            .map_err(|err| format!("failed to download {}: {}", step.file, err))
+    }
+
+    fn content_range(response: &reqwest::Response) -> Option<(u64, u64)> {
+        let range = response
+            .headers()
+            .get(reqwest::header::CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .strip_prefix("bytes ")?;
+        let (range, total) = range.split_once('/')?;
+        let (start, _) = range.split_once('-')?;
+
+        Some((start.parse().ok()?, total.parse().ok()?))
+    }
+
+    async fn download(&self, step: &InstallationStepDownload) -> Result<(), String> {
+        let file = self.path(&step.file)?;
+
+        Self::ensure_parent(&file).await?;
+
+        let mut existing = tokio::fs::metadata(&file)
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        if step.size > 0 && existing == step.size && self.verify(step, &file).await.is_ok() {
+            self.report(Progress::Download {
+                file: &step.file,
+                downloaded: existing,
+                total: step.size,
+            });
+
+            return Ok(());
+        }
+
+        if step.size == 0 || existing >= step.size {
+            existing = 0;
+        }
+
+        let mut response = self.fetch(step, existing).await?;
+
+        if existing > 0
+            && (response.status() != reqwest::StatusCode::PARTIAL_CONTENT
+                || Self::content_range(&response) != Some((existing, step.size)))
+        {
+            existing = 0;
+
+            if response.status() != reqwest::StatusCode::OK {
+                response = self.fetch(step, 0).await?;
+            }
+        }
+
+        let mut response = response
+            .error_for_status()
+            .map_err(|err| format!("failed to download {}: {}", step.file, err))?;
+
+        let mut writer = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(existing > 0)
+            .truncate(existing == 0)
+            .open(&file)
+            .await
+            .map_err(|err| format!("failed to open {}: {}", file.display(), err))?;
+
+        let mut downloaded = existing;
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| format!("failed to download {}: {}", step.file, err))?
+        {
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(|err| format!("failed to write {}: {}", file.display(), err))?;
+            downloaded += chunk.len() as u64;
+
+            self.report(Progress::Download {
+                file: &step.file,
+                downloaded,
+                total: step.size,
+            });
+        }
+
+        writer
+            .flush()
+            .await
+            .map_err(|err| format!("failed to write {}: {}", file.display(), err))?;
+
+        self.verify(step, &file).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Installer;
+    use crate::models::build::{
+        InstallationStep, InstallationStepDownload, InstallationStepRunJava,
+        InstallationStepWriteFile,
+    };
+    use sha2::Digest;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const BODY: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    #[derive(Clone, Copy)]
+    enum Mode {
+        Resume,
+        Unsatisfiable,
+        WrongRange,
+    }
+
+    async fn serve(mode: Mode) -> (String, Arc<Mutex<Vec<Option<usize>>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/server.jar", listener.local_addr().unwrap());
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = ranges.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = socket.read(&mut buffer).await.unwrap();
+                    if read == 0 {
+                        break;
+                    }
+
+                    request.extend_from_slice(&buffer[..read]);
+                }
+
+                let range = String::from_utf8_lossy(&request)
+                    .to_lowercase()
+                    .lines()
+                    .find_map(|line| line.strip_prefix("range: bytes=").map(str::to_string))
+                    .map(|range| range.trim_end_matches('-').parse::<usize>().unwrap());
+                seen.lock().unwrap().push(range);
+
+                let (status, content_range, body) = match (range, mode) {
+                    (None, _) => ("200 OK", None, BODY),
+                    (Some(_), Mode::Unsatisfiable) => (
+                        "416 Range Not Satisfiable",
+                        Some(format!("bytes */{}", BODY.len())),
+                        &BODY[..0],
+                    ),
+                    (Some(_), Mode::WrongRange) => (
+                        "206 Partial Content",
+                        Some(format!("bytes 0-{}/{}", BODY.len() - 1, BODY.len())),
+                        BODY,
+                    ),
+                    (Some(start), Mode::Resume) => (
+                        "206 Partial Content",
+                        Some(format!("bytes {}-{}/{}", start, BODY.len() - 1, BODY.len())),
+                        &BODY[start..],
+                    ),
+                };
+
+                let mut response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n",
+                    status,
+                    body.len()
+                );
+                if let Some(content_range) = content_range {
+                    response.push_str(&format!("content-range: {}\r\n", content_range));
+                }
+                response.push_str("\r\n");
+
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.write_all(body).await.unwrap();
+            }
+        });
+
+        (url, ranges)
+    }
+
+    async fn download(mode: Mode, size: u64, partial: &[u8]) -> (Vec<u8>, Vec<Option<usize>>) {
+        let (url, ranges) = serve(mode).await;
+        let directory =
+            std::env::temp_dir().join(format!("mcjars-installer-{}", rand::random::<u32>()));
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        tokio::fs::write(directory.join("server.jar"), partial)
+            .await
+            .unwrap();
+
+        Installer::new(&directory)
+            .download(&InstallationStepDownload {
+                url,
+                file: "server.jar".to_string(),
+                size,
+                sha256: Some(format!("{:x}", sha2::Sha256::digest(BODY))),
+            })
+            .await
+            .unwrap();
+
+        let data = tokio::fs::read(directory.join("server.jar")).await.unwrap();
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+
+        let ranges = ranges.lock().unwrap().clone();
+
+        (data, ranges)
+    }
+
+    #[tokio::test]
+    async fn resumes_partial_download() {
+        let (data, ranges) = download(Mode::Resume, BODY.len() as u64, &BODY[..10]).await;
+
+        assert_eq!(data, BODY);
+        assert_eq!(ranges, vec![Some(10)]);
+    }
+
+    #[tokio::test]
+    async fn unknown_size_downloads_again() {
+        let (data, ranges) = download(Mode::Resume, 0, b"stale").await;
+
+        assert_eq!(data, BODY);
+        assert_eq!(ranges, vec![None]);
+    }
+
+    #[tokio::test]
+    async fn unsatisfiable_range_downloads_again() {
+        let (data, ranges) = download(Mode::Unsatisfiable, BODY.len() as u64, &BODY[..10]).await;
+
+        assert_eq!(data, BODY);
+        assert_eq!(ranges, vec![Some(10), None]);
+    }
+
+    #[tokio::test]
+    async fn mismatched_content_range_downloads_again() {
+        let (data, ranges) = download(Mode::WrongRange, BODY.len() as u64, &BODY[..10]).await;
+
+        assert_eq!(data, BODY);
+        assert_eq!(ranges, vec![Some(10), None]);
+    }
+
+    fn write(file: &str) -> InstallationStep {
+        InstallationStep::WriteFile(InstallationStepWriteFile {
+            file: file.to_string(),
+            content: "eula=true\n".to_string(),
+        })
+    }
+
+    #[test]
+    fn parses_java_versions() {
+        assert_eq!(
+            Installer::java_version("openjdk version \"17.0.2\" 2022-01-18\nOpenJDK Runtime"),
+            Some(17)
+        );
+        assert_eq!(
+            Installer::java_version("java version \"1.8.0_292\"\nJava(TM) SE Runtime"),
+            Some(8)
+        );
+        assert_eq!(
+            Installer::java_version("openjdk version \"21\" 2023-09-19"),
+            Some(21)
+        );
+        assert_eq!(Installer::java_version("command not found"), None);
+    }
+
+    #[tokio::test]
+    async fn rejects_unsafe_paths_before_running_any_step() {
+        let directory =
+            std::env::temp_dir().join(format!("mcjars-installer-{}", rand::random::<u32>()));
+
+        let result = Installer::new(&directory)
+            .install(&[vec![write("eula.txt")], vec![write("../escape.txt")]])
+            .await;
+
+        assert!(result.is_err());
+        assert!(!directory.join("eula.txt").exists());
+        assert!(!directory.with_file_name("escape.txt").exists());
+
+        tokio::fs::remove_dir_all(&directory).await.ok();
+    }
+
+    #[tokio::test]
+    async fn requires_the_java_binary_before_running_any_step() {
+        let directory =
+            std::env::temp_dir().join(format!("mcjars-installer-{}", rand::random::<u32>()));
+
+        let result = Installer::new(&directory)
+            .java(directory.join("missing-java"), 17)
+            .install(&[
+                vec![write("eula.txt")],
+                vec![InstallationStep::RunJava(InstallationStepRunJava {
+                    file: "eula.txt".to_string(),
+                    arguments: Vec::new(),
+                })],
+            ])
+            .await;
+
+        assert!(result.unwrap_err().contains("missing-java"));
+        assert!(!directory.join("eula.txt").exists());
+
+        tokio::fs::remove_dir_all(&directory).await.ok();
+    }
+}
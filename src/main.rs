@@ -1,11 +1,14 @@
 mod cache;
 mod database;
 mod env;
+mod fetchers;
+mod installer;
 mod logger;
 mod models;
 mod requests;
 mod routes;
 mod s3;
+mod webhooks;
 
 use axum::{
     ServiceExt,
@@ -78,34 +81,33 @@ async fn handle_postprocessing(req: Request, next: Next) -> Result<Response, Sta
 
     let mut response = next.run(req).await;
 
-    if let Some(content_type) = response.headers().get("Content-Type") {
-        if content_type
+    if let Some(content_type) = response.headers().get("Content-Type")
+        && content_type
             .to_str()
             .map(|c| c.starts_with("text/plain"))
             .unwrap_or(false)
-            && response.status().is_client_error()
-        {
-            let (mut parts, body) = response.into_parts();
-
-            let text_body = String::from_utf8(
-                axum::body::to_bytes(body, usize::MAX)
-                    .await
-                    .unwrap()
-                    .into_iter()
-                    .by_ref()
-                    .collect::<Vec<u8>>(),
-            )
-            .unwrap();
+        && response.status().is_client_error()
+    {
+        let (mut parts, body) = response.into_parts();
+
+        let text_body = String::from_utf8(
+            axum::body::to_bytes(body, usize::MAX)
+                .await
+                .unwrap()
+                .into_iter()
+                .by_ref()
+                .collect::<Vec<u8>>(),
+        )
+        .unwrap();
 
-            parts
-                .headers
-                .insert("Content-Type", "application/json".parse().unwrap());
+        parts
+            .headers
+            .insert("Content-Type", "application/json".parse().unwrap());
 
-            response = Response::from_parts(
-                parts,
-                Body::from(ApiError::new(&[&text_body]).to_value().to_string()),
-            );
-        }
+        response = Response::from_parts(
+            parts,
+            Body::from(ApiError::new(&[&text_body]).to_value().to_string()),
+        );
     }
 
     let (mut parts, body) = response.into_parts();
@@ -153,6 +155,95 @@ pub fn extract_ip(headers: &HeaderMap) -> Option<IpAddr> {
     ip.parse().ok()
 }
 
+async fn install(
+    database: &database::Database,
+    cache: &cache::Cache,
+    identifier: &str,
+    directory: &str,
+) {
+    let (mut build, _, version) =
+        match models::build::Build::by_v1_identifier(database, cache, identifier, None).await {
+            Ok(Some(data)) => data,
+            Ok(None) => {
+                logger::log(
+                    logger::LoggerLevel::Error,
+                    format!("{} {} not found", "install".bright_red(), identifier.cyan()),
+                );
+
+                std::process::exit(1);
+            }
+            Err(err) => {
+                err.report();
+
+                std::process::exit(1);
+            }
+        };
+
+    match models::build::BuildHash::primary_by_build(database, cache, build.id).await {
+        Ok(Some(hash)) => build.fill_primary_hash(&hash),
+        Ok(None) => {}
+        Err(err) => {
+            err.report();
+
+            std::process::exit(1);
+        }
+    }
+
+    let java = std::env::var_os("JAVA_HOME")
+        .map(|home| std::path::PathBuf::from(home).join("bin").join("java"))
+        .unwrap_or_else(|| "java".into());
+
+    let installer = installer::Installer::new(directory)
+        .java(java, version.java)
+        .on_progress(|progress| match progress {
+            installer::Progress::Step { group, step } => logger::log(
+                logger::LoggerLevel::Info,
+                format!(
+                    "{} group {} {}",
+                    "install".bright_red(),
+                    group.to_string().cyan(),
+                    serde_json::to_string(step).unwrap().bright_black()
+                ),
+            ),
+            installer::Progress::Download {
+                file,
+                downloaded,
+                total,
+            } if downloaded == total => logger::log(
+                logger::LoggerLevel::Info,
+                format!(
+                    "{} downloaded {} {}",
+                    "install".bright_red(),
+                    file.cyan(),
+                    format!("({} bytes)", total).bright_black()
+                ),
+            ),
+            _ => {}
+        });
+
+    match installer.install(&build.installation).await {
+        Ok(()) => logger::log(
+            logger::LoggerLevel::Info,
+            format!(
+                "{} installed {} {} into {} {}",
+                "install".bright_red(),
+                version.id.cyan(),
+                build.name.cyan(),
+                directory.cyan(),
+                format!("(java {})", version.java).bright_black()
+            ),
+        ),
+        Err(err) => {
+            logger::log(
+                logger::LoggerLevel::Error,
+                format!("{} {}", "install".bright_red(), err.red()),
+            );
+
+            std::process::exit(1);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let env = env::Env::parse();
@@ -170,7 +261,44 @@ async fn main() {
     let env = Arc::new(env);
     let s3 = Arc::new(s3::S3::new(env.clone()).await);
     let database = Arc::new(database::Database::new(env.clone()).await);
-    let cache = Arc::new(cache::Cache::new(env.clone()).await);
+    let cache = match cache::Cache::new(env.clone()).await {
+        Ok(cache) => Arc::new(cache),
+        Err(err) => {
+            cache::Cache::report(&err);
+
+            std::process::exit(1);
+        }
+    };
+
+    if let [command, identifier, directory] = &std::env::args().skip(1).collect::<Vec<_>>()[..]
+        && command == "install"
+    {
+        install(&database, &cache, identifier, directory).await;
+
+        return;
+    }
+
+    if let [command] = &std::env::args().skip(1).collect::<Vec<_>>()[..]
+        && command == "backfill-configs"
+    {
+        match models::config::Config::backfill(&database).await {
+            Ok(updated) => logger::log(
+                logger::LoggerLevel::Info,
+                format!(
+                    "{} reformatted {} config values",
+                    "backfill-configs".bright_red(),
+                    updated.to_string().cyan()
+                ),
+            ),
+            Err(err) => {
+                err.report();
+
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
 
     let state = Arc::new(routes::AppState {
         start_time: Instant::now(),
@@ -181,6 +309,11 @@ async fn main() {
         requests: requests::RequestLogger::new(database.clone(), cache.clone()),
         env,
         s3,
+        client: reqwest::Client::builder()
+            .user_agent("MCJars API https://mcjars.app")
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .unwrap(),
     });
 
     {
@@ -195,6 +328,30 @@ async fn main() {
         });
     }
 
+    {
+        let webhooks = webhooks::WebhookDispatcher::new(database.clone(), cache.clone());
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+
+                webhooks.process().await;
+            }
+        });
+    }
+
+    if state.env.fetchers_enabled {
+        let fetchers = fetchers::FetcherManager::new(&state.env, database.clone(), cache.clone());
+
+        tokio::spawn(async move {
+            loop {
+                fetchers.process().await;
+
+                tokio::time::sleep(std::time::Duration::from_secs(900)).await;
+            }
+        });
+    }
+
     let app =
         OpenApiRouter::new()
             .merge(routes::router(&state))
@@ -1,8 +1,10 @@
 use super::r#type::ServerType;
 use crate::models::BaseModel;
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use sha2::Digest;
 use sqlx::{Row, postgres::PgRow, types::chrono::NaiveDateTime};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use utoipa::ToSchema;
 
 #[derive(ToSchema, Serialize, Deserialize, Clone)]
@@ -14,6 +16,16 @@ pub enum InstallationStep {
     Unzip(#[schema(inline)] InstallationStepUnzip),
     #[serde(rename = "remove")]
     Remove(#[schema(inline)] InstallationStepRemove),
+    #[serde(rename = "move")]
+    Move(#[schema(inline)] InstallationStepMove),
+    #[serde(rename = "write_file")]
+    WriteFile(#[schema(inline)] InstallationStepWriteFile),
+    #[serde(rename = "chmod")]
+    Chmod(#[schema(inline)] InstallationStepChmod),
+    #[serde(rename = "extract")]
+    Extract(#[schema(inline)] InstallationStepExtract),
+    #[serde(rename = "run_java")]
+    RunJava(#[schema(inline)] InstallationStepRunJava),
 }
 
 #[derive(ToSchema, Serialize, Deserialize, Clone)]
@@ -21,6 +33,8 @@ pub struct InstallationStepDownload {
     pub url: String,
     pub file: String,
     pub size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
 }
 #[derive(ToSchema, Serialize, Deserialize, Clone)]
 pub struct InstallationStepUnzip {
@@ -31,6 +45,158 @@ pub struct InstallationStepUnzip {
 pub struct InstallationStepRemove {
     pub location: String,
 }
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct InstallationStepMove {
+    pub from: String,
+    pub to: String,
+}
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct InstallationStepWriteFile {
+    pub file: String,
+    pub content: String,
+}
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct InstallationStepChmod {
+    pub location: String,
+    #[schema(example = "755")]
+    pub mode: String,
+}
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct InstallationStepExtract {
+    pub file: String,
+    pub location: String,
+}
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct InstallationStepRunJava {
+    pub file: String,
+    #[serde(default)]
+    #[schema(example = json!(["--installServer"]))]
+    pub arguments: Vec<String>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct BuildHash {
+    pub primary: bool,
+
+    pub sha1: String,
+    pub sha224: String,
+    pub sha256: String,
+    pub sha384: String,
+    pub sha512: String,
+    pub md5: String,
+}
+
+impl BuildHash {
+    pub async fn compute(
+        client: &reqwest::Client,
+        url: &str,
+        primary: bool,
+    ) -> Result<(Self, u64), String> {
+        let mut response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| format!("failed to download {}: {}", url, err))?;
+
+        if !response.status().is_success() {
+            return Err(format!("failed to download {}: {}", url, response.status()));
+        }
+
+        let mut md5 = md5::Md5::new();
+        let mut sha1 = sha1::Sha1::new();
+        let mut sha224 = sha2::Sha224::new();
+        let mut sha256 = sha2::Sha256::new();
+        let mut sha384 = sha2::Sha384::new();
+        let mut sha512 = sha2::Sha512::new();
+        let mut size = 0;
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| format!("failed to download {}: {}", url, err))?
+        {
+            md5.update(&chunk);
+            sha1.update(&chunk);
+            sha224.update(&chunk);
+            sha256.update(&chunk);
+            sha384.update(&chunk);
+            sha512.update(&chunk);
+            size += chunk.len() as u64;
+        }
+
+        Ok((
+            Self {
+                primary,
+                sha1: format!("{:x}", sha1.finalize()),
+                sha224: format!("{:x}", sha224.finalize()),
+                sha256: format!("{:x}", sha256.finalize()),
+                sha384: format!("{:x}", sha384.finalize()),
+                sha512: format!("{:x}", sha512.finalize()),
+                md5: format!("{:x}", md5.finalize()),
+            },
+            size,
+        ))
+    }
+
+    pub async fn primary_by_build(
+        database: &crate::database::Database,
+        cache: &crate::cache::Cache,
+        build_id: i32,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let database = database.clone();
+
+        cache
+            .cached(
+                &format!("build::{}::hash", build_id),
+                3600,
+                move || async move {
+                    Ok(sqlx::query(
+                        r#"
+                    SELECT "primary", sha1, sha224, sha256, sha384, sha512, md5
+                    FROM build_hashes
+                    WHERE build_hashes.build_id = $1 AND build_hashes.primary
+                    LIMIT 1
+                    "#,
+                    )
+                    .bind(build_id)
+                    .fetch_optional(database.read())
+                    .await?
+                    .map(|row| Self {
+                        primary: row.get("primary"),
+                        sha1: row.get("sha1"),
+                        sha224: row.get("sha224"),
+                        sha256: row.get("sha256"),
+                        sha384: row.get("sha384"),
+                        sha512: row.get("sha512"),
+                        md5: row.get("md5"),
+                    }))
+                },
+            )
+            .await
+    }
+
+    pub fn matches(&self, other: &Self) -> bool {
+        self.sha1.eq_ignore_ascii_case(&other.sha1)
+            && self.sha224.eq_ignore_ascii_case(&other.sha224)
+            && self.sha256.eq_ignore_ascii_case(&other.sha256)
+            && self.sha384.eq_ignore_ascii_case(&other.sha384)
+            && self.sha512.eq_ignore_ascii_case(&other.sha512)
+            && self.md5.eq_ignore_ascii_case(&other.md5)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        [
+            (&self.sha1, 40),
+            (&self.sha224, 56),
+            (&self.sha256, 64),
+            (&self.sha384, 96),
+            (&self.sha512, 128),
+            (&self.md5, 32),
+        ]
+        .iter()
+        .all(|(hash, length)| hash.len() == *length && hash.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+}
 
 #[derive(ToSchema, Serialize, Deserialize, Clone)]
 pub struct Build {
@@ -69,6 +235,13 @@ pub struct Build {
     pub created: Option<NaiveDateTime>,
 }
 
+fn projected<'r, T>(row: &'r PgRow, column: &str) -> Option<T>
+where
+    T: sqlx::Decode<'r, sqlx::Postgres> + sqlx::Type<sqlx::Postgres>,
+{
+    row.try_column(column).is_ok().then(|| row.get(column))
+}
+
 impl BaseModel for Build {
     fn columns(prefix: Option<&str>, table: Option<&str>) -> BTreeMap<String, String> {
         let table = table.unwrap_or("builds");
@@ -142,7 +315,7 @@ impl BaseModel for Build {
                 row.get(format!("{}type", prefix).as_str()),
             ))
             .unwrap(),
-            experimental: row.get(format!("{}experimental", prefix).as_str()),
+            experimental: projected(row, &format!("{}experimental", prefix)).unwrap_or_default(),
             name: if row.get::<i32, _>(format!("{}build_number", prefix).as_str()) == 1
                 && row
                     .try_get::<String, _>(format!("{}project_version_id", prefix).as_str())
@@ -156,28 +329,468 @@ impl BaseModel for Build {
                 )
             },
             build_number: row.get(format!("{}build_number", prefix).as_str()),
-            jar_url: row.get(format!("{}jar_url", prefix).as_str()),
-            jar_size: row.get(format!("{}jar_size", prefix).as_str()),
-            zip_url: row.get(format!("{}zip_url", prefix).as_str()),
-            zip_size: row.get(format!("{}zip_size", prefix).as_str()),
-            installation: serde_json::from_value(
-                row.get(format!("{}installation", prefix).as_str()),
-            )
-            .unwrap(),
-            changes: serde_json::from_value(row.get(format!("{}changes", prefix).as_str()))
-                .unwrap(),
-            created: row.get(format!("{}created", prefix).as_str()),
+            jar_url: projected(row, &format!("{}jar_url", prefix)).unwrap_or_default(),
+            jar_size: projected(row, &format!("{}jar_size", prefix)).unwrap_or_default(),
+            zip_url: projected(row, &format!("{}zip_url", prefix)).unwrap_or_default(),
+            zip_size: projected(row, &format!("{}zip_size", prefix)).unwrap_or_default(),
+            installation: projected(row, &format!("{}installation", prefix))
+                .map(|installation| serde_json::from_value(installation).unwrap())
+                .unwrap_or_default(),
+            changes: projected(row, &format!("{}changes", prefix))
+                .map(|changes| serde_json::from_value(changes).unwrap())
+                .unwrap_or_default(),
+            created: projected(row, &format!("{}created", prefix)).unwrap_or_default(),
+        }
+    }
+}
+
+const BUILD_FIELDS: [(&str, &[&str]); 14] = [
+    ("id", &["id"]),
+    ("versionId", &["version_id"]),
+    ("projectVersionId", &["project_version_id"]),
+    ("type", &["type"]),
+    ("experimental", &["experimental"]),
+    ("name", &["build_number", "project_version_id"]),
+    ("buildNumber", &["build_number"]),
+    ("jarUrl", &["jar_url"]),
+    ("jarSize", &["jar_size"]),
+    ("zipUrl", &["zip_url"]),
+    ("zipSize", &["zip_size"]),
+    ("installation", &["installation"]),
+    ("changes", &["changes"]),
+    ("created", &["created"]),
+];
+
+#[derive(Clone)]
+pub struct BuildFields {
+    fields: Vec<&'static str>,
+    columns: HashSet<&'static str>,
+}
+
+impl<'de> Deserialize<'de> for BuildFields {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::parse(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::fmt::Display for BuildFields {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.fields.join(","))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BuildFieldsQuery {
+    pub fields: Option<BuildFields>,
+}
+
+impl BuildFields {
+    pub fn parse(fields: &str) -> Result<Self, String> {
+        let mut selected = Vec::new();
+        let mut columns = HashSet::from(["id", "type", "build_number"]);
+
+        for field in fields.split(',').map(|field| field.trim()) {
+            match BUILD_FIELDS.iter().find(|(name, _)| *name == field) {
+                Some((name, field_columns)) => {
+                    selected.push(*name);
+                    columns.extend(field_columns.iter());
+                }
+                None => return Err(format!("unknown field {}", field)),
+            }
+        }
+
+        Ok(Self {
+            fields: selected,
+            columns,
+        })
+    }
+
+    pub fn with_lookup_columns(&self) -> Self {
+        let mut columns = self.columns.clone();
+        columns.extend(["version_id", "project_version_id", "created"]);
+
+        Self {
+            fields: self.fields.clone(),
+            columns,
+        }
+    }
+
+    pub fn key(&self) -> String {
+        let mut columns = self.columns.iter().copied().collect::<Vec<_>>();
+        columns.sort_unstable();
+
+        columns.join(",")
+    }
+
+    pub fn columns_sql(&self, prefix: Option<&str>, table: Option<&str>) -> String {
+        Build::columns(prefix, table)
+            .iter()
+            .filter(|(_, value)| {
+                self.columns
+                    .contains(&value[prefix.unwrap_or_default().len()..])
+            })
+            .map(|(key, value)| format!("{} as {}", key, value))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn project(&self, build: &mut serde_json::Value) {
+        if let Some(build) = build.as_object_mut() {
+            build.retain(|key, _| self.fields.contains(&key.as_str()));
+        }
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[schema(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UpgradePolicy {
+    SameVersion,
+    Stable,
+    LatestSupported,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct BuildChange {
+    pub id: i32,
+    pub name: String,
+    pub changes: Vec<String>,
+}
+
+impl From<Build> for BuildChange {
+    fn from(build: Build) -> Self {
+        Self {
+            id: build.id,
+            name: build.name,
+            changes: build.changes,
         }
     }
 }
 
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct JavaChange {
+    pub from: Option<i16>,
+    pub to: Option<i16>,
+    pub delta: Option<i16>,
+}
+
+impl JavaChange {
+    pub fn new(from: Option<i16>, to: Option<i16>) -> Self {
+        Self {
+            from,
+            to,
+            delta: from.zip(to).map(|(from, to)| to - from),
+        }
+    }
+}
+
+pub struct BuildFilter {
+    pub cursor: Option<i32>,
+    pub limit: Option<i64>,
+    pub experimental: Option<bool>,
+    pub since: Option<NaiveDateTime>,
+}
+
+pub fn is_safe_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.contains('\\')
+        && !path.split('/').any(|part| part == "..")
+        && path
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '/' | '+'))
+}
+
 impl Build {
+    pub fn validate_installation(installation: &[Vec<InstallationStep>]) -> Result<(), String> {
+        let mut files: HashSet<&str> = HashSet::new();
+
+        for (i, combined) in installation.iter().enumerate() {
+            if combined.is_empty() {
+                return Err(format!("installation step group {} is empty", i));
+            }
+
+            let mut combined_files = Vec::new();
+
+            for step in combined {
+                match step {
+                    InstallationStep::Download(step) => {
+                        if !step.url.starts_with("https://") && !step.url.starts_with("http://") {
+                            return Err(format!("download url {} is not http(s)", step.url));
+                        }
+
+                        if step.url.contains(['\'', '"', '`', '\n']) {
+                            return Err(format!("download url {} is invalid", step.url));
+                        }
+
+                        if !is_safe_path(&step.file) {
+                            return Err(format!("download file {} is invalid", step.file));
+                        }
+
+                        if step.sha256.as_ref().is_some_and(|sha256| {
+                            sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit())
+                        }) {
+                            return Err(format!("download sha256 of {} is invalid", step.file));
+                        }
+
+                        if files.contains(step.file.as_str())
+                            || combined_files.contains(&step.file.as_str())
+                        {
+                            return Err(format!("download file {} is duplicated", step.file));
+                        }
+
+                        combined_files.push(step.file.as_str());
+                    }
+                    InstallationStep::Unzip(step) => {
+                        if !is_safe_path(&step.file) || !is_safe_path(&step.location) {
+                            return Err(format!("unzip of {} is invalid", step.file));
+                        }
+
+                        if !files.contains(step.file.as_str()) {
+                            return Err(format!(
+                                "unzip of {} must come after its download",
+                                step.file
+                            ));
+                        }
+                    }
+                    InstallationStep::Remove(step) => {
+                        if !is_safe_path(&step.location) {
+                            return Err(format!("remove of {} is invalid", step.location));
+                        }
+                    }
+                    InstallationStep::Move(step) => {
+                        if !is_safe_path(&step.from) || !is_safe_path(&step.to) {
+                            return Err(format!("move of {} is invalid", step.from));
+                        }
+                    }
+                    InstallationStep::WriteFile(step) => {
+                        if !is_safe_path(&step.file) {
+                            return Err(format!("write of {} is invalid", step.file));
+                        }
+
+                        if step.content.len() > 65536 {
+                            return Err(format!("write of {} is too large", step.file));
+                        }
+                    }
+                    InstallationStep::Chmod(step) => {
+                        if !is_safe_path(&step.location) {
+                            return Err(format!("chmod of {} is invalid", step.location));
+                        }
+
+                        if !(3..=4).contains(&step.mode.len())
+                            || !step.mode.chars().all(|c| ('0'..='7').contains(&c))
+                        {
+                            return Err(format!("chmod mode {} is invalid", step.mode));
+                        }
+                    }
+                    InstallationStep::Extract(step) => {
+                        if !is_safe_path(&step.file) || !is_safe_path(&step.location) {
+                            return Err(format!("extract of {} is invalid", step.file));
+                        }
+
+                        if !files.contains(step.file.as_str()) {
+                            return Err(format!(
+                                "extract of {} must come after its download",
+                                step.file
+                            ));
+                        }
+                    }
+                    InstallationStep::RunJava(step) => {
+                        if !is_safe_path(&step.file) {
+                            return Err(format!("java run of {} is invalid", step.file));
+                        }
+
+                        if !step.arguments.iter().all(|argument| {
+                            !argument.is_empty()
+                                && argument.chars().all(|c| {
+                                    c.is_ascii_alphanumeric()
+                                        || matches!(
+                                            c,
+                                            '.' | '_' | '-' | '/' | '+' | '=' | ':' | ','
+                                        )
+                                })
+                        }) {
+                            return Err(format!("java run arguments of {} are invalid", step.file));
+                        }
+                    }
+                }
+            }
+
+            files.extend(combined_files);
+        }
+
+        Ok(())
+    }
+
+    pub async fn hash_artifacts(
+        &mut self,
+        client: &reqwest::Client,
+        declared: &[BuildHash],
+    ) -> Result<Vec<BuildHash>, String> {
+        let mut urls: Vec<&str> = Vec::new();
+        for url in [&self.jar_url, &self.zip_url].into_iter().flatten() {
+            if !urls.contains(&url.as_str()) {
+                urls.push(url);
+            }
+        }
+        for step in self.installation.iter().flatten() {
+            if let InstallationStep::Download(step) = step
+                && !urls.contains(&step.url.as_str())
+            {
+                urls.push(&step.url);
+            }
+        }
+
+        let mut computed: Vec<(String, BuildHash, u64)> = Vec::new();
+        for (i, url) in urls.into_iter().enumerate() {
+            let (hash, size) = BuildHash::compute(client, url, i == 0).await?;
+
+            computed.push((url.to_string(), hash, size));
+        }
+
+        let size_of = |url: &str| {
+            computed
+                .iter()
+                .find(|(computed_url, _, _)| computed_url == url)
+                .map(|(_, _, size)| *size)
+                .unwrap_or_default()
+        };
+
+        for (url, declared_size) in [
+            (&self.jar_url, &mut self.jar_size),
+            (&self.zip_url, &mut self.zip_size),
+        ] {
+            if let Some(url) = url {
+                let size = size_of(url);
+
+                if declared_size.is_some_and(|declared_size| declared_size as u64 != size) {
+                    return Err(format!("size of {} does not match", url));
+                }
+
+                *declared_size =
+                    Some(i32::try_from(size).map_err(|_| format!("{} is too large", url))?);
+            }
+        }
+
+        for step in self.installation.iter_mut().flatten() {
+            if let InstallationStep::Download(step) = step
+                && let Some((_, hash, size)) = computed
+                    .iter()
+                    .find(|(computed_url, _, _)| *computed_url == step.url)
+            {
+                if step.size != 0 && step.size != *size {
+                    return Err(format!("size of {} does not match", step.url));
+                }
+
+                if step
+                    .sha256
+                    .as_ref()
+                    .is_some_and(|sha256| !sha256.eq_ignore_ascii_case(&hash.sha256))
+                {
+                    return Err(format!("sha256 of {} does not match", step.url));
+                }
+
+                step.size = *size;
+                step.sha256 = Some(hash.sha256.clone());
+            }
+        }
+
+        for hash in declared {
+            if !computed
+                .iter()
+                .any(|(_, computed_hash, _)| computed_hash.matches(hash))
+            {
+                return Err(format!("hash {} does not match", hash.sha256));
+            }
+        }
+
+        Ok(computed
+            .into_iter()
+            .map(|(_, hash, _)| hash)
+            .filter(|hash| hash.primary)
+            .collect())
+    }
+
+    pub async fn rehash(
+        mut self,
+        database: std::sync::Arc<crate::database::Database>,
+        cache: std::sync::Arc<crate::cache::Cache>,
+        client: reqwest::Client,
+        declared: Vec<BuildHash>,
+    ) {
+        let installation = serde_json::to_value(&self.installation).unwrap();
+
+        let hashes = match self.hash_artifacts(&client, &declared).await {
+            Ok(hashes) => hashes,
+            Err(err) => {
+                crate::logger::log(
+                    crate::logger::LoggerLevel::Error,
+                    format!(
+                        "{} failed to hash build {}: {}",
+                        "builds".bright_blue(),
+                        self.id.to_string().cyan(),
+                        err
+                    ),
+                );
+
+                return;
+            }
+        };
+
+        let mut current = match Self::by_id(&database, self.id).await {
+            Ok(Some(current)) => current,
+            Ok(None) => return,
+            Err(err) => {
+                err.report();
+
+                return;
+            }
+        };
+
+        if current.jar_url != self.jar_url
+            || current.zip_url != self.zip_url
+            || serde_json::to_value(&current.installation).unwrap() != installation
+        {
+            return;
+        }
+
+        current.jar_size = self.jar_size;
+        current.zip_size = self.zip_size;
+        current.installation = self.installation;
+
+        if let Err(err) = current.save(&database, Some(&hashes)).await {
+            err.report();
+
+            return;
+        }
+        if let Err(err) = current.invalidate_cache(&cache).await {
+            crate::cache::Cache::report(&err);
+        }
+    }
+
+    pub fn fill_primary_hash(&mut self, hash: &BuildHash) {
+        let url = match self.jar_url.as_ref().or(self.zip_url.as_ref()) {
+            Some(url) => url,
+            None => return,
+        };
+
+        for step in self.installation.iter_mut().flatten() {
+            if let InstallationStep::Download(step) = step
+                && step.sha256.is_none()
+                && step.url == *url
+            {
+                step.sha256 = Some(hash.sha256.clone());
+            }
+        }
+    }
+
     pub fn installation_size(&self) -> u64 {
         self.installation
             .iter()
             .flat_map(|step| step.iter())
             .filter_map(|step| match step {
                 InstallationStep::Download(step) => Some(step.size),
+                InstallationStep::WriteFile(step) => Some(step.content.len() as u64),
                 _ => None,
             })
             .sum()
@@ -187,8 +800,24 @@ impl Build {
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         identifier: &str,
-    ) -> Option<(Self, Self, super::version::MinifiedVersion)> {
-        cache.cached(&format!("build::{}", identifier), 3600, || async {
+        fields: Option<&BuildFields>,
+    ) -> Result<Option<(Self, Self, super::version::MinifiedVersion)>, crate::database::DatabaseError>
+    {
+        let fields = fields.map(|fields| fields.with_lookup_columns());
+        let key = match &fields {
+            Some(fields) => format!("build::{}::{}", identifier, fields.key()),
+            None => format!("build::{}", identifier),
+        };
+        let columns_sql = move |table: Option<&str>| match &fields {
+            Some(fields) => fields.columns_sql(None, table),
+            None => Self::columns_sql(None, table),
+        };
+        let database = database.clone();
+        let identifier = identifier.to_string();
+
+        cache.cached_tagged(&key, 3600, |result: &Option<(Self, Self, super::version::MinifiedVersion)>| {
+            result.iter().flat_map(|(build, latest, _)| [Self::cache_tag(build.id), latest.line_cache_tag()]).collect()
+        }, move || async move {
         let hash: Option<&str> = match identifier.len() {
             32 => Some("md5"),
             40 => Some("sha1"),
@@ -199,12 +828,12 @@ impl Build {
             _ => {
                 if let Ok(id) = identifier.parse::<i32>() {
                     if id < 1 {
-                        return None;
+                        return Ok(None);
                     } else {
                         None
                     }
                 } else {
-                    return None;
+                    return Ok(None);
                 }
             }
         };
@@ -268,25 +897,24 @@ impl Build {
             ) x
             LEFT JOIN minecraft_versions mv ON mv.id = x.version_id
             "#,
-            Self::columns_sql(None, None),
+            columns_sql(None),
             if let Some(hash) = hash {
                 format!("build_hashes INNER JOIN builds ON builds.id = build_hashes.build_id WHERE {} = $1", hash)
             } else {
                 "builds WHERE builds.id = $1::int".to_string()
             },
-            Self::columns_sql(None, Some("b")),
-            Self::columns_sql(None, Some("b"))
+            columns_sql(Some("b")),
+            columns_sql(Some("b"))
         ))
         .bind(identifier)
         .fetch_all(database.read())
-        .await
-        .unwrap();
+        .await?;
 
         if query.len() != 2 {
-            return None;
+            return Ok(None);
         }
 
-        Some((
+        Ok(Some((
             Self::map(None, &query[0]),
             Self::map(None, &query[1]),
             super::version::MinifiedVersion {
@@ -306,7 +934,7 @@ impl Build {
                     .try_get("version_created")
                     .unwrap_or(query[1].try_get("version2_created").unwrap_or_default()),
             },
-        ))})
+        )))})
         .await
     }
 
@@ -316,7 +944,8 @@ impl Build {
         version_location: &str,
         version_id: &str,
         build_number: Option<i32>,
-    ) -> Option<Self> {
+        fields: Option<&BuildFields>,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
         let data = sqlx::query(&format!(
             r#"
             SELECT {}
@@ -327,7 +956,9 @@ impl Build {
                 {}
             ORDER BY id DESC
             "#,
-            Self::columns_sql(None, None),
+            fields
+                .map(|fields| fields.columns_sql(None, None))
+                .unwrap_or_else(|| Self::columns_sql(None, None)),
             version_location,
             if build_number.is_some() {
                 "AND build_number = $3"
@@ -338,13 +969,10 @@ impl Build {
         .bind(version_id)
         .bind(r#type.to_string())
         .bind(build_number)
-        .fetch_one(database.read())
-        .await;
+        .fetch_optional(database.read())
+        .await?;
 
-        match data {
-            Ok(data) => Some(Self::map(None, &data)),
-            Err(_) => None,
-        }
+        Ok(data.map(|data| Self::map(None, &data)))
     }
 
     pub async fn all_for_version(
@@ -352,8 +980,8 @@ impl Build {
         r#type: ServerType,
         version_location: &str,
         version_id: &str,
-    ) -> Vec<Self> {
-        sqlx::query(&format!(
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
             r#"
             SELECT {}
             FROM builds
@@ -368,18 +996,432 @@ impl Build {
         .bind(version_id)
         .bind(r#type.to_string())
         .fetch_all(database.read())
-        .await
-        .unwrap()
+        .await?
+        .into_iter()
+        .map(|row| Self::map(None, &row))
+        .collect())
+    }
+
+    pub async fn page_for_version(
+        database: &crate::database::Database,
+        r#type: ServerType,
+        version_location: &str,
+        version_id: &str,
+        filter: &BuildFilter,
+        fields: Option<&BuildFields>,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM builds
+            WHERE
+                {} = $1
+                AND type = $2::server_type
+                AND ($3::int IS NULL OR id < $3)
+                AND ($4::bool IS NULL OR experimental = $4)
+                AND ($5::timestamp IS NULL OR created >= $5)
+            ORDER BY id DESC
+            LIMIT $6
+            "#,
+            fields
+                .map(|fields| fields.columns_sql(None, None))
+                .unwrap_or_else(|| Self::columns_sql(None, None)),
+            version_location
+        ))
+        .bind(version_id)
+        .bind(r#type.to_string())
+        .bind(filter.cursor)
+        .bind(filter.experimental)
+        .bind(filter.since)
+        .bind(filter.limit)
+        .fetch_all(database.read())
+        .await?
         .into_iter()
         .map(|row| Self::map(None, &row))
-        .collect()
+        .collect())
+    }
+
+    pub async fn new(
+        database: &crate::database::Database,
+        build: &Build,
+        hashes: &[BuildHash],
+    ) -> Result<Self, crate::database::DatabaseError> {
+        let mut transaction = database.write().begin().await?;
+
+        if let Some(project_version_id) = &build.project_version_id {
+            sqlx::query(
+                r#"
+                INSERT INTO project_versions (id, type)
+                VALUES ($1, $2::server_type)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(project_version_id)
+            .bind(build.r#type.to_string())
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO builds (
+                version_id, project_version_id, type, experimental, build_number,
+                jar_url, jar_size, zip_url, zip_size, metadata, installation, changes, created
+            )
+            VALUES ($1, $2, $3::server_type, $4, $5, $6, $7, $8, $9, '{{}}'::jsonb, $10, $11, COALESCE($12, NOW()))
+            RETURNING {}
+            "#,
+            Self::columns_sql(None, None)
+        ))
+        .bind(&build.version_id)
+        .bind(&build.project_version_id)
+        .bind(build.r#type.to_string())
+        .bind(build.experimental)
+        .bind(build.build_number)
+        .bind(&build.jar_url)
+        .bind(build.jar_size)
+        .bind(&build.zip_url)
+        .bind(build.zip_size)
+        .bind(serde_json::to_value(&build.installation).unwrap())
+        .bind(serde_json::to_value(&build.changes).unwrap())
+        .bind(build.created)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let build = Self::map(None, &row);
+
+        for hash in hashes {
+            sqlx::query(
+                r#"
+                INSERT INTO build_hashes (build_id, "primary", sha1, sha224, sha256, sha384, sha512, md5)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(build.id)
+            .bind(hash.primary)
+            .bind(&hash.sha1)
+            .bind(&hash.sha224)
+            .bind(&hash.sha256)
+            .bind(&hash.sha384)
+            .bind(&hash.sha512)
+            .bind(&hash.md5)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(build)
+    }
+
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+        hashes: Option<&[BuildHash]>,
+    ) -> Result<(), crate::database::DatabaseError> {
+        let mut transaction = database.write().begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE builds
+            SET
+                experimental = $2,
+                build_number = $3,
+                jar_url = $4,
+                jar_size = $5,
+                zip_url = $6,
+                zip_size = $7,
+                installation = $8,
+                changes = $9
+            WHERE builds.id = $1
+            "#,
+        )
+        .bind(self.id)
+        .bind(self.experimental)
+        .bind(self.build_number)
+        .bind(&self.jar_url)
+        .bind(self.jar_size)
+        .bind(&self.zip_url)
+        .bind(self.zip_size)
+        .bind(serde_json::to_value(&self.installation).unwrap())
+        .bind(serde_json::to_value(&self.changes).unwrap())
+        .execute(&mut *transaction)
+        .await?;
+
+        if let Some(hashes) = hashes {
+            sqlx::query("DELETE FROM build_hashes WHERE build_hashes.build_id = $1")
+                .bind(self.id)
+                .execute(&mut *transaction)
+                .await?;
+
+            for hash in hashes {
+                sqlx::query(
+                    r#"
+                    INSERT INTO build_hashes (build_id, "primary", sha1, sha224, sha256, sha384, sha512, md5)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    "#,
+                )
+                .bind(self.id)
+                .bind(hash.primary)
+                .bind(&hash.sha1)
+                .bind(&hash.sha224)
+                .bind(&hash.sha256)
+                .bind(&hash.sha384)
+                .bind(&hash.sha512)
+                .bind(&hash.md5)
+                .execute(&mut *transaction)
+                .await?;
+            }
+        }
+
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        if id < 1 {
+            return Ok(None);
+        }
+
+        Ok(sqlx::query(&format!(
+            "SELECT {} FROM builds WHERE builds.id = $1",
+            Self::columns_sql(None, None)
+        ))
+        .bind(id)
+        .fetch_optional(database.read())
+        .await?
+        .map(|row| Self::map(None, &row)))
+    }
+
+    pub async fn delete_by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM builds
+            WHERE builds.id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(database.write())
+        .await?
+        .rows_affected()
+            == 1)
+    }
+
+    pub fn cache_tag(id: i32) -> String {
+        format!("build:{}", id)
+    }
+
+    pub fn line_cache_tag(&self) -> String {
+        format!(
+            "build_line:{}:{}",
+            self.r#type,
+            self.version_id
+                .as_deref()
+                .or(self.project_version_id.as_deref())
+                .unwrap_or_default()
+        )
+    }
+
+    pub async fn project_version_exists(
+        database: &crate::database::Database,
+        r#type: ServerType,
+        id: &str,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(
+            sqlx::query("SELECT 1 FROM project_versions WHERE id = $1 AND type = $2::server_type")
+                .bind(id)
+                .bind(r#type.to_string())
+                .fetch_optional(database.read())
+                .await?
+                .is_some(),
+        )
+    }
+
+    pub async fn invalidate_cache(
+        &self,
+        cache: &crate::cache::Cache,
+    ) -> Result<(), crate::cache::CacheError> {
+        let mut keys = vec![
+            "types::all".to_string(),
+            format!("versions::{}", self.r#type),
+            format!("build::{}", self.id),
+            format!("build::{}::hash", self.id),
+        ];
+
+        for version in [&self.version_id, &self.project_version_id]
+            .into_iter()
+            .flatten()
+        {
+            keys.push(format!("builds::{}::{}", self.r#type, version));
+            keys.push(format!(
+                "build::{}::{}::{}",
+                self.r#type, version, self.build_number
+            ));
+            keys.push(format!("build::{}::{}::latest", self.r#type, version));
+            keys.push(format!("version::{}::builds", version));
+            keys.push(format!("version_location::{}::{}", self.r#type, version));
+            keys.push(format!("version::{}::stats", version));
+        }
+
+        cache.invalidate(keys).await?;
+        cache.invalidate_tag(&Self::cache_tag(self.id)).await?;
+        cache.invalidate_tag(&self.line_cache_tag()).await
+    }
+
+    pub async fn all_identifiers_for_type(
+        database: &crate::database::Database,
+        r#type: ServerType,
+    ) -> Result<Vec<(Option<String>, Option<String>, i32)>, crate::database::DatabaseError> {
+        Ok(sqlx::query(
+            r#"
+            SELECT version_id, project_version_id, build_number
+            FROM builds
+            WHERE type = $1::server_type
+            "#,
+        )
+        .bind(r#type.to_string())
+        .fetch_all(database.read())
+        .await?
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect())
+    }
+
+    pub async fn latest_id(
+        database: &crate::database::Database,
+    ) -> Result<i32, crate::database::DatabaseError> {
+        Ok(sqlx::query("SELECT COALESCE(MAX(id), 0) FROM builds")
+            .fetch_one(database.read())
+            .await?
+            .get(0))
+    }
+
+    pub async fn all_after_id(
+        database: &crate::database::Database,
+        id: i32,
+        limit: i64,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM builds
+            WHERE id > $1
+            ORDER BY id ASC
+            LIMIT $2
+            "#,
+            Self::columns_sql(None, None)
+        ))
+        .bind(id)
+        .bind(limit)
+        .fetch_all(database.read())
+        .await?
+        .into_iter()
+        .map(|row| Self::map(None, &row))
+        .collect())
+    }
+
+    pub async fn upgrade_target(
+        database: &crate::database::Database,
+        build: &Build,
+        policy: UpgradePolicy,
+    ) -> Result<Option<(Self, Option<i16>, Option<NaiveDateTime>)>, crate::database::DatabaseError>
+    {
+        let filter = match policy {
+            UpgradePolicy::SameVersion => "COALESCE(b.version_id, b.project_version_id) = $2",
+            UpgradePolicy::Stable => "NOT b.experimental",
+            UpgradePolicy::LatestSupported => "(b.version_id IS NULL OR mv.supported)",
+        };
+
+        let query = format!(
+            r#"
+            SELECT {}, mv.java AS java, COALESCE(
+                mv.created,
+                (SELECT min(lb.created) FROM builds lb WHERE lb.project_version_id = b.project_version_id)
+            ) AS line_created
+            FROM builds b
+            LEFT JOIN minecraft_versions mv ON mv.id = b.version_id
+            WHERE b.type = $1::server_type AND {}
+            ORDER BY line_created DESC NULLS LAST, b.build_number DESC, b.id DESC
+            LIMIT 1
+            "#,
+            Self::columns_sql(None, Some("b")),
+            filter
+        );
+
+        let mut query = sqlx::query(&query).bind(build.r#type.to_string());
+        if matches!(policy, UpgradePolicy::SameVersion) {
+            query = query.bind(
+                build
+                    .version_id
+                    .as_ref()
+                    .or(build.project_version_id.as_ref()),
+            );
+        }
+
+        Ok(query.fetch_optional(database.read()).await?.map(|row| {
+            (
+                Self::map(None, &row),
+                row.get("java"),
+                row.get("line_created"),
+            )
+        }))
+    }
+
+    pub async fn all_between(
+        database: &crate::database::Database,
+        from: &Build,
+        to: &Build,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        let line = to.version_id.as_ref().or(to.project_version_id.as_ref());
+        let after = if from
+            .version_id
+            .as_ref()
+            .or(from.project_version_id.as_ref())
+            == line
+        {
+            from.build_number
+        } else {
+            i32::MIN
+        };
+
+        Ok(sqlx::query(&format!(
+            r#"
+            SELECT {}
+            FROM builds
+            WHERE
+                type = $1::server_type
+                AND COALESCE(version_id, project_version_id) = $2
+                AND build_number > $3
+                AND build_number <= $4
+                AND jsonb_array_length(changes) > 0
+            ORDER BY build_number ASC, id ASC
+            LIMIT 500
+            "#,
+            Self::columns_sql(None, None)
+        ))
+        .bind(to.r#type.to_string())
+        .bind(line)
+        .bind(after)
+        .bind(to.build_number)
+        .fetch_all(database.read())
+        .await?
+        .into_iter()
+        .map(|row| Self::map(None, &row))
+        .collect())
     }
 
     pub async fn all_for_minecraft_version(
         database: &crate::database::Database,
         version_id: &str,
-    ) -> Vec<Self> {
-        sqlx::query(&format!(
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
             r#"
             SELECT {}
             FROM builds
@@ -390,10 +1432,9 @@ impl Build {
         ))
         .bind(version_id)
         .fetch_all(database.read())
-        .await
-        .unwrap()
+        .await?
         .into_iter()
         .map(|row| Self::map(None, &row))
-        .collect()
+        .collect())
     }
 }
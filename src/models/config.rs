@@ -1,10 +1,11 @@
-use super::r#type::ServerType;
+use super::{BaseModel, build::Build, r#type::ServerType};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use sqlx::Row;
 use std::{fmt::Display, sync::LazyLock};
 use utoipa::ToSchema;
 
-#[derive(ToSchema, Serialize, Deserialize, Clone, Copy)]
+#[derive(ToSchema, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 #[schema(rename_all = "UPPERCASE")]
 pub enum Format {
@@ -24,25 +25,548 @@ impl Display for Format {
     }
 }
 
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ConfigDiffEntry {
+    pub path: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ConfigDiffChange {
+    pub path: String,
+    pub from: serde_json::Value,
+    pub to: serde_json::Value,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Default)]
+pub struct ConfigDiff {
+    pub added: Vec<ConfigDiffEntry>,
+    pub removed: Vec<ConfigDiffEntry>,
+    pub changed: Vec<ConfigDiffChange>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ConfigRename {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Default)]
+pub struct ConfigMigration {
+    pub carried: Vec<String>,
+    pub renamed: Vec<ConfigRename>,
+    pub dropped: Vec<ConfigDiffEntry>,
+    pub cleared: Vec<String>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ConfigTypeMismatch {
+    pub path: String,
+    pub expected: String,
+    pub found: String,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Default)]
+pub struct ConfigValidation {
+    pub unknown: Vec<ConfigDiffEntry>,
+    pub mismatched: Vec<ConfigTypeMismatch>,
+    pub missing: Vec<ConfigDiffEntry>,
+}
+
+struct Hocon {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Hocon {
+    fn parse(content: &str) -> Result<serde_json::Value, String> {
+        let mut parser = Self {
+            chars: content.chars().collect(),
+            position: 0,
+        };
+
+        parser.skip(true);
+        let value = if parser.peek() == Some('{') {
+            parser.position += 1;
+            parser.object(Some('}'))?
+        } else {
+            parser.object(None)?
+        };
+
+        parser.skip(true);
+        if parser.position < parser.chars.len() {
+            return Err(format!("unexpected input at {}", parser.position));
+        }
+
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn starts_with(&self, value: &str) -> bool {
+        value
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.position + i) == Some(&c))
+    }
+
+    fn skip(&mut self, newlines: bool) {
+        while let Some(c) = self.peek() {
+            if c == '#' || self.starts_with("//") {
+                while let Some(c) = self.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+
+                    self.position += 1;
+                }
+            } else if c == '\n' && !newlines {
+                break;
+            } else if c.is_whitespace() || c == '\u{feff}' {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn separators(&mut self) {
+        loop {
+            self.skip(true);
+
+            if self.peek() == Some(',') {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn object(&mut self, closing: Option<char>) -> Result<serde_json::Value, String> {
+        let mut object = serde_json::Value::Object(serde_json::Map::new());
+
+        loop {
+            self.separators();
+
+            match self.peek() {
+                None if closing.is_none() => break,
+                None => return Err("unexpected end of object".to_string()),
+                Some(c) if Some(c) == closing => {
+                    self.position += 1;
+                    break;
+                }
+                _ => {}
+            }
+
+            if self.starts_with("include ") {
+                while let Some(c) = self.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+
+                    self.position += 1;
+                }
+
+                continue;
+            }
+
+            let path = self.key()?;
+            self.skip(false);
+
+            let (value, append) = match self.peek() {
+                Some('{') => (self.value()?, false),
+                Some('=') | Some(':') => {
+                    self.position += 1;
+                    self.skip(false);
+
+                    (self.value()?, false)
+                }
+                Some('+') if self.starts_with("+=") => {
+                    self.position += 2;
+                    self.skip(false);
+
+                    (self.value()?, true)
+                }
+                _ => return Err(format!("expected separator at {}", self.position)),
+            };
+
+            Self::insert(&mut object, &path, value, append);
+        }
+
+        Ok(object)
+    }
+
+    fn key(&mut self) -> Result<Vec<String>, String> {
+        let mut path = Vec::new();
+
+        loop {
+            let segment = if self.peek() == Some('"') {
+                self.quoted()?
+            } else {
+                let start = self.position;
+
+                while let Some(c) = self.peek() {
+                    if c.is_whitespace() || "=:{}[],.#+\"".contains(c) || self.starts_with("//") {
+                        break;
+                    }
+
+                    self.position += 1;
+                }
+
+                if start == self.position {
+                    return Err(format!("expected key at {}", self.position));
+                }
+
+                self.chars[start..self.position].iter().collect()
+            };
+
+            path.push(segment);
+
+            if self.peek() == Some('.') {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+
+        Ok(path)
+    }
+
+    fn quoted(&mut self) -> Result<String, String> {
+        if self.starts_with("\"\"\"") {
+            self.position += 3;
+            let start = self.position;
+
+            while !self.starts_with("\"\"\"") {
+                if self.peek().is_none() {
+                    return Err("unterminated string".to_string());
+                }
+
+                self.position += 1;
+            }
+
+            let value = self.chars[start..self.position].iter().collect();
+            self.position += 3;
+
+            return Ok(value);
+        }
+
+        self.position += 1;
+        let mut value = String::new();
+
+        loop {
+            match self.peek() {
+                None | Some('\n') => return Err("unterminated string".to_string()),
+                Some('"') => {
+                    self.position += 1;
+                    break;
+                }
+                Some('\\') => {
+                    self.position += 1;
+
+                    match self.peek() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some('r') => value.push('\r'),
+                        Some('b') => value.push('\u{8}'),
+                        Some('f') => value.push('\u{c}'),
+                        Some('u') => {
+                            let code: String = self
+                                .chars
+                                .get(self.position + 1..self.position + 5)
+                                .ok_or("invalid escape")?
+                                .iter()
+                                .collect();
+
+                            value.push(
+                                u32::from_str_radix(&code, 16)
+                                    .ok()
+                                    .and_then(char::from_u32)
+                                    .ok_or("invalid escape")?,
+                            );
+                            self.position += 4;
+                        }
+                        Some(c) => value.push(c),
+                        None => return Err("unterminated string".to_string()),
+                    }
+
+                    self.position += 1;
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<serde_json::Value, String> {
+        match self.peek() {
+            Some('{') => {
+                self.position += 1;
+                self.object(Some('}'))
+            }
+            Some('[') => {
+                self.position += 1;
+                let mut array = Vec::new();
+
+                loop {
+                    self.separators();
+
+                    match self.peek() {
+                        None => return Err("unexpected end of array".to_string()),
+                        Some(']') => {
+                            self.position += 1;
+                            break;
+                        }
+                        _ => array.push(self.value()?),
+                    }
+                }
+
+                Ok(serde_json::Value::Array(array))
+            }
+            Some('"') => Ok(serde_json::Value::String(self.quoted()?)),
+            _ => {
+                let start = self.position;
+
+                while let Some(c) = self.peek() {
+                    if "\n,}]#".contains(c) || self.starts_with("//") {
+                        break;
+                    }
+
+                    self.position += 1;
+                }
+
+                let raw: String = self.chars[start..self.position].iter().collect();
+                let raw = raw.trim();
+
+                Ok(match raw {
+                    "true" => serde_json::Value::Bool(true),
+                    "false" => serde_json::Value::Bool(false),
+                    "null" => serde_json::Value::Null,
+                    raw => {
+                        if let Ok(number) = raw.parse::<i64>() {
+                            serde_json::Value::from(number)
+                        } else if let Some(number) = raw
+                            .parse::<f64>()
+                            .ok()
+                            .and_then(serde_json::Number::from_f64)
+                        {
+                            serde_json::Value::Number(number)
+                        } else {
+                            serde_json::Value::String(raw.to_string())
+                        }
+                    }
+                })
+            }
+        }
+    }
+
+    fn key_string(key: &str) -> String {
+        if !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            key.to_string()
+        } else {
+            serde_json::to_string(key).unwrap()
+        }
+    }
+
+    fn write(value: &serde_json::Value, indent: usize, output: &mut String) {
+        if let serde_json::Value::Object(map) = value {
+            for (key, value) in map {
+                output.push_str(&" ".repeat(indent));
+                output.push_str(&Self::key_string(key));
+
+                match value {
+                    serde_json::Value::Object(_) => {
+                        output.push_str(" {\n");
+                        Self::write(value, indent + 4, output);
+                        output.push_str(&" ".repeat(indent));
+                        output.push_str("}\n");
+                    }
+                    value => {
+                        output.push('=');
+                        output.push_str(&value.to_string());
+                        output.push('\n');
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert(
+        object: &mut serde_json::Value,
+        path: &[String],
+        value: serde_json::Value,
+        append: bool,
+    ) {
+        let mut current = object;
+
+        for segment in &path[..path.len() - 1] {
+            let map = current.as_object_mut().unwrap();
+            let entry = map
+                .entry(segment.clone())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+
+            if !entry.is_object() {
+                *entry = serde_json::Value::Object(serde_json::Map::new());
+            }
+
+            current = entry;
+        }
+
+        let map = current.as_object_mut().unwrap();
+        let key = path[path.len() - 1].clone();
+
+        if !append
+            && map.get(&key).is_some_and(|existing| existing.is_object())
+            && let serde_json::Value::Object(value) = value
+        {
+            let existing = map.get_mut(&key).unwrap();
+
+            for (k, v) in value {
+                Self::insert(existing, &[k], v, false);
+            }
+        } else if append {
+            match map.get_mut(&key) {
+                Some(serde_json::Value::Array(existing)) => existing.push(value),
+                _ => {
+                    map.insert(key, serde_json::Value::Array(vec![value]));
+                }
+            }
+        } else {
+            map.insert(key, value);
+        }
+    }
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> serde_json::Value {
+    match value {
+        serde_yaml::Value::Mapping(map) => serde_json::Value::Object(
+            map.into_iter()
+                .map(|(k, v)| {
+                    let key = match k {
+                        serde_yaml::Value::String(s) => s,
+                        k => serde_yaml::to_string(&k)
+                            .unwrap_or_default()
+                            .trim()
+                            .to_string(),
+                    };
+
+                    (key, yaml_to_json(v))
+                })
+                .collect(),
+        ),
+        serde_yaml::Value::Sequence(seq) => {
+            serde_json::Value::Array(seq.into_iter().map(yaml_to_json).collect())
+        }
+        serde_yaml::Value::Tagged(tagged) => yaml_to_json(tagged.value),
+        value => serde_json::to_value(value).unwrap_or_default(),
+    }
+}
+
+#[derive(Clone)]
+pub enum Redaction {
+    Key(&'static str),
+    Glob(&'static str),
+    Regex(regex::Regex),
+}
+
+impl Redaction {
+    fn glob(pattern: &[u8], path: &[u8]) -> bool {
+        match pattern {
+            [] => path.is_empty(),
+            [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| Self::glob(rest, &path[i..])),
+            [b'*', rest @ ..] => (0..=path.len())
+                .take_while(|i| *i == 0 || path[i - 1] != b'.')
+                .any(|i| Self::glob(rest, &path[i..])),
+            [b'?', rest @ ..] => !path.is_empty() && Self::glob(rest, &path[1..]),
+            [c, rest @ ..] => path.first() == Some(c) && Self::glob(rest, &path[1..]),
+        }
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        match self {
+            Redaction::Key(key) => path == *key,
+            Redaction::Glob(glob) => Self::glob(glob.as_bytes(), path.as_bytes()),
+            Redaction::Regex(regex) => regex.is_match(path),
+        }
+    }
+}
+
+pub static REDACTIONS: LazyLock<Vec<Redaction>> = LazyLock::new(|| {
+    vec![
+        Redaction::Regex(regex::Regex::new(r"(^|\.)seed-[^.]*$").unwrap()),
+        Redaction::Regex(regex::Regex::new(r"(^|[._-])(password|secret|token)$").unwrap()),
+        Redaction::Regex(
+            regex::Regex::new(
+                r"(^|\.)(database|mysql|mariadb|postgres|postgresql|mongodb|redis)\.(user|username|password|host|url|uri)$",
+            )
+            .unwrap(),
+        ),
+    ]
+});
+
 #[derive(ToSchema, Serialize, Deserialize, Clone)]
 pub struct Config {
     pub r#type: ServerType,
     pub format: Format,
     pub aliases: Vec<String>,
+
+    #[serde(skip)]
+    pub redactions: Vec<Redaction>,
 }
 
 impl Config {
-    pub fn by_alias(alias: &String) -> Option<&Config> {
+    pub fn by_alias(alias: &str) -> Option<&'static Config> {
         CONFIGS
             .iter()
-            .find(|(_, config)| config.aliases.contains(alias))
+            .find(|(_, config)| config.aliases.iter().any(|a| a == alias))
             .map(|(_, config)| config)
     }
 
+    pub fn by_path(path: &str) -> Option<(&'static str, &'static Config)> {
+        let path = path.trim_start_matches("./").replace('\\', "/");
+        if path
+            .split('/')
+            .any(|segment| segment == "plugins" || segment == "mods")
+        {
+            return None;
+        }
+
+        CONFIGS
+            .iter()
+            .filter(|(location, _)| {
+                path == location.as_str() || path.ends_with(&format!("/{}", location))
+            })
+            .max_by_key(|(location, _)| location.len())
+            .map(|(location, config)| (location.as_str(), config))
+    }
+
+    pub fn redacts(&self, path: &str) -> bool {
+        REDACTIONS
+            .iter()
+            .chain(self.redactions.iter())
+            .any(|redaction| redaction.matches(path))
+    }
+
     pub fn format(
         file: &str,
         content: &str,
     ) -> Result<(String, Option<String>), Box<dyn std::error::Error>> {
+        let config = Self::by_alias(file);
+        let redacts = |path: &str| match config {
+            Some(config) => config.redacts(path),
+            None => REDACTIONS.iter().any(|redaction| redaction.matches(path)),
+        };
+
         let mut value = "".to_string();
         let mut contains: Option<String> = None;
 
@@ -56,37 +580,21 @@ impl Config {
         }
 
         if file.ends_with(".properties") {
-            let mut data = value.lines().collect::<Vec<&str>>();
+            let mut data = value
+                .lines()
+                .map(|line| match line.split_once('=') {
+                    Some((key, value)) if !value.trim().is_empty() && redacts(key.trim()) => {
+                        format!("{}=xxx", key)
+                    }
+                    _ => line.to_string(),
+                })
+                .collect::<Vec<String>>();
             data.sort();
 
             value = data.join("\n");
         } else if file.ends_with(".yml") || file.ends_with(".yaml") {
             let mut parsed: serde_yaml::Value = serde_yaml::from_str(&value)?;
 
-            match file {
-                "config.yml" => {
-                    if let Some(stats_uuid) = parsed.get_mut("stats_uuid") {
-                        if stats_uuid.is_string() {
-                            *stats_uuid = serde_yaml::Value::String("xxx".to_string());
-                        }
-                    }
-
-                    if let Some(stats) = parsed.get_mut("stats") {
-                        if stats.is_string() {
-                            *stats = serde_yaml::Value::String("xxx".to_string());
-                        }
-                    }
-                }
-                "leaves.yml" => {
-                    if let Some(server_id) = parsed.get_mut("server-id") {
-                        if server_id.is_string() {
-                            *server_id = serde_yaml::Value::String("xxx".to_string());
-                        }
-                    }
-                }
-                _ => {}
-            }
-
             if file != "pufferfish.yml" && contains.is_none() {
                 if let Some(version) = parsed.get("config-version") {
                     if version.is_string() {
@@ -103,7 +611,7 @@ impl Config {
                 }
             }
 
-            Self::process_yaml_keys_recursively(&mut parsed, None);
+            Self::process_yaml_keys_recursively(&mut parsed, "", &redacts);
             value = serde_yaml::to_string(&parsed).unwrap();
         } else if file.ends_with(".toml") && contains.is_none() {
             let parsed: toml::Value = toml::from_str(&value)?;
@@ -121,23 +629,636 @@ impl Config {
                     ));
                 }
             }
-        }
+        } else if file.ends_with(".conf") {
+            let mut parsed = Hocon::parse(&value)?;
 
-        if file == "velocity.toml" {
-            for line in value.lines() {
-                if line.starts_with("forwarding-secret =") {
-                    value = value.replace(line, r#"forwarding-secret = "xxx""#);
-                    break;
+            if contains.is_none() {
+                let root = match parsed.as_object() {
+                    Some(map) if map.len() == 1 && map.values().all(|v| v.is_object()) => {
+                        map.values().next().unwrap()
+                    }
+                    _ => &parsed,
+                };
+
+                for key in ["config-version", "version"] {
+                    if let Some(version) = root.get(key)
+                        && (version.is_string() || version.is_i64())
+                    {
+                        contains = Some(format!("{}={}", key, version));
+                        break;
+                    }
                 }
             }
+
+            Self::process_json_keys_recursively(&mut parsed, "", &redacts);
+
+            value = String::new();
+            Hocon::write(&parsed, 0, &mut value);
+        }
+
+        if file.ends_with(".toml") {
+            let mut section = String::new();
+
+            value = value
+                .lines()
+                .map(|line| {
+                    let trimmed = line.trim();
+
+                    if trimmed.starts_with('[') {
+                        section = trimmed
+                            .trim_matches(['[', ']'])
+                            .split('.')
+                            .map(|part| part.trim().trim_matches(['"', '\'']))
+                            .collect::<Vec<&str>>()
+                            .join(".");
+
+                        return line.to_string();
+                    }
+
+                    match line.split_once('=') {
+                        Some((key, _)) => {
+                            let key_path = key.trim().trim_matches(['"', '\'']);
+                            let path = if section.is_empty() {
+                                key_path.to_string()
+                            } else {
+                                format!("{}.{}", section, key_path)
+                            };
+
+                            if redacts(&path) {
+                                format!("{}= \"xxx\"", key)
+                            } else {
+                                line.to_string()
+                            }
+                        }
+                        _ => line.to_string(),
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n");
+            value.push('\n');
         }
 
         Ok((value, contains))
     }
 
+    pub async fn similar(
+        database: &crate::database::Database,
+        config: &Config,
+        formatted: &str,
+        contains: Option<String>,
+    ) -> Result<Vec<(Build, String)>, crate::database::DatabaseError> {
+        let data = if let Some(contains) = contains {
+            sqlx::query(&format!(
+                r#"
+                SELECT
+                    {},
+                    config_values.value
+                FROM build_configs
+                INNER JOIN config_values ON config_values.id = build_configs.config_value_id
+                INNER JOIN configs ON configs.id = config_values.config_id
+                INNER JOIN builds ON
+                    builds.id = build_configs.build_id
+                    AND builds.type = $1::server_type
+                WHERE
+                    configs.type = $1::server_type
+                    AND configs.format = $2::format
+                    AND configs.location = $3
+                    AND config_values.value LIKE '%' || $4 || '%'
+                GROUP BY config_values.id, builds.id
+                LIMIT 3
+                "#,
+                Build::columns_sql(None, None)
+            ))
+            .bind(config.r#type.to_string())
+            .bind(config.format.to_string())
+            .bind(config.aliases[0].clone())
+            .bind(contains)
+            .fetch_all(database.read())
+            .await?
+        } else {
+            sqlx::query(&format!(
+                r#"
+                SELECT
+                    {},
+                    config_values.value,
+                    SIMILARITY(config_values.value, $4) AS similarity
+                FROM build_configs
+                INNER JOIN config_values ON config_values.id = build_configs.config_value_id
+                INNER JOIN configs ON configs.id = config_values.config_id
+                INNER JOIN builds ON
+                    builds.id = build_configs.build_id
+                    AND builds.type = $1::server_type
+                WHERE
+                    configs.type = $1::server_type
+                    AND configs.format = $2::format
+                    AND configs.location = $3
+                ORDER BY similarity DESC
+                LIMIT 3
+                "#,
+                Build::columns_sql(None, None)
+            ))
+            .bind(config.r#type.to_string())
+            .bind(config.format.to_string())
+            .bind(config.aliases[0].clone())
+            .bind(formatted)
+            .fetch_all(database.read())
+            .await?
+        };
+
+        Ok(data
+            .into_iter()
+            .map(|row| (Build::map(None, &row), row.get("value")))
+            .collect())
+    }
+
+    pub async fn value_for_build(
+        database: &crate::database::Database,
+        config: &Config,
+        build: i32,
+    ) -> Result<Option<String>, crate::database::DatabaseError> {
+        Ok(sqlx::query(
+            r#"
+            SELECT config_values.value
+            FROM build_configs
+            INNER JOIN config_values ON config_values.id = build_configs.config_value_id
+            INNER JOIN configs ON configs.id = config_values.config_id
+            WHERE
+                build_configs.build_id = $1
+                AND configs.format = $2::format
+                AND configs.location = $3
+            LIMIT 1
+            "#,
+        )
+        .bind(build)
+        .bind(config.format.to_string())
+        .bind(config.aliases[0].clone())
+        .fetch_optional(database.read())
+        .await?
+        .map(|row| row.get("value")))
+    }
+
+    fn digests(value: &str) -> [String; 6] {
+        use sha1::Digest;
+
+        [
+            format!("{:x}", sha1::Sha1::digest(value)),
+            format!("{:x}", sha2::Sha224::digest(value)),
+            format!("{:x}", sha2::Sha256::digest(value)),
+            format!("{:x}", sha2::Sha384::digest(value)),
+            format!("{:x}", sha2::Sha512::digest(value)),
+            format!("{:x}", md5::Md5::digest(value)),
+        ]
+    }
+
+    pub async fn backfill(
+        database: &crate::database::Database,
+    ) -> Result<u64, crate::database::DatabaseError> {
+        let mut transaction = database.write().begin().await?;
+        let mut updated = 0;
+        let mut last = 0;
+
+        loop {
+            let rows = sqlx::query(
+                r#"
+                SELECT config_values.id, config_values.config_id, config_values.value, configs.location
+                FROM config_values
+                INNER JOIN configs ON configs.id = config_values.config_id
+                WHERE config_values.id > $1
+                ORDER BY config_values.id
+                LIMIT 500
+                "#,
+            )
+            .bind(last)
+            .fetch_all(&mut *transaction)
+            .await?;
+
+            let Some(row) = rows.last() else {
+                break;
+            };
+            last = row.get::<i32, _>("id");
+
+            for row in rows {
+                let id: i32 = row.get("id");
+                let config_id: i32 = row.get("config_id");
+                let value: String = row.get("value");
+                let Ok((formatted, _)) = Self::format(row.get("location"), &value) else {
+                    continue;
+                };
+
+                if formatted == value {
+                    continue;
+                }
+
+                let [sha1, sha224, sha256, sha384, sha512, md5] = Self::digests(&formatted);
+
+                let existing: Option<i32> = sqlx::query_scalar(
+                    r#"
+                    SELECT id
+                    FROM config_values
+                    WHERE
+                        config_id = $1
+                        AND sha1 = $2
+                        AND sha224 = $3
+                        AND sha256 = $4
+                        AND sha384 = $5
+                        AND sha512 = $6
+                        AND md5 = $7
+                        AND id <> $8
+                    "#,
+                )
+                .bind(config_id)
+                .bind(&sha1)
+                .bind(&sha224)
+                .bind(&sha256)
+                .bind(&sha384)
+                .bind(&sha512)
+                .bind(&md5)
+                .bind(id)
+                .fetch_optional(&mut *transaction)
+                .await?;
+
+                if let Some(existing) = existing {
+                    sqlx::query(
+                        "UPDATE build_configs SET config_value_id = $1 WHERE config_value_id = $2",
+                    )
+                    .bind(existing)
+                    .bind(id)
+                    .execute(&mut *transaction)
+                    .await?;
+
+                    sqlx::query("DELETE FROM config_values WHERE id = $1")
+                        .bind(id)
+                        .execute(&mut *transaction)
+                        .await?;
+                } else {
+                    sqlx::query(
+                        r#"
+                        UPDATE config_values
+                        SET value = $1, sha1 = $2, sha224 = $3, sha256 = $4, sha384 = $5, sha512 = $6, md5 = $7
+                        WHERE id = $8
+                        "#,
+                    )
+                    .bind(formatted)
+                    .bind(sha1)
+                    .bind(sha224)
+                    .bind(sha256)
+                    .bind(sha384)
+                    .bind(sha512)
+                    .bind(md5)
+                    .bind(id)
+                    .execute(&mut *transaction)
+                    .await?;
+                }
+
+                updated += 1;
+            }
+        }
+
+        transaction.commit().await?;
+
+        Ok(updated)
+    }
+
+    pub fn parse(
+        format: Format,
+        content: &str,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        Ok(match format {
+            Format::Properties => serde_json::Value::Object(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| {
+                        !line.is_empty() && !line.starts_with('#') && !line.starts_with('!')
+                    })
+                    .map(|line| {
+                        let (key, value) = line.split_once(['=', ':']).unwrap_or((line, ""));
+
+                        (
+                            key.trim().to_string(),
+                            serde_json::Value::String(value.trim().to_string()),
+                        )
+                    })
+                    .collect(),
+            ),
+            Format::Yaml => yaml_to_json(serde_yaml::from_str(content)?),
+            Format::Toml => serde_json::to_value(toml::from_str::<toml::Value>(content)?)?,
+            Format::Conf => Hocon::parse(content)?,
+        })
+    }
+
+    pub fn serialize(
+        format: Format,
+        value: &serde_json::Value,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(match format {
+            Format::Properties => Self::flatten(value)
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(value) => format!("{}={}\n", key, value),
+                    value => format!("{}={}\n", key, value),
+                })
+                .collect(),
+            Format::Yaml => serde_yaml::to_string(value)?,
+            Format::Toml => toml::to_string(value)?,
+            Format::Conf => {
+                let mut output = String::new();
+                Hocon::write(value, 0, &mut output);
+
+                output
+            }
+        })
+    }
+
+    fn set_path(value: &mut serde_json::Value, path: &str, new: serde_json::Value) -> bool {
+        let Some(map) = value.as_object_mut() else {
+            return false;
+        };
+
+        if let Some(existing) = map.get_mut(path) {
+            *existing = new;
+            return true;
+        }
+
+        for (key, value) in map.iter_mut() {
+            if let Some(rest) = path
+                .strip_prefix(key.as_str())
+                .and_then(|rest| rest.strip_prefix('.'))
+                && Self::set_path(value, rest, new.clone())
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn apply(
+        format: Format,
+        merged: &mut serde_json::Value,
+        path: &str,
+        value: &serde_json::Value,
+    ) -> bool {
+        if format == Format::Properties {
+            merged[path] = value.clone();
+
+            true
+        } else {
+            Self::set_path(merged, path, value.clone())
+        }
+    }
+
+    pub fn migrate(
+        &self,
+        source: Option<&str>,
+        content: &str,
+        target: &str,
+    ) -> Result<(String, ConfigMigration), Box<dyn std::error::Error>> {
+        let format = self.format;
+        let overrides = match source {
+            Some(source) => {
+                let diff = Self::diff(format, source, content)?;
+
+                diff.changed
+                    .into_iter()
+                    .map(|change| (change.path, change.to))
+                    .chain(
+                        diff.added
+                            .into_iter()
+                            .map(|entry| (entry.path, entry.value)),
+                    )
+                    .collect::<IndexMap<String, serde_json::Value>>()
+            }
+            None => Self::flatten(&Self::parse(format, content)?),
+        };
+        let source = match source {
+            Some(source) => Self::flatten(&Self::parse(format, source)?),
+            None => IndexMap::new(),
+        };
+
+        let mut merged = Self::parse(format, target)?;
+        let target = Self::flatten(&merged);
+
+        let mut migration = ConfigMigration::default();
+
+        for (path, value) in overrides {
+            if target.contains_key(&path) && Self::apply(format, &mut merged, &path, &value) {
+                migration.carried.push(path);
+                continue;
+            }
+
+            let name = path.rsplit('.').next().unwrap_or(&path);
+            let candidates = target
+                .iter()
+                .filter(|(candidate, default)| {
+                    !source.contains_key(*candidate)
+                        && candidate.rsplit('.').next() == Some(name)
+                        && std::mem::discriminant(*default) == std::mem::discriminant(&value)
+                })
+                .map(|(candidate, _)| candidate.clone())
+                .collect::<Vec<String>>();
+
+            if let [candidate] = &candidates[..]
+                && Self::apply(format, &mut merged, candidate, &value)
+            {
+                migration.renamed.push(ConfigRename {
+                    from: path,
+                    to: candidate.clone(),
+                });
+            } else {
+                migration.dropped.push(ConfigDiffEntry { path, value });
+            }
+        }
+
+        let placeholder = serde_json::Value::String("xxx".to_string());
+
+        for (path, value) in Self::flatten(&merged) {
+            if value == placeholder
+                && self.redacts(&path)
+                && Self::apply(
+                    format,
+                    &mut merged,
+                    &path,
+                    &serde_json::Value::String(String::new()),
+                )
+            {
+                migration.cleared.push(path);
+            }
+        }
+
+        Ok((Self::serialize(format, &merged)?, migration))
+    }
+
+    fn type_name(format: Format, value: &serde_json::Value) -> &'static str {
+        match value {
+            serde_json::Value::String(value) if format == Format::Properties => {
+                if value == "true" || value == "false" {
+                    "boolean"
+                } else if value.parse::<i64>().is_ok() {
+                    "integer"
+                } else if value.parse::<f64>().is_ok() {
+                    "float"
+                } else {
+                    "string"
+                }
+            }
+            serde_json::Value::String(_) => "string",
+            serde_json::Value::Number(number) if number.is_f64() => "float",
+            serde_json::Value::Number(_) => "integer",
+            serde_json::Value::Bool(_) => "boolean",
+            serde_json::Value::Array(_) => "array",
+            serde_json::Value::Object(_) => "object",
+            serde_json::Value::Null => "null",
+        }
+    }
+
+    pub fn validate(
+        &self,
+        default: &str,
+        content: &str,
+    ) -> Result<ConfigValidation, Box<dyn std::error::Error>> {
+        let format = self.format;
+        let mut default = Self::flatten(&Self::parse(format, default)?);
+        let content = Self::flatten(&Self::parse(format, content)?);
+
+        default.retain(|path, _| !self.redacts(path));
+
+        let mut validation = ConfigValidation::default();
+
+        for (path, value) in content {
+            if self.redacts(&path) {
+                continue;
+            }
+
+            match default.shift_remove(&path) {
+                Some(expected) => {
+                    let expected = Self::type_name(format, &expected);
+                    let found = Self::type_name(format, &value);
+
+                    if expected != found
+                        && expected != "null"
+                        && !(format == Format::Properties && expected == "string")
+                        && !(expected == "float" && found == "integer")
+                    {
+                        validation.mismatched.push(ConfigTypeMismatch {
+                            path,
+                            expected: expected.to_string(),
+                            found: found.to_string(),
+                        });
+                    }
+                }
+                None => validation.unknown.push(ConfigDiffEntry { path, value }),
+            }
+        }
+
+        validation.missing.extend(
+            default
+                .into_iter()
+                .map(|(path, value)| ConfigDiffEntry { path, value }),
+        );
+
+        Ok(validation)
+    }
+
+    pub fn flatten(value: &serde_json::Value) -> IndexMap<String, serde_json::Value> {
+        fn walk(
+            value: &serde_json::Value,
+            path: &str,
+            result: &mut IndexMap<String, serde_json::Value>,
+        ) {
+            match value {
+                serde_json::Value::Object(map) if !map.is_empty() => {
+                    for (key, value) in map {
+                        let path = if path.is_empty() {
+                            key.clone()
+                        } else {
+                            format!("{}.{}", path, key)
+                        };
+
+                        walk(value, &path, result);
+                    }
+                }
+                value => {
+                    result.insert(path.to_string(), value.clone());
+                }
+            }
+        }
+
+        let mut result = IndexMap::new();
+        walk(value, "", &mut result);
+
+        result
+    }
+
+    pub fn diff(
+        format: Format,
+        default: &str,
+        content: &str,
+    ) -> Result<ConfigDiff, Box<dyn std::error::Error>> {
+        let default = Self::flatten(&Self::parse(format, default)?);
+        let mut content = Self::flatten(&Self::parse(format, content)?);
+
+        let mut diff = ConfigDiff::default();
+
+        for (path, from) in default {
+            match content.shift_remove(&path) {
+                Some(to) if to != from => diff.changed.push(ConfigDiffChange { path, from, to }),
+                Some(_) => {}
+                None => diff.removed.push(ConfigDiffEntry { path, value: from }),
+            }
+        }
+
+        diff.added.extend(
+            content
+                .into_iter()
+                .map(|(path, value)| ConfigDiffEntry { path, value }),
+        );
+
+        Ok(diff)
+    }
+
+    fn child_path(path: &str, key: &str) -> String {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    }
+
+    pub fn process_json_keys_recursively(
+        value: &mut serde_json::Value,
+        path: &str,
+        redacts: &dyn Fn(&str) -> bool,
+    ) {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<(String, serde_json::Value)> =
+                    std::mem::take(map).into_iter().collect();
+
+                entries.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+
+                for (k, v) in entries.iter_mut() {
+                    Self::process_json_keys_recursively(v, &Self::child_path(path, k), redacts);
+                }
+
+                *map = serde_json::Map::from_iter(entries);
+            }
+            serde_json::Value::Array(array) => {
+                for item in array.iter_mut() {
+                    Self::process_json_keys_recursively(item, path, redacts);
+                }
+            }
+            serde_json::Value::String(_) | serde_json::Value::Number(_) if redacts(path) => {
+                *value = serde_json::Value::String("xxx".to_string());
+            }
+            _ => {}
+        }
+    }
+
     pub fn process_yaml_keys_recursively(
         value: &mut serde_yaml::Value,
-        key: Option<&serde_yaml::Value>,
+        path: &str,
+        redacts: &dyn Fn(&str) -> bool,
     ) {
         match value {
             serde_yaml::Value::Mapping(map) => {
@@ -159,29 +1280,26 @@ impl Config {
                 });
 
                 for (k, v) in entries.iter_mut() {
-                    Self::process_yaml_keys_recursively(v, Some(k));
+                    let key = match k {
+                        serde_yaml::Value::String(s) => s.clone(),
+                        _ => serde_yaml::to_string(k)
+                            .unwrap_or_default()
+                            .trim()
+                            .to_string(),
+                    };
+
+                    Self::process_yaml_keys_recursively(v, &Self::child_path(path, &key), redacts);
                 }
 
                 *map = serde_yaml::Mapping::from_iter(entries);
             }
             serde_yaml::Value::Sequence(seq) => {
                 for item in seq.iter_mut() {
-                    Self::process_yaml_keys_recursively(item, None);
+                    Self::process_yaml_keys_recursively(item, path, redacts);
                 }
             }
-            serde_yaml::Value::String(s) => {
-                if let Some(key) = key {
-                    if key.as_str().unwrap().starts_with("seed-") {
-                        *s = "xxx".to_string();
-                    }
-                }
-            }
-            serde_yaml::Value::Number(_) => {
-                if let Some(key) = key {
-                    if key.as_str().unwrap().starts_with("seed-") {
-                        *value = serde_yaml::Value::String("xxx".to_string());
-                    }
-                }
+            serde_yaml::Value::String(_) | serde_yaml::Value::Number(_) if redacts(path) => {
+                *value = serde_yaml::Value::String("xxx".to_string());
             }
             _ => {}
         }
@@ -196,6 +1314,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Vanilla,
                 format: Format::Properties,
                 aliases: vec!["server.properties".to_string()],
+                redactions: vec![Redaction::Key("rcon.password")],
             },
         ),
         (
@@ -204,6 +1323,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Spigot,
                 format: Format::Yaml,
                 aliases: vec!["spigot.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -212,6 +1332,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Spigot,
                 format: Format::Yaml,
                 aliases: vec!["bukkit.yml".to_string()],
+                redactions: vec![Redaction::Glob("database.*")],
             },
         ),
         (
@@ -220,6 +1341,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Paper,
                 format: Format::Yaml,
                 aliases: vec!["paper.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -231,6 +1353,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/paper-global.yml".to_string(),
                     "paper-global.yml".to_string(),
                 ],
+                redactions: vec![Redaction::Key("proxies.velocity.secret")],
             },
         ),
         (
@@ -242,6 +1365,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/paper-world-defaults.yml".to_string(),
                     "paper-world-defaults.yml".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -250,6 +1374,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Pufferfish,
                 format: Format::Yaml,
                 aliases: vec!["pufferfish.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -258,6 +1383,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Purpur,
                 format: Format::Yaml,
                 aliases: vec!["purpur.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -266,6 +1392,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Leaves,
                 format: Format::Yaml,
                 aliases: vec!["leaves.yml".to_string()],
+                redactions: vec![Redaction::Key("server-id")],
             },
         ),
         (
@@ -274,6 +1401,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Canvas,
                 format: Format::Yaml,
                 aliases: vec!["canvas.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -282,6 +1410,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Divinemc,
                 format: Format::Yaml,
                 aliases: vec!["divinemc.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -293,6 +1422,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/sponge/global.conf".to_string(),
                     "global.conf".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -304,6 +1434,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/sponge/sponge.conf".to_string(),
                     "sponge.conf".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -315,6 +1446,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/sponge/tracker.conf".to_string(),
                     "tracker.conf".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -323,6 +1455,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Arclight,
                 format: Format::Conf,
                 aliases: vec!["arclight.conf".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -334,6 +1467,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/neoforge-server.toml".to_string(),
                     "neoforge-server.toml".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -345,6 +1479,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "config/neoforge-common.toml".to_string(),
                     "neoforge-common.toml".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -356,6 +1491,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                     "mohist-config/mohist.yml".to_string(),
                     "mohist.yml".to_string(),
                 ],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -364,6 +1500,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Velocity,
                 format: Format::Toml,
                 aliases: vec!["velocity.toml".to_string()],
+                redactions: vec![Redaction::Key("forwarding-secret")],
             },
         ),
         (
@@ -372,6 +1509,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Bungeecord,
                 format: Format::Yaml,
                 aliases: vec!["config.yml".to_string()],
+                redactions: vec![Redaction::Key("stats_uuid"), Redaction::Key("stats")],
             },
         ),
         (
@@ -380,6 +1518,7 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Waterfall,
                 format: Format::Yaml,
                 aliases: vec!["waterfall.yml".to_string()],
+                redactions: Vec::new(),
             },
         ),
         (
@@ -388,7 +1527,298 @@ pub static CONFIGS: LazyLock<IndexMap<String, Config>> = LazyLock::new(|| {
                 r#type: ServerType::Nanolimbo,
                 format: Format::Yaml,
                 aliases: vec!["settings.yml".to_string()],
+                redactions: vec![Redaction::Glob("infoForwarding.*")],
             },
         ),
     ])
 });
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Format, Hocon, Redaction};
+
+    const GLOBAL_CONF: &str = r#"
+# 1.0
+sponge {
+    world {
+        auto-player-save-interval=900
+        "gameprofile-lookup-batch-size"=1
+    }
+    general.file-io-thread-sleep = false
+    modules: {
+        entity-activation-range=true
+    }
+    sql.aliases=[]
+    metrics.global-state = "UNDEFINED"
+    config-version = 2
+    database.password = "hunter2"
+    "dotted.key" = "line\nbreak \"quoted\" \b\f"
+    ratio = 0.5
+    list = [ 1, "two", { three = 3 } ]
+    list += 4
+}
+"#;
+
+    #[test]
+    fn hocon_round_trips() {
+        let parsed = Hocon::parse(GLOBAL_CONF).unwrap();
+
+        let mut written = String::new();
+        Hocon::write(&parsed, 0, &mut written);
+
+        assert_eq!(Hocon::parse(&written).unwrap(), parsed);
+
+        let mut rewritten = String::new();
+        Hocon::write(&Hocon::parse(&written).unwrap(), 0, &mut rewritten);
+
+        assert_eq!(rewritten, written);
+    }
+
+    #[test]
+    fn hocon_format_sorts_redacts_and_detects_version() {
+        let (formatted, contains) = Config::format("global.conf", GLOBAL_CONF).unwrap();
+
+        assert_eq!(contains.as_deref(), Some("config-version=2"));
+        assert!(!formatted.contains("hunter2"));
+
+        let parsed = Config::parse(Format::Conf, &formatted).unwrap();
+        let keys = parsed["sponge"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<String>>();
+        let mut sorted = keys.clone();
+        sorted.sort();
+
+        assert_eq!(keys, sorted);
+        assert_eq!(parsed["sponge"]["database"]["password"], "xxx");
+        assert_eq!(parsed["sponge"]["list"].as_array().unwrap().len(), 4);
+
+        assert_eq!(
+            Config::format("global.conf", &formatted).unwrap().0,
+            formatted
+        );
+    }
+
+    #[test]
+    fn redaction_globs_match_single_and_nested_segments() {
+        assert!(Redaction::glob(b"database.*", b"database.password"));
+        assert!(!Redaction::glob(b"database.*", b"database.pool.password"));
+        assert!(!Redaction::glob(b"database.*", b"database"));
+        assert!(Redaction::glob(b"database.**", b"database.pool.password"));
+        assert!(Redaction::glob(b"**.secret", b"proxies.velocity.secret"));
+        assert!(!Redaction::glob(b"*.secret", b"proxies.velocity.secret"));
+        assert!(Redaction::glob(b"seed-?", b"seed-a"));
+        assert!(!Redaction::glob(b"seed-?", b"seed-"));
+        assert!(!Redaction::glob(b"seed-?", b"seed-ab"));
+    }
+
+    #[test]
+    fn configs_redact_their_own_rules() {
+        let properties = Config::by_alias("server.properties").unwrap();
+        assert!(properties.redacts("rcon.password"));
+        assert!(!properties.redacts("rcon.port"));
+
+        let bukkit = Config::by_alias("bukkit.yml").unwrap();
+        assert!(bukkit.redacts("database.isolation"));
+        assert!(!bukkit.redacts("database"));
+        assert!(!bukkit.redacts("settings.isolation"));
+
+        let nanolimbo = Config::by_alias("settings.yml").unwrap();
+        assert!(nanolimbo.redacts("infoForwarding.type"));
+        assert!(!nanolimbo.redacts("infoForwarding"));
+        assert!(!bukkit.redacts("infoForwarding.type"));
+    }
+
+    #[test]
+    fn format_applies_per_config_rules() {
+        let (formatted, _) = Config::format(
+            "server.properties",
+            "rcon.password=hunter2\nrcon.port=25575\nmotd=A Minecraft Server\n",
+        )
+        .unwrap();
+        assert!(formatted.contains("rcon.password=xxx"));
+        assert!(formatted.contains("rcon.port=25575"));
+
+        let (formatted, _) = Config::format(
+            "bukkit.yml",
+            "database:\n  isolation: SERIALIZABLE\n  driver: org.sqlite.JDBC\nsettings:\n  isolation: none\n",
+        )
+        .unwrap();
+        let parsed = Config::parse(Format::Yaml, &formatted).unwrap();
+        assert_eq!(parsed["database"]["isolation"], "xxx");
+        assert_eq!(parsed["database"]["driver"], "xxx");
+        assert_eq!(parsed["settings"]["isolation"], "none");
+
+        let (formatted, _) = Config::format(
+            "settings.yml",
+            "infoForwarding:\n  type: MODERN\n  secret: hunter2\nmaxPlayers: 100\n",
+        )
+        .unwrap();
+        let parsed = Config::parse(Format::Yaml, &formatted).unwrap();
+        assert_eq!(parsed["infoForwarding"]["type"], "xxx");
+        assert_eq!(parsed["infoForwarding"]["secret"], "xxx");
+        assert_eq!(parsed["maxPlayers"], 100);
+    }
+
+    #[test]
+    fn digests_match_the_stored_hash_columns() {
+        let [sha1, sha224, sha256, sha384, sha512, md5] = Config::digests("eula=true\n");
+
+        assert_eq!(
+            [
+                sha1.len(),
+                sha224.len(),
+                sha256.len(),
+                sha384.len(),
+                sha512.len(),
+                md5.len()
+            ],
+            [40, 56, 64, 96, 128, 32]
+        );
+        assert_eq!(sha1, "2df51f289e286faa8a42ef6d2b7c2e10b9c3bfb8");
+        assert_eq!(md5, "cc735350911aa7ecf615d4194f382d64");
+    }
+
+    #[test]
+    fn validate_reports_unknown_mismatched_and_missing_keys() {
+        let config = Config::by_alias("server.properties").unwrap();
+
+        let validation = config
+            .validate(
+                "motd=A Minecraft Server\nmax-players=20\nview-distance=10\nrcon.password=\n",
+                "motd=Hello\nmax-players=lots\nunknown-key=1\nrcon.password=xxx\n",
+            )
+            .unwrap();
+
+        assert_eq!(
+            validation
+                .unknown
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<&str>>(),
+            ["unknown-key"]
+        );
+        assert_eq!(validation.mismatched.len(), 1);
+        assert_eq!(validation.mismatched[0].path, "max-players");
+        assert_eq!(validation.mismatched[0].expected, "integer");
+        assert_eq!(validation.mismatched[0].found, "string");
+        assert_eq!(
+            validation
+                .missing
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<&str>>(),
+            ["view-distance"]
+        );
+    }
+
+    #[test]
+    fn validate_skips_redacted_paths() {
+        let config = Config::by_alias("bukkit.yml").unwrap();
+
+        let validation = config
+            .validate(
+                "database:\n  username: bukkit\n  isolation: SERIALIZABLE\nsettings:\n  allow-end: true\n",
+                "database:\n  username: 1\n  extra: xxx\nsettings:\n  allow-end: true\n",
+            )
+            .unwrap();
+
+        assert!(validation.unknown.is_empty());
+        assert!(validation.mismatched.is_empty());
+        assert!(validation.missing.is_empty());
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_keys() {
+        let diff = Config::diff(
+            Format::Yaml,
+            "settings:\n  allow-end: true\n  spawn-radius: 16\nticks-per:\n  autosave: 6000\n",
+            "settings:\n  allow-end: false\n  spawn-radius: 16\naliases: now-in-commands.yml\n",
+        )
+        .unwrap();
+
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].path, "settings.allow-end");
+        assert_eq!(diff.changed[0].from, true);
+        assert_eq!(diff.changed[0].to, false);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].path, "ticks-per.autosave");
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].path, "aliases");
+    }
+
+    #[test]
+    fn migrate_detects_renamed_keys() {
+        let config = Config::by_alias("bukkit.yml").unwrap();
+
+        let (merged, migration) = config
+            .migrate(
+                Some("settings:\n  spawn-radius: 16\n  allow-end: true\n"),
+                "settings:\n  spawn-radius: 32\n  allow-end: false\n",
+                "world:\n  spawn-radius: 16\nsettings:\n  allow-end: true\n",
+            )
+            .unwrap();
+        let merged = Config::parse(Format::Yaml, &merged).unwrap();
+
+        assert_eq!(migration.carried, vec!["settings.allow-end".to_string()]);
+        assert_eq!(migration.renamed.len(), 1);
+        assert_eq!(migration.renamed[0].from, "settings.spawn-radius");
+        assert_eq!(migration.renamed[0].to, "world.spawn-radius");
+        assert_eq!(merged["world"]["spawn-radius"], 32);
+        assert_eq!(merged["settings"]["allow-end"], false);
+        assert!(merged["settings"].get("spawn-radius").is_none());
+    }
+
+    #[test]
+    fn migrate_drops_ambiguous_or_mistyped_renames() {
+        let config = Config::by_alias("bukkit.yml").unwrap();
+
+        let (_, migration) = config
+            .migrate(
+                Some("settings:\n  spawn-radius: 16\n  motd: hello\n"),
+                "settings:\n  spawn-radius: 32\n  motd: world\n",
+                "overworld:\n  spawn-radius: 16\nnether:\n  spawn-radius: 16\nserver:\n  motd: 1\n",
+            )
+            .unwrap();
+
+        assert!(migration.renamed.is_empty());
+        assert_eq!(
+            migration
+                .dropped
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<&str>>(),
+            ["settings.spawn-radius", "settings.motd"]
+        );
+    }
+
+    #[test]
+    fn migrate_clears_redacted_placeholders() {
+        let config = Config::by_alias("server.properties").unwrap();
+
+        let (merged, migration) = config
+            .migrate(
+                Some("motd=A Minecraft Server\nrcon.password=xxx\n"),
+                "motd=Hello\n",
+                "motd=A Minecraft Server\nrcon.password=xxx\n",
+            )
+            .unwrap();
+
+        assert!(!merged.contains("xxx"));
+        assert!(merged.contains("motd=Hello"));
+        assert_eq!(migration.cleared, vec!["rcon.password".to_string()]);
+
+        let (merged, migration) = config
+            .migrate(
+                Some("motd=A Minecraft Server\nrcon.password=xxx\n"),
+                "motd=Hello\nrcon.password=hunter2\n",
+                "motd=A Minecraft Server\nrcon.password=xxx\n",
+            )
+            .unwrap();
+
+        assert!(merged.contains("rcon.password=hunter2"));
+        assert!(migration.cleared.is_empty());
+    }
+}
@@ -8,6 +8,7 @@ pub mod organization;
 pub mod r#type;
 pub mod user;
 pub mod version;
+pub mod webhook;
 
 pub trait BaseModel: Serialize + DeserializeOwned {
     fn columns(prefix: Option<&str>, table: Option<&str>) -> BTreeMap<String, String>;
@@ -90,16 +90,24 @@ impl BaseModel for Organization {
 
 impl Organization {
     #[allow(clippy::new_ret_no_self)]
-    pub async fn new(database: &crate::database::Database, owner_id: i32, name: &str) {
+    pub async fn new(
+        database: &crate::database::Database,
+        owner_id: i32,
+        name: &str,
+    ) -> Result<(), crate::database::DatabaseError> {
         sqlx::query("INSERT INTO organizations (owner_id, name) VALUES ($1, $2)")
             .bind(owner_id)
             .bind(name)
             .execute(database.write())
-            .await
-            .unwrap();
+            .await?;
+
+        Ok(())
     }
 
-    pub async fn save(&self, database: &crate::database::Database) {
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
         sqlx::query(
             r#"
             UPDATE organizations
@@ -121,12 +129,16 @@ impl Organization {
         .bind(&self.icon)
         .bind(serde_json::to_value(&self.types).unwrap())
         .execute(database.write())
-        .await
-        .unwrap();
+        .await?;
+
+        Ok(())
     }
 
-    pub async fn count_by_owner(database: &crate::database::Database, user_id: i32) -> i64 {
-        sqlx::query(
+    pub async fn count_by_owner(
+        database: &crate::database::Database,
+        user_id: i32,
+    ) -> Result<i64, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             SELECT COUNT(*)
             FROM organizations
@@ -135,13 +147,15 @@ impl Organization {
         )
         .bind(user_id)
         .fetch_one(database.read())
-        .await
-        .unwrap()
-        .get(0)
+        .await?
+        .get(0))
     }
 
-    pub async fn all_by_owner(database: &crate::database::Database, user_id: i32) -> Vec<Self> {
-        sqlx::query(&format!(
+    pub async fn all_by_owner(
+        database: &crate::database::Database,
+        user_id: i32,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
             r#"
             SELECT DISTINCT {}, organization_subusers.pending
             FROM organizations
@@ -156,36 +170,45 @@ impl Organization {
         ))
         .bind(user_id)
         .fetch_all(database.read())
-        .await
-        .unwrap().into_iter().map(|row| Self::map(None, &row)).collect()
+        .await?.into_iter().map(|row| Self::map(None, &row)).collect())
+    }
+
+    pub fn cache_tag(id: i32) -> String {
+        format!("org:{}", id)
     }
 
     pub async fn by_id(
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         id: i32,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
         if id < 1 {
-            return None;
+            return Ok(None);
         }
 
+        let database = database.clone();
+
         cache
-            .cached(&format!("organization::{}", id), 300, || async {
-                sqlx::query(&format!(
-                    r#"
-                    SELECT {}
-                    FROM organizations
-                    LEFT JOIN users ON organizations.owner_id = users.id
-                    WHERE organizations.id = $1
-                    "#,
-                    Self::columns_sql(None, None)
-                ))
-                .bind(id)
-                .fetch_optional(database.read())
-                .await
-                .unwrap()
-                .map(|row| Self::map(None, &row))
-            })
+            .cached_tagged(
+                &format!("organization::{}", id),
+                300,
+                move |_| vec![Self::cache_tag(id)],
+                move || async move {
+                    Ok(sqlx::query(&format!(
+                        r#"
+                        SELECT {}
+                        FROM organizations
+                        LEFT JOIN users ON organizations.owner_id = users.id
+                        WHERE organizations.id = $1
+                        "#,
+                        Self::columns_sql(None, None)
+                    ))
+                    .bind(id)
+                    .fetch_optional(database.read())
+                    .await?
+                    .map(|row| Self::map(None, &row)))
+                },
+            )
             .await
     }
 
@@ -193,25 +216,37 @@ impl Organization {
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         key: &str,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let database = database.clone();
+        let key = key.to_string();
+
         cache
-            .cached(&format!("organization::key::{}", key), 300, || async {
-                sqlx::query(&format!(
-                    r#"
-                    SELECT {}
-                    FROM organizations
-                    LEFT JOIN users ON organizations.owner_id = users.id
-                    LEFT JOIN organization_keys ON organizations.id = organization_keys.organization_id
-                    WHERE organization_keys.key = $1
-                    "#,
-                    Self::columns_sql(None, None)
-                ))
-                .bind(key)
-                .fetch_optional(database.read())
-                .await
-                .unwrap_or(None)
-                .map(|row| Self::map(None, &row))
-            })
+            .cached_tagged(
+                &format!("organization::key::{}", key),
+                300,
+                |organization: &Option<Self>| {
+                    organization
+                        .iter()
+                        .map(|organization| Self::cache_tag(organization.id))
+                        .collect()
+                },
+                move || async move {
+                    Ok(sqlx::query(&format!(
+                        r#"
+                        SELECT {}
+                        FROM organizations
+                        LEFT JOIN users ON organizations.owner_id = users.id
+                        LEFT JOIN organization_keys ON organizations.id = organization_keys.organization_id
+                        WHERE organization_keys.key = $1
+                        "#,
+                        Self::columns_sql(None, None)
+                    ))
+                    .bind(key)
+                    .fetch_optional(database.read())
+                    .await?
+                    .map(|row| Self::map(None, &row)))
+                },
+            )
             .await
     }
 
@@ -221,39 +256,48 @@ impl Organization {
         user_id: i32,
         user_admin: bool,
         organization_id: i32,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let database = database.clone();
+
         cache
-            .cached(&format!("organization::{}::user::{}", organization_id, user_id), 60, || async {
-                sqlx::query(&format!(
-                    r#"
-                    SELECT {}
-                    FROM organizations
-                    LEFT JOIN users ON organizations.owner_id = users.id
-                    LEFT JOIN organization_subusers ON organizations.id = organization_subusers.organization_id
-                    WHERE
-                        (
-                            organizations.owner_id = $1
-                            OR organization_subusers.user_id = $1
-                            OR $2
-                        )
-                        AND organizations.id = $3
-                    LIMIT 1
-                    "#,
-                    Self::columns_sql(None, None)
-                ))
-                .bind(user_id)
-                .bind(user_admin)
-                .bind(organization_id)
-                .fetch_optional(database.read())
-                .await
-                .unwrap()
-                .map(|row| Self::map(None, &row))
-            })
+            .cached_tagged(
+                &format!("organization::{}::user::{}", organization_id, user_id),
+                60,
+                move |_| vec![Self::cache_tag(organization_id)],
+                move || async move {
+                    Ok(sqlx::query(&format!(
+                        r#"
+                        SELECT {}
+                        FROM organizations
+                        LEFT JOIN users ON organizations.owner_id = users.id
+                        LEFT JOIN organization_subusers ON organizations.id = organization_subusers.organization_id
+                        WHERE
+                            (
+                                organizations.owner_id = $1
+                                OR organization_subusers.user_id = $1
+                                OR $2
+                            )
+                            AND organizations.id = $3
+                        LIMIT 1
+                        "#,
+                        Self::columns_sql(None, None)
+                    ))
+                    .bind(user_id)
+                    .bind(user_admin)
+                    .bind(organization_id)
+                    .fetch_optional(database.read())
+                    .await?
+                    .map(|row| Self::map(None, &row)))
+                },
+            )
             .await
     }
 
-    pub async fn delete_by_id(database: &crate::database::Database, id: i32) -> bool {
-        sqlx::query(
+    pub async fn delete_by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             DELETE FROM organizations
             WHERE organizations.id = $1
@@ -261,10 +305,9 @@ impl Organization {
         )
         .bind(id)
         .execute(database.write())
-        .await
-        .unwrap()
+        .await?
         .rows_affected()
-            == 1
+            == 1)
     }
 }
 
@@ -324,13 +367,13 @@ impl OrganizationKey {
         database: &crate::database::Database,
         organization_id: i32,
         name: &str,
-    ) -> (bool, String) {
+    ) -> Result<(bool, String), crate::database::DatabaseError> {
         let mut hash = sha2::Sha256::new();
         hash.update(chrono::Utc::now().timestamp().to_be_bytes());
         hash.update(organization_id.to_be_bytes());
         let hash = format!("{:x}", hash.finalize());
 
-        (
+        Ok((
             sqlx::query(
                 r#"
                 INSERT INTO organization_keys (organization_id, name, key)
@@ -342,19 +385,18 @@ impl OrganizationKey {
             .bind(name)
             .bind(&hash)
             .execute(database.write())
-            .await
-            .unwrap()
+            .await?
             .rows_affected()
                 == 1,
             hash,
-        )
+        ))
     }
 
     pub async fn count_by_organization(
         database: &crate::database::Database,
         organization_id: i32,
-    ) -> i64 {
-        sqlx::query(
+    ) -> Result<i64, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             SELECT COUNT(*)
             FROM organization_keys
@@ -363,16 +405,15 @@ impl OrganizationKey {
         )
         .bind(organization_id)
         .fetch_one(database.read())
-        .await
-        .unwrap()
-        .get(0)
+        .await?
+        .get(0))
     }
 
     pub async fn all_by_organization(
         database: &crate::database::Database,
         organization_id: i32,
-    ) -> Vec<Self> {
-        sqlx::query(&format!(
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
             r#"
             SELECT {} FROM organization_keys
             WHERE organization_keys.organization_id = $1
@@ -382,31 +423,35 @@ impl OrganizationKey {
         ))
         .bind(organization_id)
         .fetch_all(database.read())
-        .await
-        .unwrap()
+        .await?
         .into_iter()
         .map(|row| Self::map(None, &row))
-        .collect()
+        .collect())
     }
 
-    pub async fn by_id(database: &crate::database::Database, id: i32) -> Option<Self> {
+    pub async fn by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
         if id < 1 {
-            return None;
+            return Ok(None);
         }
 
-        sqlx::query(&format!(
+        Ok(sqlx::query(&format!(
             "SELECT {} FROM organization_keys WHERE organization_keys.id = $1",
             Self::columns_sql(None, None)
         ))
         .bind(id)
         .fetch_optional(database.read())
-        .await
-        .unwrap()
-        .map(|row| Self::map(None, &row))
+        .await?
+        .map(|row| Self::map(None, &row)))
     }
 
-    pub async fn delete_by_id(database: &crate::database::Database, id: i32) -> bool {
-        sqlx::query(
+    pub async fn delete_by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             DELETE FROM organization_keys
             WHERE organization_keys.id = $1
@@ -414,10 +459,9 @@ impl OrganizationKey {
         )
         .bind(id)
         .execute(database.write())
-        .await
-        .unwrap()
+        .await?
         .rows_affected()
-            == 1
+            == 1)
     }
 }
 
@@ -476,8 +520,8 @@ impl OrganizationSubuser {
         database: &crate::database::Database,
         organization_id: i32,
         user_id: i32,
-    ) -> bool {
-        sqlx::query(
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             INSERT INTO organization_subusers (organization_id, user_id)
             VALUES ($1, $2)
@@ -487,13 +531,15 @@ impl OrganizationSubuser {
         .bind(organization_id)
         .bind(user_id)
         .execute(database.write())
-        .await
-        .unwrap()
+        .await?
         .rows_affected()
-            == 1
+            == 1)
     }
 
-    pub async fn save(&self, database: &crate::database::Database) {
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
         sqlx::query(
             r#"
             UPDATE organization_subusers
@@ -508,15 +554,16 @@ impl OrganizationSubuser {
         .bind(self.user.id)
         .bind(self.pending)
         .execute(database.write())
-        .await
-        .unwrap();
+        .await?;
+
+        Ok(())
     }
 
     pub async fn count_by_organization(
         database: &crate::database::Database,
         organization_id: i32,
-    ) -> i64 {
-        sqlx::query(
+    ) -> Result<i64, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             SELECT COUNT(*)
             FROM organization_subusers
@@ -525,16 +572,15 @@ impl OrganizationSubuser {
         )
         .bind(organization_id)
         .fetch_one(database.read())
-        .await
-        .unwrap()
-        .get(0)
+        .await?
+        .get(0))
     }
 
     pub async fn all_by_organization(
         database: &crate::database::Database,
         organization_id: i32,
-    ) -> Vec<Self> {
-        sqlx::query(&format!(
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
             r#"
             SELECT {}
             FROM organization_subusers
@@ -546,19 +592,18 @@ impl OrganizationSubuser {
         ))
         .bind(organization_id)
         .fetch_all(database.read())
-        .await
-        .unwrap()
+        .await?
         .into_iter()
         .map(|row| Self::map(None, &row))
-        .collect()
+        .collect())
     }
 
     pub async fn by_ids(
         database: &crate::database::Database,
         organization_id: i32,
         user_id: i32,
-    ) -> Option<Self> {
-        sqlx::query(&format!(
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
             r#"
             SELECT {}
             FROM organization_subusers
@@ -572,17 +617,16 @@ impl OrganizationSubuser {
         .bind(organization_id)
         .bind(user_id)
         .fetch_optional(database.read())
-        .await
-        .unwrap()
-        .map(|row| Self::map(None, &row))
+        .await?
+        .map(|row| Self::map(None, &row)))
     }
 
     pub async fn delete_by_ids(
         database: &crate::database::Database,
         organization_id: i32,
         user_id: i32,
-    ) -> bool {
-        sqlx::query(
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query(
             r#"
             DELETE FROM organization_subusers
             WHERE
@@ -593,9 +637,8 @@ impl OrganizationSubuser {
         .bind(organization_id)
         .bind(user_id)
         .execute(database.write())
-        .await
-        .unwrap()
+        .await?
         .rows_affected()
-            == 1
+            == 1)
     }
 }
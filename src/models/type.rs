@@ -146,9 +146,11 @@ impl ServerType {
     pub async fn all(
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
-    ) -> IndexMap<ServerType, ServerTypeInfo> {
+    ) -> Result<IndexMap<ServerType, ServerTypeInfo>, crate::database::DatabaseError> {
+        let database = database.clone();
+
         cache
-            .cached("types::all", 1800, || async {
+            .cached("types::all", 1800, move || async move {
                 let data = sqlx::query(
                     r#"
                     SELECT
@@ -161,8 +163,7 @@ impl ServerType {
                     "#,
                 )
                 .fetch_all(database.read())
-                .await
-                .unwrap();
+                .await?;
 
                 let mut types = IndexMap::new();
                 for row in data {
@@ -182,7 +183,7 @@ impl ServerType {
                     );
                 }
 
-                types
+                Ok(types)
             })
             .await
     }
@@ -83,7 +83,7 @@ impl User {
         name: Option<String>,
         email: String,
         login: String,
-    ) -> Self {
+    ) -> Result<Self, crate::database::DatabaseError> {
         let row = sqlx::query(&format!(
             r#"
             INSERT INTO users (github_id, name, email, login, last_login, created)
@@ -102,16 +102,15 @@ impl User {
         .bind(&email)
         .bind(&login)
         .fetch_one(database.write())
-        .await
-        .unwrap();
+        .await?;
 
-        Self::map(None, &row)
+        Ok(Self::map(None, &row))
     }
 
     pub async fn by_session(
         database: &crate::database::Database,
         session: &str,
-    ) -> Option<(Self, UserSession)> {
+    ) -> Result<Option<(Self, UserSession)>, crate::database::DatabaseError> {
         let row = sqlx::query(&format!(
             r#"
             SELECT {}, {}
@@ -124,24 +123,26 @@ impl User {
         ))
         .bind(session)
         .fetch_optional(database.read())
-        .await
-        .unwrap();
+        .await?;
 
-        row.map(|row| {
+        Ok(row.map(|row| {
             (
                 Self::map(None, &row),
                 UserSession::map(Some("session_"), &row),
             )
-        })
+        }))
     }
 
     pub async fn by_login(
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         login: &str,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let database = database.clone();
+        let login = login.to_string();
+
         cache
-            .cached(&format!("user::{}", login), 3600, || async {
+            .cached(&format!("user::{}", login), 3600, move || async move {
                 let row = sqlx::query(&format!(
                     r#"
                     SELECT {}
@@ -152,10 +153,9 @@ impl User {
                 ))
                 .bind(login.replace('%', "\\%").replace('_', "\\_"))
                 .fetch_optional(database.read())
-                .await
-                .unwrap();
+                .await?;
 
-                row.map(|row| Self::map(None, &row))
+                Ok(row.map(|row| Self::map(None, &row)))
             })
             .await
     }
@@ -251,7 +251,7 @@ impl UserSession {
         user_id: i32,
         ip: sqlx::types::ipnetwork::IpNetwork,
         user_agent: &str,
-    ) -> (Self, String) {
+    ) -> Result<(Self, String), crate::database::DatabaseError> {
         let mut hash = sha2::Sha256::new();
         hash.update(chrono::Utc::now().timestamp().to_be_bytes());
         hash.update(user_id.to_be_bytes());
@@ -270,13 +270,15 @@ impl UserSession {
         .bind(ip)
         .bind(user_agent)
         .fetch_one(database.write())
-        .await
-        .unwrap();
+        .await?;
 
-        (Self::map(None, &row), hash)
+        Ok((Self::map(None, &row), hash))
     }
 
-    pub async fn save(&self, database: &crate::database::Database) {
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
         sqlx::query(
             r#"
             UPDATE user_sessions
@@ -292,11 +294,15 @@ impl UserSession {
         .bind(&self.user_agent)
         .bind(self.last_used)
         .execute(database.write())
-        .await
-        .unwrap();
+        .await?;
+
+        Ok(())
     }
 
-    pub async fn delete_by_session(database: &crate::database::Database, session: &str) {
+    pub async fn delete_by_session(
+        database: &crate::database::Database,
+        session: &str,
+    ) -> Result<(), crate::database::DatabaseError> {
         sqlx::query(
             r#"
             DELETE FROM user_sessions
@@ -305,7 +311,8 @@ impl UserSession {
         )
         .bind(session)
         .execute(database.write())
-        .await
-        .unwrap();
+        .await?;
+
+        Ok(())
     }
 }
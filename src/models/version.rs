@@ -5,10 +5,14 @@ use super::{
 use crate::models::BaseModel;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, types::chrono::NaiveDateTime};
+use sqlx::{Row, postgres::PgRow, types::chrono::NaiveDateTime};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
 use utoipa::ToSchema;
 
-#[derive(ToSchema, Serialize, Deserialize)]
+#[derive(ToSchema, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
 #[schema(rename_all = "UPPERCASE")]
 pub enum VersionType {
@@ -16,6 +20,142 @@ pub enum VersionType {
     Snapshot,
 }
 
+impl Display for VersionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_value(self).unwrap().as_str().unwrap()
+        )
+    }
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct MinecraftVersion {
+    pub id: String,
+
+    pub r#type: VersionType,
+    pub supported: bool,
+    pub java: i16,
+
+    pub created: NaiveDateTime,
+}
+
+impl BaseModel for MinecraftVersion {
+    fn columns(prefix: Option<&str>, table: Option<&str>) -> BTreeMap<String, String> {
+        let table = table.unwrap_or("minecraft_versions");
+
+        BTreeMap::from([
+            (
+                format!("{}.id", table),
+                format!("{}id", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.type::text", table),
+                format!("{}type", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.supported", table),
+                format!("{}supported", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.java", table),
+                format!("{}java", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.created", table),
+                format!("{}created", prefix.unwrap_or_default()),
+            ),
+        ])
+    }
+
+    fn map(prefix: Option<&str>, row: &PgRow) -> Self {
+        let prefix = prefix.unwrap_or_default();
+
+        Self {
+            id: row.get(format!("{}id", prefix).as_str()),
+            r#type: serde_json::from_value(serde_json::Value::String(
+                row.get(format!("{}type", prefix).as_str()),
+            ))
+            .unwrap(),
+            supported: row.get(format!("{}supported", prefix).as_str()),
+            java: row.get(format!("{}java", prefix).as_str()),
+            created: row.get(format!("{}created", prefix).as_str()),
+        }
+    }
+}
+
+impl MinecraftVersion {
+    pub async fn by_id(
+        database: &crate::database::Database,
+        id: &str,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
+            "SELECT {} FROM minecraft_versions WHERE minecraft_versions.id = $1",
+            Self::columns_sql(None, None)
+        ))
+        .bind(id)
+        .fetch_optional(database.read())
+        .await?
+        .map(|row| Self::map(None, &row)))
+    }
+
+    pub async fn all_ids(
+        database: &crate::database::Database,
+    ) -> Result<HashSet<String>, crate::database::DatabaseError> {
+        Ok(sqlx::query("SELECT id FROM minecraft_versions")
+            .fetch_all(database.read())
+            .await?
+            .into_iter()
+            .map(|row| row.get(0))
+            .collect())
+    }
+
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            INSERT INTO minecraft_versions (id, type, supported, java, created)
+            VALUES ($1, $2::version_type, $3, $4, $5)
+            ON CONFLICT (id) DO UPDATE SET
+                type = EXCLUDED.type,
+                supported = EXCLUDED.supported,
+                java = EXCLUDED.java,
+                created = EXCLUDED.created
+            "#,
+        )
+        .bind(&self.id)
+        .bind(self.r#type.to_string())
+        .bind(self.supported)
+        .bind(self.java)
+        .bind(self.created)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn invalidate_cache(
+        &self,
+        cache: &crate::cache::Cache,
+    ) -> Result<(), crate::cache::CacheError> {
+        let mut keys = vec![
+            format!("version::{}::stats", self.id),
+            format!("version::{}::builds", self.id),
+        ];
+
+        for r#type in ServerType::variants() {
+            keys.push(format!("versions::{}", r#type));
+            keys.push(format!("builds::{}::{}", r#type, self.id));
+            keys.push(format!("version_location::{}::{}", r#type, self.id));
+        }
+
+        cache.invalidate(keys).await
+    }
+}
+
 #[derive(ToSchema, Serialize, Deserialize)]
 pub struct MinifiedVersion {
     pub id: String,
@@ -44,11 +184,17 @@ impl MinifiedVersionStats {
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         id: &str,
-    ) -> Option<Self> {
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        let database = database.clone();
+        let id = id.to_string();
+
         cache
-            .cached(&format!("version::{}::stats", id), 3600, || async {
-                let data = sqlx::query(
-                    r#"
+            .cached(
+                &format!("version::{}::stats", id),
+                3600,
+                move || async move {
+                    let data = sqlx::query(
+                        r#"
                     SELECT
                         builds.type::text AS type,
                         COUNT(builds.id) AS builds,
@@ -66,39 +212,39 @@ impl MinifiedVersionStats {
                         minecraft_versions.supported,
                         minecraft_versions.java
                     "#,
-                )
-                .bind(id)
-                .fetch_all(database.read())
-                .await
-                .unwrap();
-
-                if data.is_empty() {
-                    return None;
-                }
+                    )
+                    .bind(id)
+                    .fetch_all(database.read())
+                    .await?;
 
-                let mut builds = IndexMap::new();
+                    if data.is_empty() {
+                        return Ok(None);
+                    }
 
-                for r#type in ServerType::variants() {
-                    builds.insert(
-                        r#type,
-                        data.iter()
-                            .find(|x| x.get::<String, _>("type") == r#type.to_string())
-                            .map(|x| x.get("builds"))
-                            .unwrap_or_default(),
-                    );
-                }
+                    let mut builds = IndexMap::new();
 
-                Some(MinifiedVersionStats {
-                    r#type: serde_json::from_value(serde_json::Value::String(
-                        data[0].get("minecraft_version_type"),
-                    ))
-                    .unwrap(),
-                    supported: data[0].get("minecraft_version_supported"),
-                    java: data[0].get("minecraft_version_java"),
-                    builds,
-                    created: data[0].get("minecraft_version_created"),
-                })
-            })
+                    for r#type in ServerType::variants() {
+                        builds.insert(
+                            r#type,
+                            data.iter()
+                                .find(|x| x.get::<String, _>("type") == r#type.to_string())
+                                .map(|x| x.get("builds"))
+                                .unwrap_or_default(),
+                        );
+                    }
+
+                    Ok(Some(MinifiedVersionStats {
+                        r#type: serde_json::from_value(serde_json::Value::String(
+                            data[0].get("minecraft_version_type"),
+                        ))
+                        .unwrap(),
+                        supported: data[0].get("minecraft_version_supported"),
+                        java: data[0].get("minecraft_version_java"),
+                        builds,
+                        created: data[0].get("minecraft_version_created"),
+                    }))
+                },
+            )
             .await
     }
 }
@@ -121,12 +267,15 @@ impl Version {
         cache: &crate::cache::Cache,
         r#type: ServerType,
         id: &str,
-    ) -> Option<String> {
+    ) -> Result<Option<String>, crate::database::DatabaseError> {
+        let database = database.clone();
+        let id = id.to_string();
+
         cache
             .cached(
                 &format!("version_location::{}::{}", r#type, id),
                 86400,
-                || async {
+                move || async move {
                     let (minecraft, project) = tokio::join!(
                         sqlx::query(
                             r#"
@@ -137,7 +286,7 @@ impl Version {
                             LIMIT 1
                             "#
                         )
-                        .bind(id)
+                        .bind(&id)
                         .fetch_optional(database.read()),
                         sqlx::query(
                             r#"
@@ -149,17 +298,17 @@ impl Version {
                             LIMIT 1
                             "#
                         )
-                        .bind(id)
+                        .bind(&id)
                         .bind(r#type.to_string())
                         .fetch_optional(database.read()),
                     );
 
-                    if project.map(|x| x.is_some()).unwrap_or_default() {
-                        Some("project_version_id".to_string())
-                    } else if minecraft.map(|x| x.is_some()).unwrap_or_default() {
-                        Some("version_id".to_string())
+                    if project?.is_some() {
+                        Ok(Some("project_version_id".to_string()))
+                    } else if minecraft?.is_some() {
+                        Ok(Some("version_id".to_string()))
                     } else {
-                        None
+                        Ok(None)
                     }
                 },
             )
@@ -170,9 +319,11 @@ impl Version {
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         r#type: ServerType,
-    ) -> IndexMap<String, Self> {
+    ) -> Result<IndexMap<String, Self>, crate::database::DatabaseError> {
+        let database = database.clone();
+
         cache
-            .cached(&format!("versions::{}", r#type), 1800, || async {
+            .cached(&format!("versions::{}", r#type), 1800, move || async move {
                 let mut versions = IndexMap::new();
 
                 if SERVER_TYPES_WITH_PROJECT_AS_IDENTIFIER.contains(&r#type) {
@@ -201,8 +352,7 @@ impl Version {
                     ))
                     .bind(r#type.to_string())
                     .fetch_all(database.read())
-                    .await
-                    .unwrap();
+                    .await?;
 
                     for (i, row) in data.iter().enumerate() {
                         let latest = super::build::Build::map(None, row);
@@ -252,8 +402,7 @@ impl Version {
                     ))
                     .bind(r#type.to_string())
                     .fetch_all(database.read())
-                    .await
-                    .unwrap();
+                    .await?;
 
                     for row in data {
                         let version = Version {
@@ -272,7 +421,7 @@ impl Version {
                     }
                 }
 
-                versions
+                Ok(versions)
             })
             .await
     }
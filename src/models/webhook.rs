@@ -0,0 +1,334 @@
+use super::r#type::ServerType;
+use crate::models::BaseModel;
+use rand::distr::SampleString;
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, postgres::PgRow, types::chrono::NaiveDateTime};
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, SocketAddr},
+};
+use utoipa::ToSchema;
+
+#[derive(ToSchema, Serialize, Deserialize, Clone)]
+pub struct Webhook {
+    pub id: i32,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub organization_id: Option<i32>,
+
+    pub label: Option<String>,
+    pub url: String,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub secret: String,
+    pub types: Vec<ServerType>,
+    pub enabled: bool,
+
+    pub successful: i32,
+    pub failed: i32,
+
+    pub created: NaiveDateTime,
+}
+
+impl BaseModel for Webhook {
+    fn columns(prefix: Option<&str>, table: Option<&str>) -> BTreeMap<String, String> {
+        let table = table.unwrap_or("webhooks");
+
+        BTreeMap::from([
+            (
+                format!("{}.id", table),
+                format!("{}id", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.organization_id", table),
+                format!("{}organization_id", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.label", table),
+                format!("{}label", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.url", table),
+                format!("{}url", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.secret", table),
+                format!("{}secret", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.types", table),
+                format!("{}types", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.enabled", table),
+                format!("{}enabled", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.successful", table),
+                format!("{}successful", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.failed", table),
+                format!("{}failed", prefix.unwrap_or_default()),
+            ),
+            (
+                format!("{}.created", table),
+                format!("{}created", prefix.unwrap_or_default()),
+            ),
+        ])
+    }
+
+    fn map(prefix: Option<&str>, row: &PgRow) -> Self {
+        let prefix = prefix.unwrap_or_default();
+
+        Self {
+            id: row.get(format!("{}id", prefix).as_str()),
+            organization_id: row.get(format!("{}organization_id", prefix).as_str()),
+
+            label: row.get(format!("{}label", prefix).as_str()),
+            url: row.get(format!("{}url", prefix).as_str()),
+            secret: row.get(format!("{}secret", prefix).as_str()),
+            types: serde_json::from_value(row.get(format!("{}types", prefix).as_str())).unwrap(),
+            enabled: row.get(format!("{}enabled", prefix).as_str()),
+
+            successful: row.get(format!("{}successful", prefix).as_str()),
+            failed: row.get(format!("{}failed", prefix).as_str()),
+
+            created: row.get(format!("{}created", prefix).as_str()),
+        }
+    }
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let octets = ip.octets();
+
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || octets[0] == 0
+                || (octets[0] == 100 && (octets[1] & 0xc0) == 64)
+                || (octets[0] == 198 && (octets[1] & 0xfe) == 18)
+                || octets[0] >= 240)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+impl Webhook {
+    pub async fn resolve_url(url: &str) -> Result<(reqwest::Url, Vec<SocketAddr>), &'static str> {
+        if url.len() > 255 {
+            return Err("url must be a valid http(s) url");
+        }
+
+        let url = match reqwest::Url::parse(url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+            _ => return Err("url must be a valid http(s) url"),
+        };
+        let port = url
+            .port_or_known_default()
+            .ok_or("url must be a valid http(s) url")?;
+
+        let host = url
+            .host_str()
+            .ok_or("url must be a valid http(s) url")?
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+
+        let addrs = match host.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => match tokio::net::lookup_host((host, port)).await {
+                Ok(addrs) => addrs.collect::<Vec<_>>(),
+                Err(_) => return Err("url host could not be resolved"),
+            },
+        };
+
+        if addrs.is_empty() {
+            return Err("url host could not be resolved");
+        }
+
+        if addrs.iter().any(|addr| !is_public(addr.ip())) {
+            return Err("url must not point to a private address");
+        }
+
+        Ok((url, addrs))
+    }
+
+    pub async fn new(
+        database: &crate::database::Database,
+        organization_id: i32,
+        label: Option<&str>,
+        url: &str,
+        types: &[ServerType],
+    ) -> Result<(Self, String), crate::database::DatabaseError> {
+        let secret = rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 64);
+
+        let row = sqlx::query(&format!(
+            r#"
+            INSERT INTO webhooks (organization_id, label, url, secret, types)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            Self::columns_sql(None, None)
+        ))
+        .bind(organization_id)
+        .bind(label)
+        .bind(url)
+        .bind(&secret)
+        .bind(serde_json::to_value(types).unwrap())
+        .fetch_one(database.write())
+        .await?;
+
+        Ok((Self::map(None, &row), secret))
+    }
+
+    pub async fn save(
+        &self,
+        database: &crate::database::Database,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(
+            r#"
+            UPDATE webhooks
+            SET
+                label = $2,
+                url = $3,
+                types = $4,
+                enabled = $5
+            WHERE webhooks.id = $1
+            "#,
+        )
+        .bind(self.id)
+        .bind(&self.label)
+        .bind(&self.url)
+        .bind(serde_json::to_value(&self.types).unwrap())
+        .bind(self.enabled)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn count_by_organization(
+        database: &crate::database::Database,
+        organization_id: i32,
+    ) -> Result<i64, crate::database::DatabaseError> {
+        Ok(sqlx::query(
+            r#"
+            SELECT COUNT(*)
+            FROM webhooks
+            WHERE webhooks.organization_id = $1
+            "#,
+        )
+        .bind(organization_id)
+        .fetch_one(database.read())
+        .await?
+        .get(0))
+    }
+
+    pub async fn all_by_organization(
+        database: &crate::database::Database,
+        organization_id: i32,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
+            r#"
+            SELECT {} FROM webhooks
+            WHERE webhooks.organization_id = $1
+            ORDER BY webhooks.id DESC
+            "#,
+            Self::columns_sql(None, None)
+        ))
+        .bind(organization_id)
+        .fetch_all(database.read())
+        .await?
+        .into_iter()
+        .map(|row| Self::map(None, &row))
+        .collect())
+    }
+
+    pub async fn all_enabled_by_type(
+        database: &crate::database::Database,
+        r#type: ServerType,
+    ) -> Result<Vec<Self>, crate::database::DatabaseError> {
+        Ok(sqlx::query(&format!(
+            r#"
+            SELECT {} FROM webhooks
+            WHERE
+                webhooks.enabled
+                AND webhooks.types @> jsonb_build_array($1::text)
+            "#,
+            Self::columns_sql(None, None)
+        ))
+        .bind(r#type.to_string())
+        .fetch_all(database.read())
+        .await?
+        .into_iter()
+        .map(|row| Self::map(None, &row))
+        .collect())
+    }
+
+    pub async fn by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<Option<Self>, crate::database::DatabaseError> {
+        if id < 1 {
+            return Ok(None);
+        }
+
+        Ok(sqlx::query(&format!(
+            "SELECT {} FROM webhooks WHERE webhooks.id = $1",
+            Self::columns_sql(None, None)
+        ))
+        .bind(id)
+        .fetch_optional(database.read())
+        .await?
+        .map(|row| Self::map(None, &row)))
+    }
+
+    pub async fn record_delivery(
+        database: &crate::database::Database,
+        id: i32,
+        successful: bool,
+    ) -> Result<(), crate::database::DatabaseError> {
+        sqlx::query(if successful {
+            "UPDATE webhooks SET successful = successful + 1 WHERE webhooks.id = $1"
+        } else {
+            "UPDATE webhooks SET failed = failed + 1 WHERE webhooks.id = $1"
+        })
+        .bind(id)
+        .execute(database.write())
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_id(
+        database: &crate::database::Database,
+        id: i32,
+    ) -> Result<bool, crate::database::DatabaseError> {
+        Ok(sqlx::query(
+            r#"
+            DELETE FROM webhooks
+            WHERE webhooks.id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(database.write())
+        .await?
+        .rows_affected()
+            == 1)
+    }
+}
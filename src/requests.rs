@@ -2,7 +2,6 @@ use axum::http::{Method, request::Parts};
 use chrono::NaiveDateTime;
 use colored::Colorize;
 use rand::distr::SampleString;
-use rustis::commands::{ExpireOption, GenericCommands, StringCommands};
 use serde::{Deserialize, Serialize};
 use sqlx::types::ipnetwork::IpNetwork;
 use std::{collections::HashMap, sync::Arc};
@@ -77,22 +76,24 @@ impl RequestLogger {
         if organization.is_none() || !organization.as_ref().unwrap().verified {
             let ratelimit_key = format!("mcjars_api::ratelimit::{}", ip);
 
-            let count = self.cache.client.incr(&ratelimit_key).await.unwrap();
-            if count == 1 {
-                self.cache
-                    .client
-                    .expire(&ratelimit_key, 60, ExpireOption::None)
-                    .await
-                    .unwrap();
-            }
-
-            ratelimit = Some(RateLimitData {
-                limit: if organization.is_some() { 240 } else { 120 },
-                hits: count,
-            });
-
-            if count > ratelimit.unwrap().limit {
-                return Err(ratelimit);
+            match self.cache.backend.incr(&ratelimit_key).await {
+                Ok(count) => {
+                    if count == 1
+                        && let Err(err) = self.cache.backend.expire(&ratelimit_key, 60).await
+                    {
+                        crate::cache::Cache::report(&err);
+                    }
+
+                    ratelimit = Some(RateLimitData {
+                        limit: if organization.is_some() { 240 } else { 120 },
+                        hits: count,
+                    });
+
+                    if count > ratelimit.unwrap().limit {
+                        return Err(ratelimit);
+                    }
+                }
+                Err(err) => crate::cache::Cache::report(&err),
             }
         }
 
@@ -251,8 +252,10 @@ impl RequestLogger {
             }
         }
 
+        let mut processed = 0;
+
         for r in requests.iter() {
-            sqlx::query!(
+            if let Err(err) = sqlx::query!(
                 r#"
                 INSERT INTO requests (id, organization_id, origin, method, path, time, status, body, ip, continent, country, data, user_agent, created)
                 VALUES ($1, $2, $3, $4::text::Method, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
@@ -275,12 +278,22 @@ impl RequestLogger {
             )
             .execute(self.database.write())
             .await
-            .unwrap();
+            {
+                crate::database::DatabaseError::from(err).report();
+
+                break;
+            }
+
+            processed += 1;
+        }
+
+        if processed < requests.len() {
+            processing.splice(0..0, requests.drain(processed..));
         }
 
         crate::logger::log(
             crate::logger::LoggerLevel::Info,
-            format!("processed {} requests", requests.len().to_string().cyan()),
+            format!("processed {} requests", processed.to_string().cyan()),
         );
     }
 }
@@ -0,0 +1,213 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod patch {
+    use crate::{
+        models::build::{Build, BuildHash, InstallationStep},
+        routes::{ApiError, GetState},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[schema(rename_all = "camelCase")]
+    pub struct Payload {
+        experimental: Option<bool>,
+
+        build_number: Option<i32>,
+        jar_url: Option<String>,
+        jar_size: Option<i32>,
+        zip_url: Option<String>,
+        zip_size: Option<i32>,
+
+        installation: Option<Vec<Vec<InstallationStep>>>,
+        changes: Option<Vec<String>>,
+        hashes: Option<Vec<BuildHash>>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        build: Build,
+    }
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "build" = i32,
+            description = "The build ID",
+            example = 1,
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        Path(build): Path<i32>,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let mut build = match Build::by_id(&state.database, build).await {
+            Ok(Some(build)) => build,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["build not found"]).to_value()),
+                );
+            }
+            Err(err) => return err.into(),
+        };
+
+        let rehash = data.jar_url.is_some()
+            || data.jar_size.is_some()
+            || data.zip_url.is_some()
+            || data.zip_size.is_some()
+            || data.installation.is_some()
+            || data.hashes.is_some();
+
+        if let Some(installation) = data.installation {
+            if let Err(error) = Build::validate_installation(&installation) {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&[&error]).to_value()),
+                );
+            }
+
+            build.installation = installation;
+        }
+
+        if let Some(hashes) = &data.hashes
+            && !hashes.iter().all(|hash| hash.is_valid())
+        {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&["invalid hashes"]).to_value()),
+            );
+        }
+
+        if let Some(experimental) = data.experimental {
+            build.experimental = experimental;
+        }
+
+        if let Some(build_number) = data.build_number {
+            build.build_number = build_number;
+        }
+
+        if data.jar_url.is_some() || data.jar_size.is_some() {
+            if let Some(jar_url) = data.jar_url {
+                build.jar_url = Some(jar_url);
+            }
+
+            build.jar_size = data.jar_size;
+        }
+
+        if data.zip_url.is_some() || data.zip_size.is_some() {
+            if let Some(zip_url) = data.zip_url {
+                build.zip_url = Some(zip_url);
+            }
+
+            build.zip_size = data.zip_size;
+        }
+
+        if let Some(changes) = data.changes {
+            build.changes = changes;
+        }
+
+        let hashes = data.hashes.as_ref().map(|hashes| {
+            hashes
+                .iter()
+                .filter(|hash| hash.primary)
+                .cloned()
+                .collect::<Vec<BuildHash>>()
+        });
+
+        if let Err(err) = build.save(&state.database, hashes.as_deref()).await {
+            return err.into();
+        }
+        if rehash {
+            tokio::spawn(build.clone().rehash(
+                state.database.clone(),
+                state.cache.clone(),
+                state.client.clone(),
+                data.hashes.unwrap_or_default(),
+            ));
+        }
+        if let Err(err) = build.invalidate_cache(&state.cache).await {
+            crate::cache::Cache::report(&err);
+        }
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    build,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+mod delete {
+    use crate::{
+        models::build::Build,
+        routes::{ApiError, GetState},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+    }
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "build" = i32,
+            description = "The build ID",
+            example = 1,
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        Path(build): Path<i32>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let build = match Build::by_id(&state.database, build).await {
+            Ok(build) => build,
+            Err(err) => return err.into(),
+        };
+
+        if let Some(build) = build {
+            if let Err(err) = Build::delete_by_id(&state.database, build.id).await {
+                return err.into();
+            }
+            if let Err(err) = build.invalidate_cache(&state.cache).await {
+                crate::cache::Cache::report(&err);
+            }
+
+            (
+                StatusCode::OK,
+                axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                axum::Json(ApiError::new(&["build not found"]).to_value()),
+            )
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(patch::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
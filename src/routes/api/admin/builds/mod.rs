@@ -0,0 +1,169 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _build_;
+
+mod post {
+    use crate::{
+        models::{
+            build::{Build, BuildHash, InstallationStep},
+            r#type::ServerType,
+            version::MinecraftVersion,
+        },
+        routes::{ApiError, GetState},
+    };
+    use axum::http::StatusCode;
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[schema(rename_all = "camelCase")]
+    pub struct Payload {
+        r#type: ServerType,
+        version_id: Option<String>,
+        project_version_id: Option<String>,
+        #[serde(default)]
+        experimental: bool,
+
+        build_number: i32,
+        jar_url: Option<String>,
+        jar_size: Option<i32>,
+        zip_url: Option<String>,
+        zip_size: Option<i32>,
+
+        installation: Vec<Vec<InstallationStep>>,
+        #[serde(default)]
+        changes: Vec<String>,
+        #[serde(default)]
+        hashes: Vec<BuildHash>,
+
+        created: Option<NaiveDateTime>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        build: Build,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = CREATED, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        if data.version_id.is_none() && data.project_version_id.is_none() {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(
+                    ApiError::new(&["versionId or projectVersionId is required"]).to_value(),
+                ),
+            );
+        }
+
+        if let Some(version_id) = &data.version_id {
+            match MinecraftVersion::by_id(&state.database, version_id).await {
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        axum::Json(ApiError::new(&["version not found"]).to_value()),
+                    );
+                }
+                Err(err) => return err.into(),
+            }
+        }
+
+        if let Some(project_version_id) = &data.project_version_id {
+            match Build::project_version_exists(&state.database, data.r#type, project_version_id)
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        axum::Json(ApiError::new(&["project version not found"]).to_value()),
+                    );
+                }
+                Err(err) => return err.into(),
+            }
+        }
+
+        if let Err(error) = Build::validate_installation(&data.installation) {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&[&error]).to_value()),
+            );
+        }
+
+        if !data.hashes.iter().all(|hash| hash.is_valid()) {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&["invalid hashes"]).to_value()),
+            );
+        }
+
+        let build = Build {
+            id: 0,
+            version_id: data.version_id,
+            project_version_id: data.project_version_id,
+            r#type: data.r#type,
+            experimental: data.experimental,
+            name: String::new(),
+            build_number: data.build_number,
+            jar_url: data.jar_url,
+            jar_size: data.jar_size,
+            zip_url: data.zip_url,
+            zip_size: data.zip_size,
+            installation: data.installation,
+            changes: data.changes,
+            created: data.created,
+        };
+
+        let hashes = data
+            .hashes
+            .iter()
+            .filter(|hash| hash.primary)
+            .cloned()
+            .collect::<Vec<BuildHash>>();
+
+        let build = match Build::new(&state.database, &build, &hashes).await {
+            Ok(build) => build,
+            Err(err) => return err.into(),
+        };
+
+        tokio::spawn(build.clone().rehash(
+            state.database.clone(),
+            state.cache.clone(),
+            state.client.clone(),
+            data.hashes,
+        ));
+
+        if let Err(err) = build.invalidate_cache(&state.cache).await {
+            crate::cache::Cache::report(&err);
+        }
+
+        (
+            StatusCode::CREATED,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    build,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .nest("/{build}", _build_::router(state))
+        .with_state(state.clone())
+}
@@ -0,0 +1,32 @@
+use super::{ApiError, State, user::GetUser};
+use axum::{body::Body, extract::Request, http::StatusCode, middleware::Next, response::Response};
+use utoipa_axum::router::OpenApiRouter;
+
+mod builds;
+mod versions;
+
+async fn auth(user: GetUser, req: Request, next: Next) -> Result<Response, StatusCode> {
+    if !user.admin {
+        return Ok(Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .header("Content-Type", "application/json")
+            .body(Body::from(
+                serde_json::to_string(&ApiError::new(&["forbidden"])).unwrap(),
+            ))
+            .unwrap());
+    }
+
+    Ok(next.run(req).await)
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .nest("/builds", builds::router(state))
+        .nest("/versions", versions::router(state))
+        .route_layer(axum::middleware::from_fn(auth))
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            super::user::auth,
+        ))
+        .with_state(state.clone())
+}
@@ -0,0 +1,88 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod patch {
+    use crate::{
+        models::version::{MinecraftVersion, VersionType},
+        routes::{ApiError, GetState},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        r#type: Option<VersionType>,
+        supported: Option<bool>,
+        java: Option<i16>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        version: MinecraftVersion,
+    }
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "version" = String,
+            description = "The minecraft version",
+            example = "1.17.1",
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        Path(version): Path<String>,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let mut version = match MinecraftVersion::by_id(&state.database, &version).await {
+            Ok(Some(version)) => version,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["version not found"]).to_value()),
+                );
+            }
+            Err(err) => return err.into(),
+        };
+
+        if let Some(r#type) = data.r#type {
+            version.r#type = r#type;
+        }
+
+        if let Some(supported) = data.supported {
+            version.supported = supported;
+        }
+
+        if let Some(java) = data.java {
+            version.java = java;
+        }
+
+        if let Err(err) = version.save(&state.database).await {
+            return err.into();
+        }
+        if let Err(err) = version.invalidate_cache(&state.cache).await {
+            crate::cache::Cache::report(&err);
+        }
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    version,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(patch::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,93 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _version_;
+
+mod post {
+    use crate::{
+        models::version::{MinecraftVersion, VersionType},
+        routes::{ApiError, GetState},
+    };
+    use axum::http::StatusCode;
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        #[schema(max_length = 63)]
+        id: String,
+        r#type: VersionType,
+        supported: bool,
+        java: i16,
+        created: Option<NaiveDateTime>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        version: MinecraftVersion,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = CREATED, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        if !(1..64).contains(&data.id.len()) {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&["id must be between 1 and 63 characters"]).to_value()),
+            );
+        }
+
+        let existing = match MinecraftVersion::by_id(&state.database, &data.id).await {
+            Ok(existing) => existing,
+            Err(err) => return err.into(),
+        };
+
+        let version = MinecraftVersion {
+            created: data
+                .created
+                .or(existing.as_ref().map(|existing| existing.created))
+                .unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+            id: data.id,
+            r#type: data.r#type,
+            supported: data.supported,
+            java: data.java,
+        };
+
+        if let Err(err) = version.save(&state.database).await {
+            return err.into();
+        }
+        if let Err(err) = version.invalidate_cache(&state.cache).await {
+            crate::cache::Cache::report(&err);
+        }
+
+        (
+            if existing.is_some() {
+                StatusCode::OK
+            } else {
+                StatusCode::CREATED
+            },
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    version,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(post::route))
+        .nest("/{version}", _version_::router(state))
+        .with_state(state.clone())
+}
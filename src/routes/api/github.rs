@@ -85,15 +85,22 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
                     .find(|email| email["primary"].as_bool().unwrap())
                     .unwrap();
 
-                let user = User::new(
+                let user = match User::new(
                     &state.database,
                     data["id"].as_i64().unwrap() as i32,
                     data["name"].as_str().map(|s| s.to_string()),
                     email["email"].as_str().unwrap().to_string(),
                     data["login"].as_str().unwrap().to_string(),
-                ).await;
+                ).await {
+                    Ok(user) => user,
+                    Err(err) => {
+                        err.report();
 
-                let (_, key) = UserSession::new(
+                        return (err.status(), HeaderMap::new(), err.message());
+                    }
+                };
+
+                let (_, key) = match UserSession::new(
                     &state.database,
                     user.id,
                     headers
@@ -104,7 +111,14 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
                         .parse()
                         .unwrap(),
                     headers.get("User-Agent").map(|ua| ua.to_str().unwrap()).unwrap_or("")
-                ).await;
+                ).await {
+                    Ok(session) => session,
+                    Err(err) => {
+                        err.report();
+
+                        return (err.status(), HeaderMap::new(), err.message());
+                    }
+                };
 
                 cookies.add(
                     Cookie::build(("session", key))
@@ -1,6 +1,7 @@
 use super::{ApiError, GetState, State};
 use utoipa_axum::router::OpenApiRouter;
 
+mod admin;
 mod github;
 mod organization;
 mod user;
@@ -14,5 +15,6 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/organization", organization::router(state))
         .nest("/github", github::router(state))
         .nest("/user", user::router(state))
+        .nest("/admin", admin::router(state))
         .with_state(state.clone())
 }
@@ -8,6 +8,7 @@ mod get {
         models::r#type::ServerType,
         routes::{GetState, api::organization::GetOrganization},
     };
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use utoipa::ToSchema;
@@ -44,15 +45,18 @@ mod get {
     pub async fn route(
         state: GetState,
         organization: GetOrganization,
-    ) -> axum::Json<serde_json::Value> {
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
         let organization = organization.as_ref().unwrap().clone();
 
-        let (requests, user_agents, origins) = state
+        let database = state.database.clone();
+        let organization_id = organization.id;
+
+        let (requests, user_agents, origins) = match state
             .cache
             .cached(
                 &format!("organization::{}::stats", organization.id),
                 300,
-                || async {
+                move || async move {
                     let (requests, user_agents, origins) = tokio::join!(
                         sqlx::query(
                             r#"
@@ -62,8 +66,8 @@ mod get {
                     WHERE requests.organization_id = $1
                     "#,
                         )
-                        .bind(organization.id)
-                        .fetch_one(state.database.read()),
+                        .bind(organization_id)
+                        .fetch_one(database.read()),
                         sqlx::query(
                             r#"
                     SELECT
@@ -73,8 +77,8 @@ mod get {
                     GROUP BY requests.user_agent
                     "#,
                         )
-                        .bind(organization.id)
-                        .fetch_all(state.database.read()),
+                        .bind(organization_id)
+                        .fetch_all(database.read()),
                         sqlx::query(
                             r#"
                     SELECT
@@ -84,8 +88,8 @@ mod get {
                     GROUP BY requests.origin
                     "#,
                         )
-                        .bind(organization.id)
-                        .fetch_all(state.database.read())
+                        .bind(organization_id)
+                        .fetch_all(database.read())
                     );
 
                     let requests = requests.unwrap();
@@ -100,26 +104,33 @@ mod get {
                         .map(|row| row.get("origin"))
                         .collect();
 
-                    (requests.get("requests"), user_agents, origins)
+                    Ok((requests.get("requests"), user_agents, origins))
                 },
             )
-            .await;
+            .await
+        {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                infos: Infos {
-                    icon: organization.icon,
-                    name: organization.name,
-                    types: organization.types,
-                },
-                stats: Stats {
-                    requests,
-                    user_agents,
-                    origins,
-                },
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    infos: Infos {
+                        icon: organization.icon,
+                        name: organization.name,
+                        types: organization.types,
+                    },
+                    stats: Stats {
+                        requests,
+                        user_agents,
+                        origins,
+                    },
+                })
+                .unwrap(),
+            ),
         )
     }
 }
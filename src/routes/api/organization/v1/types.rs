@@ -6,6 +6,7 @@ mod get {
         models::r#type::{ServerType, ServerTypeInfo},
         routes::{GetState, api::organization::GetOrganization},
     };
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
@@ -22,30 +23,36 @@ mod get {
     pub async fn route(
         state: GetState,
         organization: GetOrganization,
-    ) -> axum::Json<serde_json::Value> {
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
         let organization = organization.as_ref().unwrap();
-        let data = ServerType::all(&state.database, &state.cache).await;
+        let data = match ServerType::all(&state.database, &state.cache).await {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                types: if !organization.types.is_empty() {
-                    ServerType::extract(&data, &organization.types)
-                } else {
-                    data
-                },
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    types: if !organization.types.is_empty() {
+                        ServerType::extract(&data, &organization.types)
+                    } else {
+                        data
+                    },
+                })
+                .unwrap(),
+            ),
         )
     }
 }
 
 mod patch {
     use crate::{
-        models::r#type::ServerType,
+        models::{organization::Organization, r#type::ServerType},
         routes::{GetState, api::organization::GetOrganization},
     };
-    use rustis::commands::GenericCommands;
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -72,23 +79,26 @@ mod patch {
         state: GetState,
         mut organization: GetOrganization,
         axum::Json(data): axum::Json<Payload>,
-    ) -> axum::Json<serde_json::Value> {
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
         let organization = organization.as_mut().unwrap();
 
         organization.types = data.types;
-        organization.save(&state.database).await;
+        if let Err(err) = organization.save(&state.database).await {
+            return err.into();
+        }
 
-        let keys: Vec<String> = state
+        if let Err(err) = state
             .cache
-            .client
-            .keys(format!("organization::{}*", organization.id))
+            .invalidate_tag(&Organization::cache_tag(organization.id))
             .await
-            .unwrap();
-        if !keys.is_empty() {
-            state.cache.client.del(keys).await.unwrap();
+        {
+            crate::cache::Cache::report(&err);
         }
 
-        axum::Json(serde_json::to_value(&Response { success: true }).unwrap())
+        (
+            StatusCode::OK,
+            axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+        )
     }
 }
 
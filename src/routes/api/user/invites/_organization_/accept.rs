@@ -6,7 +6,7 @@ mod post {
         models::organization::{Organization, OrganizationSubuser},
         routes::{ApiError, GetState, api::user::GetUser},
     };
-    use axum::extract::Path;
+    use axum::{extract::Path, http::StatusCode};
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -29,27 +29,53 @@ mod post {
         state: GetState,
         user: GetUser,
         Path(organization): Path<i32>,
-    ) -> axum::Json<serde_json::Value> {
-        let organization = Organization::by_id(&state.database, &state.cache, organization).await;
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let organization =
+            match Organization::by_id(&state.database, &state.cache, organization).await {
+                Ok(organization) => organization,
+                Err(err) => return err.into(),
+            };
 
         if let Some(organization) = organization {
-            let subuser =
-                OrganizationSubuser::by_ids(&state.database, organization.id, user.id).await;
+            let subuser = match OrganizationSubuser::by_ids(
+                &state.database,
+                organization.id,
+                user.id,
+            )
+            .await
+            {
+                Ok(subuser) => subuser,
+                Err(err) => return err.into(),
+            };
 
             if let Some(mut subuser) = subuser {
                 if !subuser.pending {
-                    return axum::Json(ApiError::new(&["subuser already accepted"]).to_value());
+                    return (
+                        StatusCode::OK,
+                        axum::Json(ApiError::new(&["subuser already accepted"]).to_value()),
+                    );
                 }
 
                 subuser.pending = false;
-                subuser.save(&state.database).await;
+                if let Err(err) = subuser.save(&state.database).await {
+                    return err.into();
+                }
 
-                axum::Json(serde_json::to_value(&Response { success: true }).unwrap())
+                (
+                    StatusCode::OK,
+                    axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+                )
             } else {
-                axum::Json(ApiError::new(&["organization not found"]).to_value())
+                (
+                    StatusCode::OK,
+                    axum::Json(ApiError::new(&["organization not found"]).to_value()),
+                )
             }
         } else {
-            axum::Json(ApiError::new(&["organization not found"]).to_value())
+            (
+                StatusCode::OK,
+                axum::Json(ApiError::new(&["organization not found"]).to_value()),
+            )
         }
     }
 }
@@ -6,7 +6,7 @@ mod post {
         models::organization::{Organization, OrganizationSubuser},
         routes::{ApiError, GetState, api::user::GetUser},
     };
-    use axum::extract::Path;
+    use axum::{extract::Path, http::StatusCode};
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -29,20 +29,46 @@ mod post {
         state: GetState,
         user: GetUser,
         Path(organization): Path<i32>,
-    ) -> axum::Json<serde_json::Value> {
-        let organization = Organization::by_id(&state.database, &state.cache, organization).await;
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let organization =
+            match Organization::by_id(&state.database, &state.cache, organization).await {
+                Ok(organization) => organization,
+                Err(err) => return err.into(),
+            };
 
         if let Some(organization) = organization {
             let deleted =
-                OrganizationSubuser::delete_by_ids(&state.database, organization.id, user.id).await;
+                match OrganizationSubuser::delete_by_ids(&state.database, organization.id, user.id)
+                    .await
+                {
+                    Ok(deleted) => deleted,
+                    Err(err) => return err.into(),
+                };
 
             if !deleted {
-                return axum::Json(ApiError::new(&["subuser not found"]).to_value());
+                return (
+                    StatusCode::OK,
+                    axum::Json(ApiError::new(&["subuser not found"]).to_value()),
+                );
             }
 
-            axum::Json(serde_json::to_value(&Response { success: true }).unwrap())
+            if let Err(err) = state
+                .cache
+                .invalidate_tag(&Organization::cache_tag(organization.id))
+                .await
+            {
+                crate::cache::Cache::report(&err);
+            }
+
+            (
+                StatusCode::OK,
+                axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+            )
         } else {
-            axum::Json(ApiError::new(&["organization not found"]).to_value())
+            (
+                StatusCode::OK,
+                axum::Json(ApiError::new(&["organization not found"]).to_value()),
+            )
         }
     }
 }
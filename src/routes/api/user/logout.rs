@@ -3,6 +3,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod get {
     use crate::{models::user::UserSession, routes::GetState};
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use tower_cookies::{Cookie, Cookies};
     use utoipa::ToSchema;
@@ -15,9 +16,14 @@ mod get {
     #[utoipa::path(post, path = "/", responses(
         (status = OK, body = inline(Response)),
     ))]
-    pub async fn route(state: GetState, cookies: Cookies) -> axum::Json<serde_json::Value> {
+    pub async fn route(
+        state: GetState,
+        cookies: Cookies,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
         let session = cookies.get("session").unwrap();
-        UserSession::delete_by_session(&state.database, session.value()).await;
+        if let Err(err) = UserSession::delete_by_session(&state.database, session.value()).await {
+            return err.into();
+        }
 
         cookies.add(
             Cookie::build(("session", ""))
@@ -33,7 +39,10 @@ mod get {
                 .build(),
         );
 
-        axum::Json(serde_json::to_value(&Response { success: true }).unwrap())
+        (
+            StatusCode::OK,
+            axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+        )
     }
 }
 
@@ -10,7 +10,7 @@ mod organizations;
 
 pub type GetUser = axum::extract::Extension<User>;
 
-async fn auth(
+pub async fn auth(
     state: GetState,
     cookies: Cookies,
     mut req: Request,
@@ -31,12 +31,31 @@ async fn auth(
             .unwrap());
     }
 
+    let database = state.database.clone();
+    let session = session_id.clone();
+
     let user = state
         .cache
-        .cached(&format!("user::session::{}", session_id), 300, || {
-            User::by_session(&state.database, &session_id)
-        })
+        .cached(
+            &format!("user::session::{}", session_id),
+            300,
+            move || async move { User::by_session(&database, &session).await },
+        )
         .await;
+    let user = match user {
+        Ok(user) => user,
+        Err(err) => {
+            err.report();
+
+            return Ok(Response::builder()
+                .status(err.status())
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&ApiError::new(&[err.message()])).unwrap(),
+                ))
+                .unwrap());
+        }
+    };
 
     if user.is_none() {
         return Ok(Response::builder()
@@ -64,7 +83,9 @@ async fn auth(
         .map(|ua| ua.to_str().unwrap_or_default())
         .unwrap_or_default()
         .to_string();
-    session.save(&state.database).await;
+    if let Err(err) = session.save(&state.database).await {
+        err.report();
+    }
 
     cookies.add(
         Cookie::build(("session", session_id))
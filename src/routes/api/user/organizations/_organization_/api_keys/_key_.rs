@@ -38,7 +38,10 @@ mod get {
         organization: GetOrganization,
         Path((_organization, key)): Path<(i32, i32)>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let key = OrganizationKey::by_id(&state.database, key).await;
+        let key = match OrganizationKey::by_id(&state.database, key).await {
+            Ok(key) => key,
+            Err(err) => return err.into(),
+        };
 
         if let Some(key) = key {
             if key.organization_id != organization.id {
@@ -69,7 +72,7 @@ mod get {
 
 mod delete {
     use crate::{
-        models::organization::OrganizationKey,
+        models::organization::{Organization, OrganizationKey},
         routes::{ApiError, GetState, api::user::organizations::_organization_::GetOrganization},
     };
     use axum::{extract::Path, http::StatusCode};
@@ -101,7 +104,10 @@ mod delete {
         organization: GetOrganization,
         Path((_organization, key)): Path<(i32, i32)>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let key = OrganizationKey::by_id(&state.database, key).await;
+        let key = match OrganizationKey::by_id(&state.database, key).await {
+            Ok(key) => key,
+            Err(err) => return err.into(),
+        };
 
         if let Some(key) = key {
             if key.organization_id != organization.id {
@@ -111,7 +117,17 @@ mod delete {
                 );
             }
 
-            OrganizationKey::delete_by_id(&state.database, key.id).await;
+            if let Err(err) = OrganizationKey::delete_by_id(&state.database, key.id).await {
+                return err.into();
+            }
+
+            if let Err(err) = state
+                .cache
+                .invalidate_tag(&Organization::cache_tag(organization.id))
+                .await
+            {
+                crate::cache::Cache::report(&err);
+            }
 
             (
                 StatusCode::OK,
@@ -8,6 +8,7 @@ mod get {
         models::organization::OrganizationKey,
         routes::{GetState, api::user::organizations::_organization_::GetOrganization},
     };
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -31,14 +32,22 @@ mod get {
     pub async fn route(
         state: GetState,
         organization: GetOrganization,
-    ) -> axum::Json<serde_json::Value> {
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                api_keys: OrganizationKey::all_by_organization(&state.database, organization.id)
-                    .await,
-            })
-            .unwrap(),
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let api_keys =
+            match OrganizationKey::all_by_organization(&state.database, organization.id).await {
+                Ok(api_keys) => api_keys,
+                Err(err) => return err.into(),
+            };
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    api_keys,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
@@ -86,7 +95,11 @@ mod post {
             );
         }
 
-        let count = OrganizationKey::count_by_organization(&state.database, organization.id).await;
+        let count =
+            match OrganizationKey::count_by_organization(&state.database, organization.id).await {
+                Ok(count) => count,
+                Err(err) => return err.into(),
+            };
         if count >= 15 {
             return (
                 StatusCode::CONFLICT,
@@ -95,7 +108,10 @@ mod post {
         }
 
         let (inserted, key) =
-            OrganizationKey::new(&state.database, organization.id, &payload.name).await;
+            match OrganizationKey::new(&state.database, organization.id, &payload.name).await {
+                Ok(data) => data,
+                Err(err) => return err.into(),
+            };
         if inserted {
             (
                 StatusCode::CREATED,
@@ -2,12 +2,12 @@ use super::State;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod post {
-    use crate::routes::{
-        ApiError, GetState, api::user::organizations::_organization_::GetOrganization,
+    use crate::{
+        models::organization::Organization,
+        routes::{ApiError, GetState, api::user::organizations::_organization_::GetOrganization},
     };
     use axum::{body::Bytes, http::StatusCode};
     use image::{ImageReader, codecs::webp::WebPEncoder, imageops::FilterType};
-    use rustis::commands::GenericCommands;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -82,16 +82,16 @@ mod post {
         }
 
         organization.icon = url.clone();
-        organization.save(&state.database).await;
+        if let Err(err) = organization.save(&state.database).await {
+            return err.into();
+        }
 
-        let keys: Vec<String> = state
+        if let Err(err) = state
             .cache
-            .client
-            .keys(format!("organization::{}*", organization.id))
+            .invalidate_tag(&Organization::cache_tag(organization.id))
             .await
-            .unwrap();
-        if !keys.is_empty() {
-            state.cache.client.del(keys).await.unwrap();
+        {
+            crate::cache::Cache::report(&err);
         }
 
         (
@@ -15,6 +15,7 @@ mod api_keys;
 mod icon;
 mod stats;
 mod subusers;
+mod webhooks;
 
 pub type GetOrganization = axum::extract::Extension<Organization>;
 
@@ -50,14 +51,28 @@ async fn auth(
         }
     };
 
-    let organization = Organization::by_id_and_user(
+    let organization = match Organization::by_id_and_user(
         &state.database,
         &state.cache,
         user.id,
         user.admin,
         organization,
     )
-    .await;
+    .await
+    {
+        Ok(organization) => organization,
+        Err(err) => {
+            err.report();
+
+            return Ok(Response::builder()
+                .status(err.status())
+                .header("Content-Type", "application/json")
+                .body(Body::from(
+                    serde_json::to_string(&ApiError::new(&[err.message()])).unwrap(),
+                ))
+                .unwrap());
+        }
+    };
 
     if organization.is_none() {
         return Ok(Response::builder()
@@ -77,6 +92,7 @@ async fn auth(
 mod get {
     use super::GetOrganization;
     use crate::models::organization::Organization;
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -95,13 +111,18 @@ mod get {
             example = 1,
         ),
     ))]
-    pub async fn route(organization: GetOrganization) -> axum::Json<serde_json::Value> {
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                organization: organization.0,
-            })
-            .unwrap(),
+    pub async fn route(
+        organization: GetOrganization,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    organization: organization.0,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
@@ -158,7 +179,10 @@ mod patch {
                 );
             }
 
-            let user = User::by_login(&state.database, &state.cache, &owner).await;
+            let user = match User::by_login(&state.database, &state.cache, &owner).await {
+                Ok(user) => user,
+                Err(err) => return err.into(),
+            };
             if user.is_none() {
                 return (
                     StatusCode::NOT_FOUND,
@@ -168,7 +192,10 @@ mod patch {
 
             owner_id = user.unwrap().id;
 
-            let count = Organization::count_by_owner(&state.database, owner_id).await;
+            let count = match Organization::count_by_owner(&state.database, owner_id).await {
+                Ok(count) => count,
+                Err(err) => return err.into(),
+            };
             if count >= 1 {
                 return (
                     StatusCode::CONFLICT,
@@ -178,7 +205,11 @@ mod patch {
                 );
             }
 
-            OrganizationSubuser::delete_by_ids(&state.database, organization.id, owner_id).await;
+            if let Err(err) =
+                OrganizationSubuser::delete_by_ids(&state.database, organization.id, owner_id).await
+            {
+                return err.into();
+            }
         }
 
         if let Some(name) = data.name {
@@ -194,7 +225,17 @@ mod patch {
         }
 
         organization.owner.id = owner_id;
-        organization.save(&state.database).await;
+        if let Err(err) = organization.save(&state.database).await {
+            return err.into();
+        }
+
+        if let Err(err) = state
+            .cache
+            .invalidate_tag(&Organization::cache_tag(organization.id))
+            .await
+        {
+            crate::cache::Cache::report(&err);
+        }
 
         (
             StatusCode::OK,
@@ -251,7 +292,17 @@ mod delete {
                 .unwrap_or_default();
         }
 
-        Organization::delete_by_id(&state.database, organization.id).await;
+        if let Err(err) = Organization::delete_by_id(&state.database, organization.id).await {
+            return err.into();
+        }
+
+        if let Err(err) = state
+            .cache
+            .invalidate_tag(&Organization::cache_tag(organization.id))
+            .await
+        {
+            crate::cache::Cache::report(&err);
+        }
 
         (
             StatusCode::OK,
@@ -269,6 +320,7 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .nest("/icon", icon::router(state))
         .nest("/api-keys", api_keys::router(state))
         .nest("/subusers", subusers::router(state))
+        .nest("/webhooks", webhooks::router(state))
         .route_layer(axum::middleware::from_fn_with_state(state.clone(), auth))
         .with_state(state.clone())
 }
@@ -3,6 +3,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod get {
     use crate::routes::{GetState, api::user::organizations::_organization_::GetOrganization};
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use utoipa::ToSchema;
@@ -39,13 +40,16 @@ mod get {
     pub async fn route(
         state: GetState,
         organization: GetOrganization,
-    ) -> axum::Json<serde_json::Value> {
-        let stats = state
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+        let organization_id = organization.id;
+
+        let stats = match state
             .cache
             .cached(
-                &format!("organization::stats::{}", organization.id),
+                &format!("organization::stats::{}", organization_id),
                 300,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -59,29 +63,35 @@ mod get {
                         WHERE requests.organization_id = $1
                         "#,
                     )
-                    .bind(organization.id)
-                    .fetch_one(state.database.read())
-                    .await
-                    .unwrap();
+                    .bind(organization_id)
+                    .fetch_one(database.read())
+                    .await?;
 
-                    Stats {
+                    Ok(Stats {
                         requests: data.get(0),
                         user_agents: data.get(1),
                         ips: data.get(2),
                         origins: data.get(3),
                         continents: data.get(4),
                         countries: data.get(5),
-                    }
+                    })
                 },
             )
-            .await;
+            .await
+        {
+            Ok(stats) => stats,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                stats,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    stats,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
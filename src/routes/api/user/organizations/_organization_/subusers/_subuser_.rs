@@ -36,11 +36,22 @@ mod get {
         organization: GetOrganization,
         Path((_organization, login)): Path<(i32, String)>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let user = User::by_login(&state.database, &state.cache, &login).await;
+        let user = match User::by_login(&state.database, &state.cache, &login).await {
+            Ok(user) => user,
+            Err(err) => return err.into(),
+        };
 
         if let Some(user) = user {
-            let subuser =
-                OrganizationSubuser::by_ids(&state.database, organization.id, user.id).await;
+            let subuser = match OrganizationSubuser::by_ids(
+                &state.database,
+                organization.id,
+                user.id,
+            )
+            .await
+            {
+                Ok(subuser) => subuser,
+                Err(err) => return err.into(),
+            };
 
             if let Some(subuser) = subuser {
                 (
@@ -70,7 +81,10 @@ mod get {
 
 mod delete {
     use crate::{
-        models::{organization::OrganizationSubuser, user::User},
+        models::{
+            organization::{Organization, OrganizationSubuser},
+            user::User,
+        },
         routes::{
             ApiError, GetState,
             api::user::{GetUser, organizations::_organization_::GetOrganization},
@@ -108,7 +122,10 @@ mod delete {
         organization: GetOrganization,
         Path((_organization, login)): Path<(i32, String)>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let user = User::by_login(&state.database, &state.cache, &login).await;
+        let user = match User::by_login(&state.database, &state.cache, &login).await {
+            Ok(user) => user,
+            Err(err) => return err.into(),
+        };
 
         if auth_user.id != user.as_ref().map(|u| u.id).unwrap_or_default()
             && auth_user.id != organization.owner.id
@@ -120,11 +137,32 @@ mod delete {
         }
 
         if let Some(user) = user {
-            let subuser =
-                OrganizationSubuser::by_ids(&state.database, organization.id, user.id).await;
+            let subuser = match OrganizationSubuser::by_ids(
+                &state.database,
+                organization.id,
+                user.id,
+            )
+            .await
+            {
+                Ok(subuser) => subuser,
+                Err(err) => return err.into(),
+            };
 
             if subuser.is_some() {
-                OrganizationSubuser::delete_by_ids(&state.database, organization.id, user.id).await;
+                if let Err(err) =
+                    OrganizationSubuser::delete_by_ids(&state.database, organization.id, user.id)
+                        .await
+                {
+                    return err.into();
+                }
+
+                if let Err(err) = state
+                    .cache
+                    .invalidate_tag(&Organization::cache_tag(organization.id))
+                    .await
+                {
+                    crate::cache::Cache::report(&err);
+                }
 
                 (
                     StatusCode::OK,
@@ -8,6 +8,7 @@ mod get {
         models::organization::OrganizationSubuser,
         routes::{GetState, api::user::organizations::_organization_::GetOrganization},
     };
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -31,21 +32,33 @@ mod get {
     pub async fn route(
         state: GetState,
         organization: GetOrganization,
-    ) -> axum::Json<serde_json::Value> {
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                users: OrganizationSubuser::all_by_organization(&state.database, organization.id)
-                    .await,
-            })
-            .unwrap(),
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let users = match OrganizationSubuser::all_by_organization(&state.database, organization.id)
+            .await
+        {
+            Ok(users) => users,
+            Err(err) => return err.into(),
+        };
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    users,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
 
 mod post {
     use crate::{
-        models::{organization::OrganizationSubuser, user::User},
+        models::{
+            organization::{Organization, OrganizationSubuser},
+            user::User,
+        },
         routes::{
             ApiError, GetState,
             api::user::{GetUser, organizations::_organization_::GetOrganization},
@@ -90,10 +103,20 @@ mod post {
             );
         }
 
-        let user = User::by_login(&state.database, &state.cache, &payload.login).await;
+        let user = match User::by_login(&state.database, &state.cache, &payload.login).await {
+            Ok(user) => user,
+            Err(err) => return err.into(),
+        };
 
-        let count =
-            OrganizationSubuser::count_by_organization(&state.database, organization.id).await;
+        let count = match OrganizationSubuser::count_by_organization(
+            &state.database,
+            organization.id,
+        )
+        .await
+        {
+            Ok(count) => count,
+            Err(err) => return err.into(),
+        };
         if count >= 15 {
             return (
                 StatusCode::CONFLICT,
@@ -110,9 +133,20 @@ mod post {
             }
 
             let inserted =
-                OrganizationSubuser::new(&state.database, organization.id, user.id).await;
+                match OrganizationSubuser::new(&state.database, organization.id, user.id).await {
+                    Ok(inserted) => inserted,
+                    Err(err) => return err.into(),
+                };
 
             if inserted {
+                if let Err(err) = state
+                    .cache
+                    .invalidate_tag(&Organization::cache_tag(organization.id))
+                    .await
+                {
+                    crate::cache::Cache::report(&err);
+                }
+
                 (
                     StatusCode::CREATED,
                     axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
@@ -0,0 +1,246 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::{
+        models::webhook::Webhook,
+        routes::{ApiError, GetState, api::user::organizations::_organization_::GetOrganization},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        webhook: Webhook,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "organization" = i32,
+            description = "The organization ID",
+            example = 1,
+        ),
+        (
+            "webhook" = i32,
+            description = "The webhook ID",
+            example = 1,
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        organization: GetOrganization,
+        Path((_organization, webhook)): Path<(i32, i32)>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let webhook = match Webhook::by_id(&state.database, webhook).await {
+            Ok(webhook) => webhook,
+            Err(err) => return err.into(),
+        };
+
+        if let Some(webhook) = webhook {
+            if webhook.organization_id != Some(organization.id) {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["webhook not found"]).to_value()),
+                );
+            }
+
+            (
+                StatusCode::OK,
+                axum::Json(
+                    serde_json::to_value(&Response {
+                        success: true,
+                        webhook,
+                    })
+                    .unwrap(),
+                ),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                axum::Json(ApiError::new(&["webhook not found"]).to_value()),
+            )
+        }
+    }
+}
+
+mod patch {
+    use crate::{
+        models::{r#type::ServerType, webhook::Webhook},
+        routes::{ApiError, GetState, api::user::organizations::_organization_::GetOrganization},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        label: Option<String>,
+        url: Option<String>,
+        types: Option<Vec<ServerType>>,
+        enabled: Option<bool>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+    }
+
+    #[utoipa::path(patch, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "organization" = i32,
+            description = "The organization ID",
+            example = 1,
+        ),
+        (
+            "webhook" = i32,
+            description = "The webhook ID",
+            example = 1,
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        organization: GetOrganization,
+        Path((_organization, webhook)): Path<(i32, i32)>,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let mut webhook = match Webhook::by_id(&state.database, webhook).await {
+            Ok(Some(webhook)) if webhook.organization_id == Some(organization.id) => webhook,
+            Err(err) => return err.into(),
+            _ => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["webhook not found"]).to_value()),
+                );
+            }
+        };
+
+        if let Some(label) = data.label {
+            if !(1..32).contains(&label.len()) {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(
+                        ApiError::new(&["label must be between 1 and 32 characters"]).to_value(),
+                    ),
+                );
+            }
+
+            webhook.label = Some(label);
+        }
+
+        if let Some(url) = data.url {
+            if let Err(error) = Webhook::resolve_url(&url).await {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&[error]).to_value()),
+                );
+            }
+
+            webhook.url = url;
+        }
+
+        if let Some(types) = data.types {
+            if types.is_empty() {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&["at least one type is required"]).to_value()),
+                );
+            }
+
+            webhook.types = types;
+        }
+
+        if let Some(enabled) = data.enabled {
+            webhook.enabled = enabled;
+        }
+
+        if let Err(err) = webhook.save(&state.database).await {
+            return err.into();
+        }
+
+        (
+            StatusCode::OK,
+            axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+        )
+    }
+}
+
+mod delete {
+    use crate::{
+        models::webhook::Webhook,
+        routes::{ApiError, GetState, api::user::organizations::_organization_::GetOrganization},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+    }
+
+    #[utoipa::path(delete, path = "/", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "organization" = i32,
+            description = "The organization ID",
+            example = 1,
+        ),
+        (
+            "webhook" = i32,
+            description = "The webhook ID",
+            example = 1,
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        organization: GetOrganization,
+        Path((_organization, webhook)): Path<(i32, i32)>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let webhook = match Webhook::by_id(&state.database, webhook).await {
+            Ok(webhook) => webhook,
+            Err(err) => return err.into(),
+        };
+
+        if let Some(webhook) = webhook {
+            if webhook.organization_id != Some(organization.id) {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["webhook not found"]).to_value()),
+                );
+            }
+
+            if let Err(err) = Webhook::delete_by_id(&state.database, webhook.id).await {
+                return err.into();
+            }
+
+            (
+                StatusCode::OK,
+                axum::Json(serde_json::to_value(&Response { success: true }).unwrap()),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                axum::Json(ApiError::new(&["webhook not found"]).to_value()),
+            )
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(patch::route))
+        .routes(routes!(delete::route))
+        .with_state(state.clone())
+}
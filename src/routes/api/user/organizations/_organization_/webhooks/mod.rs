@@ -0,0 +1,160 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod _webhook_;
+
+mod get {
+    use crate::{
+        models::webhook::Webhook,
+        routes::{GetState, api::user::organizations::_organization_::GetOrganization},
+    };
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        webhooks: Vec<Webhook>,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = inline(Response)),
+    ), params(
+        (
+            "organization" = i32,
+            description = "The organization ID",
+            example = 1,
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        organization: GetOrganization,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let webhooks = match Webhook::all_by_organization(&state.database, organization.id).await {
+            Ok(webhooks) => webhooks,
+            Err(err) => return err.into(),
+        };
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    webhooks,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+mod post {
+    use crate::{
+        models::{r#type::ServerType, webhook::Webhook},
+        routes::{ApiError, GetState, api::user::organizations::_organization_::GetOrganization},
+    };
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        label: Option<String>,
+        url: String,
+        types: Vec<ServerType>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        webhook: Webhook,
+        secret: String,
+    }
+
+    #[utoipa::path(post, path = "/", responses(
+        (status = CREATED, body = inline(Response)),
+        (status = CONFLICT, body = inline(ApiError)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+    ), params(
+        (
+            "organization" = i32,
+            description = "The organization ID",
+            example = 1,
+        ),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        organization: GetOrganization,
+        axum::Json(payload): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        if let Some(label) = &payload.label
+            && !(1..32).contains(&label.len())
+        {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(
+                    ApiError::new(&["label must be between 1 and 32 characters"]).to_value(),
+                ),
+            );
+        }
+
+        if let Err(error) = Webhook::resolve_url(&payload.url).await {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&[error]).to_value()),
+            );
+        }
+
+        if payload.types.is_empty() {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&["at least one type is required"]).to_value()),
+            );
+        }
+
+        let count = match Webhook::count_by_organization(&state.database, organization.id).await {
+            Ok(count) => count,
+            Err(err) => return err.into(),
+        };
+        if count >= 15 {
+            return (
+                StatusCode::CONFLICT,
+                axum::Json(ApiError::new(&["you cannot have more than 15 webhooks"]).to_value()),
+            );
+        }
+
+        let (webhook, secret) = match Webhook::new(
+            &state.database,
+            organization.id,
+            payload.label.as_deref(),
+            &payload.url,
+            &payload.types,
+        )
+        .await
+        {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
+
+        (
+            StatusCode::CREATED,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    webhook,
+                    secret,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .routes(routes!(post::route))
+        .nest("/{webhook}", _webhook_::router(state))
+        .with_state(state.clone())
+}
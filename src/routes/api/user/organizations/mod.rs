@@ -8,6 +8,7 @@ mod get {
         models::organization::Organization,
         routes::{GetState, api::user::GetUser},
     };
+    use axum::http::StatusCode;
     use indexmap::IndexSet;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
@@ -30,8 +31,14 @@ mod get {
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
     ))]
-    pub async fn route(state: GetState, user: GetUser) -> axum::Json<serde_json::Value> {
-        let raw_organizations = Organization::all_by_owner(&state.database, user.id).await;
+    pub async fn route(
+        state: GetState,
+        user: GetUser,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let raw_organizations = match Organization::all_by_owner(&state.database, user.id).await {
+            Ok(raw_organizations) => raw_organizations,
+            Err(err) => return err.into(),
+        };
         let mut used_organization_ids = IndexSet::new();
         let mut organizations = Organizations {
             owned: Vec::new(),
@@ -55,12 +62,15 @@ mod get {
             }
         }
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                organizations,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    organizations,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
@@ -102,7 +112,10 @@ mod post {
             );
         }
 
-        let count = Organization::count_by_owner(&state.database, user.id).await;
+        let count = match Organization::count_by_owner(&state.database, user.id).await {
+            Ok(count) => count,
+            Err(err) => return err.into(),
+        };
         if count >= 1 {
             return (
                 StatusCode::CONFLICT,
@@ -110,7 +123,9 @@ mod post {
             );
         }
 
-        Organization::new(&state.database, user.id, &payload.name).await;
+        if let Err(err) = Organization::new(&state.database, user.id, &payload.name).await {
+            return err.into();
+        }
 
         (
             StatusCode::OK,
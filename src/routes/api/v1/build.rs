@@ -3,10 +3,16 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod get {
     use crate::{
-        models::{build::Build, version::MinifiedVersion},
+        models::{
+            build::{Build, BuildFieldsQuery},
+            version::MinifiedVersion,
+        },
         routes::{ApiError, GetData, GetState},
     };
-    use axum::{extract::Path, http::StatusCode};
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
     use serde::{Deserialize, Serialize};
     use serde_json::json;
     use utoipa::ToSchema;
@@ -21,6 +27,7 @@ mod get {
 
     #[utoipa::path(get, path = "/{build}", responses(
         (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
         (status = NOT_FOUND, body = inline(ApiError)),
     ), params(
         (
@@ -28,13 +35,30 @@ mod get {
             description = "The build number or hash to lookup",
             example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
         ),
+        (
+            "fields" = Option<String>,
+            Query,
+            description = "Comma separated list of build fields to return",
+            example = "id,buildNumber",
+        ),
     ))]
     pub async fn route(
         state: GetState,
         request_data: GetData,
         Path(identifier): Path<String>,
+        Query(BuildFieldsQuery { fields }): Query<BuildFieldsQuery>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let data = Build::by_v1_identifier(&state.database, &state.cache, &identifier).await;
+        let data = match Build::by_v1_identifier(
+            &state.database,
+            &state.cache,
+            &identifier,
+            fields.as_ref(),
+        )
+        .await
+        {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
         if let Some((build, latest, version)) = data {
             *request_data.lock().unwrap() = json!({
@@ -49,18 +73,20 @@ mod get {
                 }
             });
 
-            (
-                StatusCode::OK,
-                axum::Json(
-                    serde_json::to_value(&Response {
-                        success: true,
-                        build,
-                        latest,
-                        version,
-                    })
-                    .unwrap(),
-                ),
-            )
+            let mut response = serde_json::to_value(&Response {
+                success: true,
+                build,
+                latest,
+                version,
+            })
+            .unwrap();
+
+            if let Some(fields) = &fields {
+                fields.project(&mut response["build"]);
+                fields.project(&mut response["latest"]);
+            }
+
+            (StatusCode::OK, axum::Json(response))
         } else {
             (
                 StatusCode::NOT_FOUND,
@@ -3,10 +3,17 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod get {
     use crate::{
-        models::{build::Build, r#type::ServerType, version::Version},
+        models::{
+            build::{Build, BuildFieldsQuery},
+            r#type::ServerType,
+            version::Version,
+        },
         routes::{ApiError, GetState},
     };
-    use axum::{extract::Path, http::StatusCode};
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
 
@@ -35,11 +42,18 @@ mod get {
             "build" = String,
             description = "The build number or latest",
             example = "latest",
-        )
+        ),
+        (
+            "fields" = Option<String>,
+            Query,
+            description = "Comma separated list of build fields to return",
+            example = "id,buildNumber",
+        ),
     ))]
     pub async fn route(
         state: GetState,
         Path((r#type, version, build)): Path<(ServerType, String, String)>,
+        Query(BuildFieldsQuery { fields }): Query<BuildFieldsQuery>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
         let build: Option<i32> = if build == "latest" {
             None
@@ -64,34 +78,60 @@ mod get {
             }
         };
 
-        let location = Version::location(&state.database, &state.cache, r#type, &version).await;
+        let location =
+            match Version::location(&state.database, &state.cache, r#type, &version).await {
+                Ok(location) => location,
+                Err(err) => return err.into(),
+            };
 
         if let Some(location) = location {
-            let data = state
+            let database = state.database.clone();
+            let build_fields = fields.clone();
+
+            let data = match state
                 .cache
                 .cached(
                     &format!(
-                        "build::{}::{}::{}",
+                        "build::{}::{}::{}{}",
                         r#type,
                         version,
-                        build.map(|b| b.to_string()).unwrap_or("latest".to_string())
+                        build.map(|b| b.to_string()).unwrap_or("latest".to_string()),
+                        fields
+                            .as_ref()
+                            .map(|fields| format!("::{}", fields.key()))
+                            .unwrap_or_default()
                     ),
                     3600,
-                    || Build::by_build_number(&state.database, r#type, &location, &version, build),
+                    move || async move {
+                        Build::by_build_number(
+                            &database,
+                            r#type,
+                            &location,
+                            &version,
+                            build,
+                            build_fields.as_ref(),
+                        )
+                        .await
+                    },
                 )
-                .await;
+                .await
+            {
+                Ok(data) => data,
+                Err(err) => return err.into(),
+            };
 
             if let Some(data) = data {
-                (
-                    StatusCode::OK,
-                    axum::Json(
-                        serde_json::to_value(&Response {
-                            success: true,
-                            build: data,
-                        })
-                        .unwrap(),
-                    ),
-                )
+                let mut response = serde_json::to_value(&Response {
+                    success: true,
+                    build: data,
+                })
+                .unwrap();
+
+                if let Some(fields) = &fields {
+                    fields.project(&mut response["build"]);
+                }
+
+                (StatusCode::OK, axum::Json(response))
             } else {
                 (
                     StatusCode::NOT_FOUND,
@@ -5,10 +5,17 @@ mod _build_;
 
 mod get {
     use crate::{
-        models::{build::Build, r#type::ServerType, version::Version},
+        models::{
+            build::{Build, BuildFieldsQuery, BuildFilter},
+            r#type::ServerType,
+            version::Version,
+        },
         routes::{ApiError, GetData, GetState},
     };
-    use axum::{extract::Path, http::StatusCode};
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
     use serde::{Deserialize, Serialize};
     use serde_json::json;
     use utoipa::ToSchema;
@@ -21,6 +28,7 @@ mod get {
 
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
         (status = NOT_FOUND, body = inline(ApiError)),
     ), params(
         (
@@ -32,22 +40,61 @@ mod get {
             "version" = String,
             description = "The server version",
             example = "1.17.1",
-        )
+        ),
+        (
+            "fields" = Option<String>,
+            Query,
+            description = "Comma separated list of build fields to return",
+            example = "id,buildNumber",
+        ),
     ))]
     pub async fn route(
         state: GetState,
         request_data: GetData,
         Path((r#type, version)): Path<(ServerType, String)>,
+        Query(BuildFieldsQuery { fields }): Query<BuildFieldsQuery>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let location = Version::location(&state.database, &state.cache, r#type, &version).await;
+        let location =
+            match Version::location(&state.database, &state.cache, r#type, &version).await {
+                Ok(location) => location,
+                Err(err) => return err.into(),
+            };
 
         if let Some(location) = location {
-            let data = state
-                .cache
-                .cached(&format!("builds::{}::{}", r#type, version), 1800, || {
-                    Build::all_for_version(&state.database, r#type, &location, &version)
-                })
-                .await;
+            let data = if let Some(fields) = &fields {
+                Build::page_for_version(
+                    &state.database,
+                    r#type,
+                    &location,
+                    &version,
+                    &BuildFilter {
+                        cursor: None,
+                        limit: None,
+                        experimental: None,
+                        since: None,
+                    },
+                    Some(fields),
+                )
+                .await
+            } else {
+                let database = state.database.clone();
+                let version = version.clone();
+
+                state
+                    .cache
+                    .cached(
+                        &format!("builds::{}::{}", r#type, version),
+                        1800,
+                        move || async move {
+                            Build::all_for_version(&database, r#type, &location, &version).await
+                        },
+                    )
+                    .await
+            };
+            let data = match data {
+                Ok(data) => data,
+                Err(err) => return err.into(),
+            };
 
             *request_data.lock().unwrap() = json!({
                 "type": "builds",
@@ -57,16 +104,19 @@ mod get {
                 }
             });
 
-            (
-                StatusCode::OK,
-                axum::Json(
-                    serde_json::to_value(&Response {
-                        success: true,
-                        builds: data,
-                    })
-                    .unwrap(),
-                ),
-            )
+            let mut response = serde_json::to_value(&Response {
+                success: true,
+                builds: data,
+            })
+            .unwrap();
+
+            if let Some(fields) = &fields {
+                for build in response["builds"].as_array_mut().unwrap() {
+                    fields.project(build);
+                }
+            }
+
+            (StatusCode::OK, axum::Json(response))
         } else {
             (
                 StatusCode::NOT_FOUND,
@@ -9,6 +9,7 @@ mod get {
         routes::{GetData, GetState},
     };
     use axum::extract::Path;
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use serde_json::json;
@@ -33,8 +34,11 @@ mod get {
         state: GetState,
         request_data: GetData,
         Path(r#type): Path<ServerType>,
-    ) -> axum::Json<serde_json::Value> {
-        let data = Version::all(&state.database, &state.cache, r#type).await;
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let data = match Version::all(&state.database, &state.cache, r#type).await {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
         *request_data.lock().unwrap() = json!({
             "type": "builds",
@@ -43,12 +47,15 @@ mod get {
             }
         });
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                versions: data,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    versions: data,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
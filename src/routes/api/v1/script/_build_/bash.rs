@@ -1,11 +1,64 @@
-use super::State;
+use super::{
+    State,
+    plan::{Command, Dialect},
+    sh::{Sh, quote},
+};
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+pub struct Bash;
+
+impl Dialect for Bash {
+    fn shebang(&self) -> Option<&'static str> {
+        Some("#!/bin/bash")
+    }
+
+    fn header(&self, java: i16) -> Vec<String> {
+        vec![
+            format!("export JAVA_VERSION={}", java),
+            "".to_string(),
+            r#"fail() { echo "$1" >&2; exit 1; }"#.to_string(),
+            "pids=()".to_string(),
+        ]
+    }
+
+    fn echo(&self, message: &str) -> String {
+        Sh.echo(message)
+    }
+
+    fn command(&self, command: &Command) -> String {
+        match command {
+            Command::Download { url, file } => {
+                format!("curl -fsSL -o {} {}", quote(file), quote(url))
+            }
+            command => Sh.command(command),
+        }
+    }
+
+    fn run(&self, command: &Command, failure: &str) -> String {
+        format!("{} || fail {}", self.command(command), quote(failure))
+    }
+
+    fn exit(&self, code: i32) -> String {
+        Sh.exit(code)
+    }
+
+    fn background(&self, command: &Command, failure: &str) -> Option<String> {
+        Some(format!(
+            "{{ {}; }} & pids+=($!)",
+            self.run(command, failure)
+        ))
+    }
+
+    fn wait(&self) -> Vec<String> {
+        vec![
+            r#"for pid in "${pids[@]}"; do wait "$pid" || exit 1; done"#.to_string(),
+            "pids=()".to_string(),
+        ]
+    }
+}
+
 mod get {
-    use crate::{
-        models::build::{Build, InstallationStep},
-        routes::GetState,
-    };
+    use crate::routes::GetState;
     use axum::{
         extract::{Path, Query},
         http::StatusCode,
@@ -17,10 +70,19 @@ mod get {
     pub struct Params {
         #[serde(default)]
         echo: bool,
+
+        directory: Option<String>,
+        java: Option<String>,
+        memory: Option<u32>,
+        #[serde(default)]
+        eula: bool,
+        #[serde(default)]
+        start: bool,
     }
 
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = String),
+        (status = BAD_REQUEST, body = String),
         (status = NOT_FOUND, body = String),
     ), params(
         (
@@ -34,113 +96,80 @@ mod get {
             description = "Whether to echo inside the script",
             example = "true",
         ),
+        (
+            "directory" = Option<String>,
+            Query,
+            description = "The directory to install the server into",
+            example = "server",
+        ),
+        (
+            "java" = Option<String>,
+            Query,
+            description = "The java binary to run the server with, defaults to JAVA_HOME or java",
+            example = "/usr/lib/jvm/java-21-openjdk/bin/java",
+        ),
+        (
+            "memory" = Option<u32>,
+            Query,
+            description = "The memory to give the server in megabytes",
+            example = "4096",
+        ),
+        (
+            "eula" = bool,
+            Query,
+            description = "Whether to accept the Minecraft EULA",
+            example = "true",
+        ),
+        (
+            "start" = bool,
+            Query,
+            description = "Whether to write a start script",
+            example = "true",
+        ),
     ))]
     pub async fn route(
         state: GetState,
         Path(identifier): Path<String>,
         Query(query): Query<Params>,
     ) -> (StatusCode, String) {
-        let data = Build::by_v1_identifier(&state.database, &state.cache, &identifier).await;
+        let options = super::super::plan::Options {
+            directory: query.directory,
+            java: query.java,
+            memory: query.memory,
+            eula: query.eula,
+            start: query.start,
+        };
 
-        if let Some((build, _, version)) = data {
-            let mut script = format!(
-                r#"
-#!/bin/bash
-export JAVA_VERSION={}
+        if let Err(error) = options.validate() {
+            return (
+                StatusCode::BAD_REQUEST,
+                super::super::plan::fail(&super::Bash, &error),
+            );
+        }
 
-echo "Installing Server"
-                "#,
-                version.java
-            )
-            .trim()
-            .to_string();
-
-            for combined in build.installation {
-                let mut steps = vec![];
-
-                for step in combined {
-                    match step {
-                        InstallationStep::Remove(step) => {
-                            steps.push(format!(
-                                r#"
-echo "Removing {}"
-rm -rf {}
-                                "#,
-                                step.location, step.location
-                            ));
-                        }
-                        InstallationStep::Download(step) => {
-                            steps.push(format!(
-                                r#"
-echo "Downloading {}"
-mkdir -p ./{}
-rm -f {}
-curl -s -o {} '{}'&
-                                "#,
-                                step.file,
-                                std::path::Path::new(&step.file)
-                                    .parent()
-                                    .unwrap()
-                                    .to_str()
-                                    .unwrap(),
-                                step.file,
-                                step.file,
-                                step.url
-                            ));
-                        }
-                        InstallationStep::Unzip(step) => {
-                            steps.push(format!(
-                                r#"
-echo "Unzipping {}"
-mkdir -p {}
-unzip -o {} -d {}&
-                                "#,
-                                step.file, step.location, step.file, step.location
-                            ));
-                        }
-                    }
-                }
-
-                steps.push("wait".to_string());
-
-                script.push_str(
-                    &steps
-                        .iter()
-                        .map(|s| s.trim_end())
-                        .collect::<Vec<&str>>()
-                        .join("\n"),
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (
+                    err.status(),
+                    super::super::plan::fail(&super::Bash, err.message()),
                 );
             }
+        };
 
-            script.push_str(
-                r#"
-
-echo "Installation complete"
-echo "Use Java version: $JAVA_VERSION"
-exit 0
-                "#
-                .trim_end(),
-            );
+        if let Some((build, version)) = data {
+            let plan = super::super::plan::Plan::new(&build, version.java, options);
 
             (
                 StatusCode::OK,
-                script
-                    .lines()
-                    .filter(|l| !l.starts_with("echo") || query.echo)
-                    .collect::<Vec<&str>>()
-                    .join("\n"),
+                super::super::plan::render(&plan, &super::Bash, query.echo),
             )
         } else {
             (
                 StatusCode::NOT_FOUND,
-                r#"
-#!/bin/bash
-
-echo "Build not found"
-exit 1
-                "#
-                .trim()
-                .to_string(),
+                super::super::plan::fail(&super::Bash, "Build not found"),
             )
         }
     }
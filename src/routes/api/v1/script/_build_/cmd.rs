@@ -0,0 +1,214 @@
+use super::{
+    State,
+    plan::{Command, Dialect},
+};
+use base64::Engine;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+fn path(value: &str) -> String {
+    format!("\"{}\"", value.replace('/', "\\").replace('%', "%%"))
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('%', "%%"))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '^' | '&' | '<' | '>' | '|' | '(' | ')' => format!("^{}", c),
+            '%' => "%%".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+pub struct Cmd;
+
+impl Dialect for Cmd {
+    fn header(&self, java: i16) -> Vec<String> {
+        vec![
+            "@echo off".to_string(),
+            "setlocal".to_string(),
+            format!("set JAVA_VERSION={}", java),
+        ]
+    }
+
+    fn echo(&self, message: &str) -> String {
+        format!("echo {}", escape(message))
+    }
+
+    fn command(&self, command: &Command) -> String {
+        match command {
+            Command::Enter(location) => format!("cd /d {}", path(location)),
+            Command::MakeDirectory(location) => {
+                format!("if not exist {} mkdir {}", path(location), path(location))
+            }
+            Command::Remove(location) => format!(
+                "if exist {} (rmdir /s /q {}) else if exist {} (del /f /q {})",
+                path(&format!("{}/*", location)),
+                path(location),
+                path(location),
+                path(location)
+            ),
+            Command::Download { url, file } => {
+                format!("curl.exe -fsSL -o {} {}", path(file), quote(url))
+            }
+            Command::Verify { file, sha256 } => format!(
+                "certutil -hashfile {} SHA256 | findstr /i /x {} >nul",
+                path(file),
+                quote(sha256)
+            ),
+            Command::Unzip { file, location } => {
+                format!("tar -xf {} -C {}", path(file), path(location))
+            }
+            Command::Extract { file, location } => {
+                format!("tar -xzf {} -C {}", path(file), path(location))
+            }
+            Command::Move { from, to } => format!("move /y {} {} >nul", path(from), path(to)),
+            Command::Write { file, content } => {
+                let encoded = path(&format!("{}.b64", file));
+
+                std::iter::once(format!("type nul > {}", encoded))
+                    .chain(
+                        base64::engine::general_purpose::STANDARD
+                            .encode(content)
+                            .as_bytes()
+                            .chunks(4096)
+                            .map(|chunk| {
+                                format!(">> {} echo {}", encoded, String::from_utf8_lossy(chunk))
+                            }),
+                    )
+                    .chain(std::iter::once(format!(
+                        "certutil -f -decode {} {} >nul && del {}",
+                        encoded,
+                        path(file),
+                        encoded
+                    )))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            }
+            Command::Chmod { location, mode } => format!("rem chmod {} {}", mode, path(location)),
+            Command::RunJava {
+                java,
+                file,
+                arguments,
+            } => std::iter::once(format!("{} -jar {}", path(java), path(file)))
+                .chain(arguments.iter().map(|argument| quote(argument)))
+                .collect::<Vec<String>>()
+                .join(" "),
+            Command::Start {
+                java, memory, jar, ..
+            } => self.command(&Command::Write {
+                file: "start.bat".to_string(),
+                content: [
+                    "@echo off".to_string(),
+                    "cd /d \"%~dp0\"".to_string(),
+                    match java {
+                        Some(java) => format!("set \"JAVA={}\"", java.replace('/', "\\")),
+                        None => "set \"JAVA=java\"\r\nif defined JAVA_HOME set \"JAVA=%JAVA_HOME%\\bin\\java\""
+                            .to_string(),
+                    },
+                    format!(
+                        "\"%JAVA%\"{} -jar \"{}\" nogui %*",
+                        memory
+                            .map(|memory| format!(" -Xms{}M -Xmx{}M", memory, memory))
+                            .unwrap_or_default(),
+                        jar.replace('/', "\\")
+                    ),
+                ]
+                .join("\r\n")
+                    + "\r\n",
+            }),
+        }
+    }
+
+    fn run(&self, command: &Command, failure: &str) -> String {
+        match command {
+            Command::MakeDirectory(_) | Command::Remove(_) | Command::Chmod { .. } => {
+                self.command(command)
+            }
+            command => self
+                .command(command)
+                .lines()
+                .map(|line| format!("{} || (echo {} 1>&2 & exit /b 1)", line, escape(failure)))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }
+    }
+
+    fn exit(&self, code: i32) -> String {
+        format!("exit /b {}", code)
+    }
+}
+
+mod get {
+    use crate::routes::GetState;
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        echo: bool,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = NOT_FOUND, body = String),
+    ), params(
+        (
+            "build",
+            description = "The build number or hash to lookup",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+        (
+            "echo",
+            Query,
+            description = "Whether to echo inside the script",
+            example = "true",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        Path(identifier): Path<String>,
+        Query(query): Query<Params>,
+    ) -> (StatusCode, String) {
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (
+                    err.status(),
+                    super::super::plan::fail(&super::Cmd, err.message()),
+                );
+            }
+        };
+
+        if let Some((build, version)) = data {
+            let plan = super::super::plan::Plan::new(&build, version.java, Default::default());
+
+            (
+                StatusCode::OK,
+                super::super::plan::render(&plan, &super::Cmd, query.echo),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                super::super::plan::fail(&super::Cmd, "Build not found"),
+            )
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,69 @@
+use super::State;
+use crate::models::build::Build;
+use serde_json::json;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub fn render(build: &Build, java: i16) -> String {
+    let compose = json!({
+        "services": {
+            "server": {
+                "build": {
+                    "context": ".",
+                    "dockerfile_inline": super::dockerfile::render(build, java),
+                    "args": {
+                        "EULA": "false",
+                    },
+                },
+                "restart": "unless-stopped",
+                "stdin_open": true,
+                "tty": true,
+                "ports": ["25565:25565"],
+                "volumes": [
+                    "./world:/server/world",
+                    "./plugins:/server/plugins",
+                    "./mods:/server/mods",
+                ],
+            },
+        },
+    });
+
+    serde_yaml::to_string(&compose).unwrap()
+}
+
+mod get {
+    use crate::routes::GetState;
+    use axum::{extract::Path, http::StatusCode};
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = NOT_FOUND, body = String),
+    ), params(
+        (
+            "build",
+            description = "The build number or hash to lookup",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+    ))]
+    pub async fn route(state: GetState, Path(identifier): Path<String>) -> (StatusCode, String) {
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (err.status(), format!("# {}", err.message()));
+            }
+        };
+
+        if let Some((build, version)) = data {
+            (StatusCode::OK, super::render(&build, version.java))
+        } else {
+            (StatusCode::NOT_FOUND, "# Build not found".to_string())
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,78 @@
+use super::State;
+use crate::models::build::Build;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub fn render(build: &Build, java: i16) -> String {
+    let script = super::plan::render(
+        &super::plan::Plan::new(build, java, Default::default()),
+        &super::bash::Bash,
+        false,
+    );
+
+    format!(
+        r#"
+# syntax=docker/dockerfile:1
+FROM eclipse-temurin:{}-jre
+
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends curl unzip \
+    && rm -rf /var/lib/apt/lists/*
+
+WORKDIR /server
+
+RUN <<'EOF'
+{}
+EOF
+
+ARG EULA=false
+RUN echo "eula=${{EULA}}" > eula.txt
+
+EXPOSE 25565
+
+CMD ["java", "-jar", "{}"]
+        "#,
+        super::java_version(java),
+        script,
+        super::server_jar(build)
+    )
+    .trim()
+    .to_string()
+}
+
+mod get {
+    use crate::routes::GetState;
+    use axum::{extract::Path, http::StatusCode};
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = NOT_FOUND, body = String),
+    ), params(
+        (
+            "build",
+            description = "The build number or hash to lookup",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+    ))]
+    pub async fn route(state: GetState, Path(identifier): Path<String>) -> (StatusCode, String) {
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (err.status(), format!("# {}", err.message()));
+            }
+        };
+
+        if let Some((build, version)) = data {
+            (StatusCode::OK, super::render(&build, version.java))
+        } else {
+            (StatusCode::NOT_FOUND, "# Build not found".to_string())
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -0,0 +1,107 @@
+use super::State;
+use crate::models::{build::Build, version::MinifiedVersion};
+use serde_json::json;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub fn render(build: &Build, version: &MinifiedVersion, app_url: &str) -> serde_json::Value {
+    let java = super::java_version(version.java);
+    let plan = super::plan::Plan::new(build, version.java, Default::default());
+    let script = super::plan::render(&plan, &super::bash::Bash, true);
+    let container = if plan.runs_java() {
+        format!("eclipse-temurin:{}-jdk", java)
+    } else {
+        "ghcr.io/pterodactyl/installers:debian".to_string()
+    };
+
+    json!({
+        "_comment": "DO NOT EDIT: FILE GENERATED AUTOMATICALLY BY PTERODACTYL PANEL - PTERODACTYL.IO",
+        "meta": {
+            "version": "PTDL_v2",
+            "update_url": format!("{}/api/v1/script/{}/egg", app_url, build.id),
+        },
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "name": format!("{} {} {}", build.r#type, version.id, build.name),
+        "author": "support@mcjars.app",
+        "description": format!(
+            "{} {} {}, generated by MCJars from the build installation steps.",
+            build.r#type, version.id, build.name
+        ),
+        "features": ["eula", "java_version", "pid_limit"],
+        "docker_images": {
+            format!("Java {}", java): format!("ghcr.io/pterodactyl/yolks:java_{}", java),
+        },
+        "file_denylist": [],
+        "startup": "java -Xms128M -XX:MaxRAMPercentage=95.0 -jar {{SERVER_JARFILE}}",
+        "config": {
+            "files": "{}",
+            "startup": "{\r\n    \"done\": \")! For help, type \"\r\n}",
+            "logs": "{}",
+            "stop": "stop",
+        },
+        "scripts": {
+            "installation": {
+                "script": format!(
+                    "#!/bin/bash\napt update\napt install -y curl unzip\n\nmkdir -p /mnt/server\ncd /mnt/server\n\n{}",
+                    script.trim_start_matches("#!/bin/bash").trim_start()
+                ),
+                "container": container,
+                "entrypoint": "bash",
+            },
+        },
+        "variables": [
+            {
+                "name": "Server Jar File",
+                "description": "The name of the server jarfile to run the server with.",
+                "env_variable": "SERVER_JARFILE",
+                "default_value": super::server_jar(build),
+                "user_viewable": true,
+                "user_editable": true,
+                "rules": "required|regex:/^([\\w\\d._-]+)(\\.jar)$/",
+                "field_type": "text",
+            },
+        ],
+    })
+}
+
+mod get {
+    use crate::routes::{ApiError, GetState};
+    use axum::{extract::Path, http::StatusCode};
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = serde_json::Value),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "build",
+            description = "The build number or hash to lookup",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        Path(identifier): Path<String>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
+
+        if let Some((build, version)) = data {
+            (
+                StatusCode::OK,
+                axum::Json(super::render(&build, &version, &state.env.app_url)),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                axum::Json(ApiError::new(&["build not found"]).to_value()),
+            )
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
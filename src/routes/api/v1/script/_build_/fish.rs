@@ -0,0 +1,122 @@
+use super::{
+    State,
+    plan::{Command, Dialect},
+    sh::Sh,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+pub struct Fish;
+
+impl Dialect for Fish {
+    fn shebang(&self) -> Option<&'static str> {
+        Some("#!/usr/bin/env fish")
+    }
+
+    fn header(&self, java: i16) -> Vec<String> {
+        vec![
+            format!("set -gx JAVA_VERSION {}", java),
+            "".to_string(),
+            "function fail; echo $argv[1] >&2; exit 1; end".to_string(),
+        ]
+    }
+
+    fn echo(&self, message: &str) -> String {
+        format!("echo {}", quote(message))
+    }
+
+    fn command(&self, command: &Command) -> String {
+        match command {
+            Command::Download { url, file } => {
+                format!("curl -fsSL -o {} {}", quote(file), quote(url))
+            }
+            Command::Verify { file, sha256 } => format!(
+                "test (sha256sum {} | string split -f 1 ' ') = {}",
+                quote(file),
+                quote(sha256)
+            ),
+            command => Sh.command(command),
+        }
+    }
+
+    fn run(&self, command: &Command, failure: &str) -> String {
+        format!("{}; or fail {}", self.command(command), quote(failure))
+    }
+
+    fn exit(&self, code: i32) -> String {
+        format!("exit {}", code)
+    }
+}
+
+mod get {
+    use crate::routes::GetState;
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        echo: bool,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = NOT_FOUND, body = String),
+    ), params(
+        (
+            "build",
+            description = "The build number or hash to lookup",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+        (
+            "echo",
+            Query,
+            description = "Whether to echo inside the script",
+            example = "true",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        Path(identifier): Path<String>,
+        Query(query): Query<Params>,
+    ) -> (StatusCode, String) {
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (
+                    err.status(),
+                    super::super::plan::fail(&super::Fish, err.message()),
+                );
+            }
+        };
+
+        if let Some((build, version)) = data {
+            let plan = super::super::plan::Plan::new(&build, version.java, Default::default());
+
+            (
+                StatusCode::OK,
+                super::super::plan::render(&plan, &super::Fish, query.echo),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                super::super::plan::fail(&super::Fish, "Build not found"),
+            )
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
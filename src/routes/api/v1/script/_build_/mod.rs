@@ -1,12 +1,444 @@
 use super::State;
+use crate::{
+    database::DatabaseError,
+    models::{
+        build::{Build, BuildHash, InstallationStep},
+        version::MinifiedVersion,
+    },
+};
+use std::collections::HashSet;
 use utoipa_axum::router::OpenApiRouter;
 
 mod bash;
+mod cmd;
+mod compose;
+mod dockerfile;
+mod egg;
+mod fish;
+mod plan;
 mod powershell;
+mod sh;
+
+pub async fn lookup(
+    state: &crate::routes::AppState,
+    identifier: &str,
+) -> Result<Option<(Build, MinifiedVersion)>, DatabaseError> {
+    let (mut build, _, version) =
+        match Build::by_v1_identifier(&state.database, &state.cache, identifier, None).await? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+
+    if let Some(hash) = BuildHash::primary_by_build(&state.database, &state.cache, build.id).await?
+    {
+        build.fill_primary_hash(&hash);
+    }
+
+    Ok(Some((build, version)))
+}
+
+pub fn java_version(java: i16) -> i16 {
+    [8, 11, 17, 21, 25]
+        .into_iter()
+        .find(|version| *version >= java)
+        .unwrap_or(25)
+}
+
+pub fn server_jar(build: &Build) -> String {
+    let installers: HashSet<&str> = build
+        .installation
+        .iter()
+        .flatten()
+        .filter_map(|step| match step {
+            InstallationStep::RunJava(step) => Some(step.file.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let mut jar: Option<&str> = None;
+    let mut primary = false;
+
+    for step in build.installation.iter().flatten() {
+        match step {
+            InstallationStep::Download(step)
+                if step.file.ends_with(".jar") && !installers.contains(step.file.as_str()) =>
+            {
+                let matches = build.jar_url.as_deref() == Some(step.url.as_str());
+
+                if jar.is_none() || (matches && !primary) {
+                    jar = Some(&step.file);
+                    primary = matches;
+                }
+            }
+            InstallationStep::Move(step)
+                if step.to.ends_with(".jar")
+                    && !installers.contains(step.to.as_str())
+                    && (jar.is_none() || jar == Some(step.from.as_str())) =>
+            {
+                jar = Some(&step.to);
+            }
+            InstallationStep::Remove(step) if jar == Some(step.location.as_str()) => {
+                jar = None;
+                primary = false;
+            }
+            _ => {}
+        }
+    }
+
+    jar.unwrap_or("server.jar").to_string()
+}
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .nest("/bash", bash::router(state))
         .nest("/powershell", powershell::router(state))
+        .nest("/sh", sh::router(state))
+        .nest("/fish", fish::router(state))
+        .nest("/cmd", cmd::router(state))
+        .nest("/egg", egg::router(state))
+        .nest("/dockerfile", dockerfile::router(state))
+        .nest("/compose", compose::router(state))
         .with_state(state.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        bash::Bash,
+        cmd::Cmd,
+        fish::Fish,
+        plan::{self, Command, Options, Plan},
+        powershell::PowerShell,
+        sh::{self, Sh},
+    };
+    use crate::models::{
+        build::{
+            Build, InstallationStep, InstallationStepDownload, InstallationStepMove,
+            InstallationStepRemove, InstallationStepRunJava,
+        },
+        r#type::ServerType,
+        version::{MinifiedVersion, VersionType},
+    };
+    use base64::Engine;
+
+    const SHA256: &str = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951";
+
+    fn download(url: &str, file: &str) -> InstallationStep {
+        InstallationStep::Download(InstallationStepDownload {
+            url: url.to_string(),
+            file: file.to_string(),
+            size: 1,
+            sha256: Some(SHA256.to_string()),
+        })
+    }
+
+    fn build(
+        r#type: ServerType,
+        jar_url: Option<&str>,
+        installation: Vec<Vec<InstallationStep>>,
+    ) -> Build {
+        Build {
+            id: 1,
+            version_id: Some("1.20.1".to_string()),
+            project_version_id: None,
+            r#type,
+            experimental: false,
+            name: "#1".to_string(),
+            build_number: 1,
+            jar_url: jar_url.map(str::to_string),
+            jar_size: None,
+            zip_url: None,
+            zip_size: None,
+            installation,
+            changes: Vec::new(),
+            created: None,
+        }
+    }
+
+    fn paper() -> Build {
+        build(
+            ServerType::Paper,
+            Some("https://example.com/paper.jar"),
+            vec![vec![
+                download("https://example.com/library.jar", "libraries/library.jar"),
+                download("https://example.com/paper.jar", "server.jar"),
+            ]],
+        )
+    }
+
+    fn forge() -> Build {
+        build(
+            ServerType::Forge,
+            Some("https://example.com/forge-installer.jar"),
+            vec![
+                vec![download(
+                    "https://example.com/forge-installer.jar",
+                    "installer.jar",
+                )],
+                vec![InstallationStep::RunJava(InstallationStepRunJava {
+                    file: "installer.jar".to_string(),
+                    arguments: vec!["--installServer".to_string()],
+                })],
+                vec![
+                    InstallationStep::Remove(InstallationStepRemove {
+                        location: "installer.jar".to_string(),
+                    }),
+                    InstallationStep::Move(InstallationStepMove {
+                        from: "forge-1.20.1-47.3.0.jar".to_string(),
+                        to: "server.jar".to_string(),
+                    }),
+                ],
+            ],
+        )
+    }
+
+    fn version() -> MinifiedVersion {
+        MinifiedVersion {
+            id: "1.20.1".to_string(),
+            r#type: VersionType::Release,
+            supported: true,
+            java: 17,
+            builds: 1,
+            created: chrono::NaiveDateTime::default(),
+        }
+    }
+
+    fn options() -> Options {
+        Options {
+            directory: Some("server".to_string()),
+            eula: true,
+            start: true,
+            ..Default::default()
+        }
+    }
+
+    fn start_jar(plan: &Plan) -> Option<&str> {
+        plan.groups
+            .iter()
+            .flat_map(|group| &group.steps)
+            .flat_map(|step| &step.commands)
+            .find_map(|command| match command {
+                Command::Start { jar, .. } => Some(jar.as_str()),
+                _ => None,
+            })
+    }
+
+    fn encoded(content: &str) -> String {
+        base64::engine::general_purpose::STANDARD.encode(content)
+    }
+
+    #[test]
+    fn server_jar_prefers_the_jar_url_download() {
+        assert_eq!(super::server_jar(&paper()), "server.jar");
+    }
+
+    #[test]
+    fn server_jar_skips_installers() {
+        assert_eq!(super::server_jar(&forge()), "server.jar");
+
+        let build = build(
+            ServerType::Neoforge,
+            Some("https://example.com/neoforge-installer.jar"),
+            vec![
+                vec![download(
+                    "https://example.com/neoforge-installer.jar",
+                    "neoforge-installer.jar",
+                )],
+                vec![InstallationStep::RunJava(InstallationStepRunJava {
+                    file: "neoforge-installer.jar".to_string(),
+                    arguments: vec!["--installServer".to_string()],
+                })],
+            ],
+        );
+        assert_eq!(super::server_jar(&build), "server.jar");
+    }
+
+    #[test]
+    fn server_jar_follows_moves() {
+        let build = build(
+            ServerType::Vanilla,
+            None,
+            vec![vec![
+                download("https://example.com/minecraft_server.jar", "minecraft.jar"),
+                InstallationStep::Move(InstallationStepMove {
+                    from: "minecraft.jar".to_string(),
+                    to: "vanilla.jar".to_string(),
+                }),
+            ]],
+        );
+
+        assert_eq!(super::server_jar(&build), "vanilla.jar");
+    }
+
+    #[test]
+    fn plan_starts_the_server_jar() {
+        let plan = Plan::new(&forge(), 17, options());
+
+        assert!(plan.runs_java());
+        assert_eq!(start_jar(&plan), Some("server.jar"));
+    }
+
+    #[test]
+    fn bash_renders_downloads_checks_and_start_script() {
+        let script = plan::render(&Plan::new(&paper(), 17, options()), &Bash, true);
+
+        assert!(script.starts_with("#!/bin/bash\nexport JAVA_VERSION=17"));
+        assert!(script.contains("mkdir -p 'server'"));
+        assert!(script.contains(
+            "{ curl -fsSL -o 'server.jar' 'https://example.com/paper.jar' || fail 'Downloading server.jar failed'; } & pids+=($!)"
+        ));
+        assert!(script.contains(&format!(
+            "[ \"$(sha256sum 'server.jar' | cut -d ' ' -f 1)\" = '{}' ] || fail 'Checksum mismatch for server.jar'",
+            SHA256
+        )));
+        assert!(script.contains(&encoded(&sh::start_script(None, 17, None, "server.jar"))));
+        assert!(script.ends_with("echo 'Use Java version: 17'\nexit 0"));
+    }
+
+    #[test]
+    fn sh_renders_installers_and_start_script() {
+        let script = plan::render(&Plan::new(&forge(), 17, options()), &Sh, false);
+
+        assert!(script.starts_with("#!/bin/sh\nexport JAVA_VERSION=17"));
+        assert!(
+            script.contains("download 'installer.jar' 'https://example.com/forge-installer.jar'")
+        );
+        assert!(script.contains(
+            "'java' -jar 'installer.jar' '--installServer' || fail 'Running installer.jar failed'"
+        ));
+        assert!(script.contains("mv -f 'forge-1.20.1-47.3.0.jar' 'server.jar'"));
+        assert!(script.contains(&encoded("eula=true\n")));
+        assert!(script.contains(&encoded(&sh::start_script(None, 17, None, "server.jar"))));
+        assert!(!script.contains(&encoded(&sh::start_script(None, 17, None, "installer.jar"))));
+        assert!(script.ends_with("exit 0"));
+    }
+
+    #[test]
+    fn fish_renders_downloads_and_failures() {
+        let script = plan::render(&Plan::new(&forge(), 17, options()), &Fish, false);
+
+        assert!(script.starts_with("#!/usr/bin/env fish\nset -gx JAVA_VERSION 17"));
+        assert!(
+            script.contains(
+                "curl -fsSL -o 'installer.jar' 'https://example.com/forge-installer.jar'"
+            )
+        );
+        assert!(script.contains(&format!(
+            "test (sha256sum 'installer.jar' | string split -f 1 ' ') = '{}'; or fail 'Checksum mismatch for installer.jar'",
+            SHA256
+        )));
+        assert!(script.contains("; or fail 'Running installer.jar failed'"));
+        assert!(script.contains(&encoded(&sh::start_script(None, 17, None, "server.jar"))));
+    }
+
+    #[test]
+    fn cmd_renders_windows_paths_and_start_script() {
+        let script = plan::render(&Plan::new(&forge(), 17, options()), &Cmd, false);
+
+        assert!(script.contains("\"java\" -jar \"installer.jar\" \"--installServer\""));
+        assert!(script.contains(&format!(
+            "certutil -hashfile \"installer.jar\" SHA256 | findstr /i /x \"{}\" >nul",
+            SHA256
+        )));
+        assert!(script.contains("move /y \"forge-1.20.1-47.3.0.jar\" \"server.jar\" >nul"));
+        assert!(script.contains(&encoded(
+            "@echo off\r\ncd /d \"%~dp0\"\r\nset \"JAVA=java\"\r\nif defined JAVA_HOME set \"JAVA=%JAVA_HOME%\\bin\\java\"\r\n\"%JAVA%\" -jar \"server.jar\" nogui %*\r\n"
+        )));
+        assert!(script.ends_with("exit /b 0"));
+    }
+
+    #[test]
+    fn cmd_checks_every_line_of_a_write() {
+        let script = plan::Dialect::run(
+            &Cmd,
+            &Command::Write {
+                file: "config/server.properties".to_string(),
+                content: "motd=A Minecraft Server\n".repeat(1024),
+            },
+            "Writing failed",
+        );
+        let lines = script.lines().collect::<Vec<&str>>();
+
+        assert!(lines.len() > 3);
+        assert!(
+            lines
+                .iter()
+                .all(|line| line.ends_with(" || (echo Writing failed 1>&2 & exit /b 1)"))
+        );
+    }
+
+    #[test]
+    fn powershell_renders_downloads_and_java_checks() {
+        let script = plan::render(&Plan::new(&forge(), 17, options()), &PowerShell, false);
+
+        assert!(script.starts_with("$ErrorActionPreference = 'Stop'"));
+        assert!(script.contains(
+            "Invoke-WebRequest -UseBasicParsing -Uri 'https://example.com/forge-installer.jar' -OutFile 'installer.jar'"
+        ));
+        assert!(script.contains(
+            "& 'java' -jar 'installer.jar' '--installServer'; if ($LASTEXITCODE -ne 0) { throw 'Running installer.jar failed' }"
+        ));
+        assert!(script.contains(
+            "Move-Item -Force -Path 'forge-1.20.1-47.3.0.jar' -Destination 'server.jar'"
+        ));
+        assert!(script.contains("'start.ps1'"));
+    }
+
+    #[test]
+    fn egg_defaults_to_the_server_jar() {
+        let egg = super::egg::render(&forge(), &version(), "https://mcjars.app");
+
+        assert_eq!(
+            egg["meta"]["update_url"],
+            "https://mcjars.app/api/v1/script/1/egg"
+        );
+        assert_eq!(egg["variables"][0]["default_value"], "server.jar");
+        assert_eq!(
+            egg["scripts"]["installation"]["container"],
+            "eclipse-temurin:17-jdk"
+        );
+        assert_eq!(
+            egg["docker_images"]["Java 17"],
+            "ghcr.io/pterodactyl/yolks:java_17"
+        );
+
+        let script = egg["scripts"]["installation"]["script"].as_str().unwrap();
+        assert!(script.starts_with("#!/bin/bash\napt update"));
+        assert_eq!(script.matches("#!/bin/bash").count(), 1);
+    }
+
+    #[test]
+    fn egg_uses_the_installer_image_without_java_steps() {
+        let egg = super::egg::render(&paper(), &version(), "https://mcjars.app");
+
+        assert_eq!(
+            egg["scripts"]["installation"]["container"],
+            "ghcr.io/pterodactyl/installers:debian"
+        );
+    }
+
+    #[test]
+    fn dockerfile_runs_the_server_jar() {
+        let dockerfile = super::dockerfile::render(&forge(), 17);
+
+        assert!(
+            dockerfile.starts_with("# syntax=docker/dockerfile:1\nFROM eclipse-temurin:17-jre")
+        );
+        assert!(dockerfile.contains("RUN <<'EOF'\n#!/bin/bash\n"));
+        assert!(dockerfile.ends_with(r#"CMD ["java", "-jar", "server.jar"]"#));
+    }
+
+    #[test]
+    fn compose_inlines_the_dockerfile() {
+        let compose: serde_json::Value =
+            serde_yaml::from_str(&super::compose::render(&paper(), 21)).unwrap();
+        let server = &compose["services"]["server"];
+
+        assert_eq!(
+            server["build"]["dockerfile_inline"],
+            super::dockerfile::render(&paper(), 21)
+        );
+        assert_eq!(server["build"]["args"]["EULA"], "false");
+        assert_eq!(server["ports"][0], "25565:25565");
+    }
+}
@@ -0,0 +1,364 @@
+use crate::models::build::{Build, InstallationStep};
+
+#[derive(Default)]
+pub struct Options {
+    pub directory: Option<String>,
+    pub java: Option<String>,
+    pub memory: Option<u32>,
+    pub eula: bool,
+    pub start: bool,
+}
+
+impl Options {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(directory) = &self.directory
+            && (directory.is_empty() || directory.chars().any(|c| c.is_control() || c == '"'))
+        {
+            return Err("Invalid install directory".to_string());
+        }
+
+        if let Some(java) = &self.java
+            && (java.is_empty() || java.chars().any(|c| c.is_control() || c == '"'))
+        {
+            return Err("Invalid java path".to_string());
+        }
+
+        if let Some(memory) = self.memory
+            && !(128..=1048576).contains(&memory)
+        {
+            return Err("Memory must be between 128 and 1048576 megabytes".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+pub enum Command {
+    Enter(String),
+    MakeDirectory(String),
+    Remove(String),
+    Download {
+        url: String,
+        file: String,
+    },
+    Verify {
+        file: String,
+        sha256: String,
+    },
+    Unzip {
+        file: String,
+        location: String,
+    },
+    Extract {
+        file: String,
+        location: String,
+    },
+    Move {
+        from: String,
+        to: String,
+    },
+    Write {
+        file: String,
+        content: String,
+    },
+    Chmod {
+        location: String,
+        mode: String,
+    },
+    RunJava {
+        java: String,
+        file: String,
+        arguments: Vec<String>,
+    },
+    Start {
+        java: Option<String>,
+        version: i16,
+        memory: Option<u32>,
+        jar: String,
+    },
+}
+
+pub struct Step {
+    pub message: String,
+    pub commands: Vec<Command>,
+    pub parallel: bool,
+}
+
+pub struct Group {
+    pub steps: Vec<Step>,
+    pub checks: Vec<(Command, String)>,
+}
+
+pub struct Plan {
+    pub java: i16,
+    pub groups: Vec<Group>,
+}
+
+fn parent(path: &str) -> Option<Command> {
+    std::path::Path::new(path)
+        .parent()
+        .and_then(|parent| parent.to_str())
+        .filter(|parent| !parent.is_empty())
+        .map(|parent| Command::MakeDirectory(parent.to_string()))
+}
+
+impl Plan {
+    pub fn new(build: &Build, java: i16, options: Options) -> Self {
+        let mut groups = Vec::with_capacity(build.installation.len() + 2);
+        let binary = options.java.clone().unwrap_or_else(|| "java".to_string());
+
+        if let Some(directory) = &options.directory {
+            groups.push(Group {
+                steps: vec![Step {
+                    message: format!("Entering {}", directory),
+                    commands: vec![
+                        Command::MakeDirectory(directory.clone()),
+                        Command::Enter(directory.clone()),
+                    ],
+                    parallel: false,
+                }],
+                checks: Vec::new(),
+            });
+        }
+
+        for combined in &build.installation {
+            let mut group = Group {
+                steps: Vec::with_capacity(combined.len()),
+                checks: Vec::new(),
+            };
+
+            for step in combined {
+                group.steps.push(match step {
+                    InstallationStep::Download(step) => {
+                        if let Some(sha256) = &step.sha256 {
+                            group.checks.push((
+                                Command::Verify {
+                                    file: step.file.clone(),
+                                    sha256: sha256.clone(),
+                                },
+                                format!("Checksum mismatch for {}", step.file),
+                            ));
+                        }
+
+                        Step {
+                            message: format!("Downloading {}", step.file),
+                            commands: parent(&step.file)
+                                .into_iter()
+                                .chain([
+                                    Command::Remove(step.file.clone()),
+                                    Command::Download {
+                                        url: step.url.clone(),
+                                        file: step.file.clone(),
+                                    },
+                                ])
+                                .collect(),
+                            parallel: true,
+                        }
+                    }
+                    InstallationStep::Unzip(step) => Step {
+                        message: format!("Unzipping {}", step.file),
+                        commands: vec![
+                            Command::MakeDirectory(step.location.clone()),
+                            Command::Unzip {
+                                file: step.file.clone(),
+                                location: step.location.clone(),
+                            },
+                        ],
+                        parallel: true,
+                    },
+                    InstallationStep::Remove(step) => Step {
+                        message: format!("Removing {}", step.location),
+                        commands: vec![Command::Remove(step.location.clone())],
+                        parallel: false,
+                    },
+                    InstallationStep::Move(step) => Step {
+                        message: format!("Moving {} to {}", step.from, step.to),
+                        commands: parent(&step.to)
+                            .into_iter()
+                            .chain([Command::Move {
+                                from: step.from.clone(),
+                                to: step.to.clone(),
+                            }])
+                            .collect(),
+                        parallel: false,
+                    },
+                    InstallationStep::WriteFile(step) => Step {
+                        message: format!("Writing {}", step.file),
+                        commands: parent(&step.file)
+                            .into_iter()
+                            .chain([Command::Write {
+                                file: step.file.clone(),
+                                content: step.content.clone(),
+                            }])
+                            .collect(),
+                        parallel: false,
+                    },
+                    InstallationStep::Chmod(step) => Step {
+                        message: format!("Changing mode of {} to {}", step.location, step.mode),
+                        commands: vec![Command::Chmod {
+                            location: step.location.clone(),
+                            mode: step.mode.clone(),
+                        }],
+                        parallel: false,
+                    },
+                    InstallationStep::Extract(step) => Step {
+                        message: format!("Extracting {}", step.file),
+                        commands: vec![
+                            Command::MakeDirectory(step.location.clone()),
+                            Command::Extract {
+                                file: step.file.clone(),
+                                location: step.location.clone(),
+                            },
+                        ],
+                        parallel: true,
+                    },
+                    InstallationStep::RunJava(step) => Step {
+                        message: format!("Running {}", step.file),
+                        commands: vec![Command::RunJava {
+                            java: binary.clone(),
+                            file: step.file.clone(),
+                            arguments: step.arguments.clone(),
+                        }],
+                        parallel: false,
+                    },
+                });
+            }
+
+            groups.push(group);
+        }
+
+        let mut steps = Vec::new();
+
+        if options.eula {
+            steps.push(Step {
+                message: "Accepting EULA".to_string(),
+                commands: vec![Command::Write {
+                    file: "eula.txt".to_string(),
+                    content: "eula=true\n".to_string(),
+                }],
+                parallel: false,
+            });
+        }
+
+        if options.start {
+            steps.push(Step {
+                message: "Writing start script".to_string(),
+                commands: vec![Command::Start {
+                    java: options.java,
+                    version: java,
+                    memory: options.memory,
+                    jar: super::server_jar(build),
+                }],
+                parallel: false,
+            });
+        }
+
+        if !steps.is_empty() {
+            groups.push(Group {
+                steps,
+                checks: Vec::new(),
+            });
+        }
+
+        Self { java, groups }
+    }
+
+    pub fn runs_java(&self) -> bool {
+        self.groups
+            .iter()
+            .flat_map(|group| &group.steps)
+            .flat_map(|step| &step.commands)
+            .any(|command| matches!(command, Command::RunJava { .. }))
+    }
+}
+
+pub trait Dialect {
+    fn shebang(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn header(&self, java: i16) -> Vec<String>;
+    fn echo(&self, message: &str) -> String;
+    fn command(&self, command: &Command) -> String;
+    fn run(&self, command: &Command, failure: &str) -> String;
+    fn exit(&self, code: i32) -> String;
+
+    fn background(&self, _command: &Command, _failure: &str) -> Option<String> {
+        None
+    }
+
+    fn wait(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn group(&self, lines: Vec<String>) -> Vec<String> {
+        lines
+    }
+}
+
+pub fn render(plan: &Plan, dialect: &impl Dialect, echo: bool) -> String {
+    let mut lines: Vec<String> = dialect.shebang().map(String::from).into_iter().collect();
+
+    lines.extend(dialect.header(plan.java));
+    if echo {
+        lines.push(dialect.echo("Installing Server"));
+    }
+
+    for group in &plan.groups {
+        let mut block = Vec::new();
+        let mut waiting = false;
+
+        for step in &group.steps {
+            if echo {
+                block.push(dialect.echo(&step.message));
+            }
+
+            let failure = format!("{} failed", step.message);
+
+            for (i, command) in step.commands.iter().enumerate() {
+                let background = if step.parallel && i == step.commands.len() - 1 {
+                    dialect.background(command, &failure)
+                } else {
+                    None
+                };
+
+                match background {
+                    Some(line) => {
+                        block.push(line);
+                        waiting = true;
+                    }
+                    None => block.push(dialect.run(command, &failure)),
+                }
+            }
+        }
+
+        if waiting {
+            block.extend(dialect.wait());
+        }
+
+        for (check, failure) in &group.checks {
+            block.push(dialect.run(check, failure));
+        }
+
+        lines.push(String::new());
+        lines.extend(dialect.group(block));
+    }
+
+    lines.push(String::new());
+    if echo {
+        lines.push(dialect.echo("Installation complete"));
+        lines.push(dialect.echo(&format!("Use Java version: {}", plan.java)));
+    }
+    lines.push(dialect.exit(0));
+
+    lines.join("\n")
+}
+
+pub fn fail(dialect: &impl Dialect, message: &str) -> String {
+    let mut lines: Vec<String> = dialect.shebang().map(String::from).into_iter().collect();
+
+    lines.push(dialect.echo(message));
+    lines.push(dialect.exit(1));
+
+    lines.join("\n")
+}
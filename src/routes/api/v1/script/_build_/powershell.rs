@@ -1,11 +1,165 @@
-use super::State;
+use super::{
+    State,
+    plan::{Command, Dialect},
+};
+use base64::Engine;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn start_script(java: Option<&str>, version: i16, memory: Option<u32>, jar: &str) -> String {
+    format!(
+        r#"
+Set-Location -Path $PSScriptRoot
+$java = {}
+$output = (& $java -version 2>&1 | Select-Object -First 1).ToString()
+$version = 0
+if ($output -match 'version "(\d+)(?:\.(\d+))?') {{
+    $version = if ($Matches[1] -eq '1') {{ [int]$Matches[2] }} else {{ [int]$Matches[1] }}
+}}
+if ($version -lt {}) {{
+    throw "Java {} or newer is required, found $version"
+}}
+& $java{} -jar {} nogui @args
+exit $LASTEXITCODE
+        "#,
+        java.map(quote).unwrap_or_else(|| {
+            "if ($env:JAVA_HOME) { Join-Path $env:JAVA_HOME 'bin/java' } else { 'java' }"
+                .to_string()
+        }),
+        version,
+        version,
+        memory
+            .map(|memory| format!(" -Xms{}M -Xmx{}M", memory, memory))
+            .unwrap_or_default(),
+        quote(jar)
+    )
+    .trim()
+    .to_string()
+        + "\n"
+}
+
+pub struct PowerShell;
+
+impl Dialect for PowerShell {
+    fn header(&self, java: i16) -> Vec<String> {
+        vec![
+            "$ErrorActionPreference = 'Stop'".to_string(),
+            "$ProgressPreference = 'SilentlyContinue'".to_string(),
+            format!("$env:JAVA_VERSION = {}", java),
+        ]
+    }
+
+    fn echo(&self, message: &str) -> String {
+        format!("Write-Host {}", quote(message))
+    }
+
+    fn command(&self, command: &Command) -> String {
+        match command {
+            Command::Enter(location) => format!("Set-Location -Path {}", quote(location)),
+            Command::MakeDirectory(location) => format!(
+                "New-Item -ItemType Directory -Force -Path {} | Out-Null",
+                quote(location)
+            ),
+            Command::Remove(location) => format!(
+                "Remove-Item -Recurse -Force -ErrorAction SilentlyContinue -Path {}",
+                quote(location)
+            ),
+            Command::Download { url, file } => format!(
+                "Invoke-WebRequest -UseBasicParsing -Uri {} -OutFile {}",
+                quote(url),
+                quote(file)
+            ),
+            Command::Verify { file, sha256 } => format!(
+                "(Get-FileHash -Algorithm SHA256 -Path {}).Hash -eq {}",
+                quote(file),
+                quote(sha256)
+            ),
+            Command::Unzip { file, location } => format!(
+                "Expand-Archive -Force -Path {} -DestinationPath {}",
+                quote(file),
+                quote(location)
+            ),
+            Command::Extract { file, location } => {
+                format!("tar -xzf {} -C {}", quote(file), quote(location))
+            }
+            Command::Move { from, to } => format!(
+                "Move-Item -Force -Path {} -Destination {}",
+                quote(from),
+                quote(to)
+            ),
+            Command::Write { file, content } => format!(
+                "[IO.File]::WriteAllBytes((Join-Path $PWD {}), [Convert]::FromBase64String({}))",
+                quote(file),
+                quote(&base64::engine::general_purpose::STANDARD.encode(content))
+            ),
+            Command::Chmod { location, mode } => format!(
+                "if ($IsLinux -or $IsMacOS) {{ chmod {} {} }}",
+                mode,
+                quote(location)
+            ),
+            Command::RunJava {
+                java,
+                file,
+                arguments,
+            } => std::iter::once(format!("& {} -jar {}", quote(java), quote(file)))
+                .chain(arguments.iter().map(|argument| quote(argument)))
+                .collect::<Vec<String>>()
+                .join(" "),
+            Command::Start {
+                java,
+                version,
+                memory,
+                jar,
+            } => self.command(&Command::Write {
+                file: "start.ps1".to_string(),
+                content: start_script(java.as_deref(), *version, *memory, jar),
+            }),
+        }
+    }
+
+    fn run(&self, command: &Command, failure: &str) -> String {
+        match command {
+            Command::Verify { .. } => format!(
+                "if (-not ({})) {{ throw {} }}",
+                self.command(command),
+                quote(failure)
+            ),
+            Command::Chmod { location, mode } => format!(
+                "if ($IsLinux -or $IsMacOS) {{ chmod {} {}; if ($LASTEXITCODE -ne 0) {{ throw {} }} }}",
+                mode,
+                quote(location),
+                quote(failure)
+            ),
+            Command::Extract { .. } | Command::RunJava { .. } => format!(
+                "{}; if ($LASTEXITCODE -ne 0) {{ throw {} }}",
+                self.command(command),
+                quote(failure)
+            ),
+            command => format!(
+                "try {{ {} }} catch {{ throw {} }}",
+                self.command(command),
+                quote(failure)
+            ),
+        }
+    }
+
+    fn exit(&self, code: i32) -> String {
+        format!("exit {}", code)
+    }
+
+    fn group(&self, lines: Vec<String>) -> Vec<String> {
+        std::iter::once("Invoke-Command {".to_string())
+            .chain(lines)
+            .chain(std::iter::once("}".to_string()))
+            .collect()
+    }
+}
+
 mod get {
-    use crate::{
-        models::build::{Build, InstallationStep},
-        routes::GetState,
-    };
+    use crate::routes::GetState;
     use axum::extract::{Path, Query};
     use reqwest::StatusCode;
     use serde::{Deserialize, Serialize};
@@ -15,10 +169,19 @@ mod get {
     pub struct Params {
         #[serde(default)]
         echo: bool,
+
+        directory: Option<String>,
+        java: Option<String>,
+        memory: Option<u32>,
+        #[serde(default)]
+        eula: bool,
+        #[serde(default)]
+        start: bool,
     }
 
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = String),
+        (status = BAD_REQUEST, body = String),
         (status = NOT_FOUND, body = String),
     ), params(
         (
@@ -32,111 +195,80 @@ mod get {
             description = "Whether to echo inside the script",
             example = "true",
         ),
+        (
+            "directory" = Option<String>,
+            Query,
+            description = "The directory to install the server into",
+            example = "server",
+        ),
+        (
+            "java" = Option<String>,
+            Query,
+            description = "The java binary to run the server with, defaults to JAVA_HOME or java",
+            example = "/usr/lib/jvm/java-21-openjdk/bin/java",
+        ),
+        (
+            "memory" = Option<u32>,
+            Query,
+            description = "The memory to give the server in megabytes",
+            example = "4096",
+        ),
+        (
+            "eula" = bool,
+            Query,
+            description = "Whether to accept the Minecraft EULA",
+            example = "true",
+        ),
+        (
+            "start" = bool,
+            Query,
+            description = "Whether to write a start script",
+            example = "true",
+        ),
     ))]
     pub async fn route(
         state: GetState,
         Path(identifier): Path<String>,
         Query(query): Query<Params>,
     ) -> (StatusCode, String) {
-        let data = Build::by_v1_identifier(&state.database, &state.cache, &identifier).await;
-
-        if let Some((build, _, version)) = data {
-            let mut script = format!(
-                r#"
-Write-Host "Installing Server"
-$env:JAVA_VERSION = {}
-                "#,
-                version.java
-            )
-            .trim()
-            .to_string();
-
-            for combined in build.installation {
-                let mut steps = vec![
-                    "".to_string(),
-                    "".to_string(),
-                    "Invoke-Command {".to_string(),
-                ];
-
-                for step in combined {
-                    match step {
-                        InstallationStep::Remove(step) => {
-                            steps.push(format!(
-                                r#"
-Write-Host "Removing {}"
-Remove-Item -Recurse -Force {}
-                                "#,
-                                step.location, step.location
-                            ));
-                        }
-                        InstallationStep::Download(step) => {
-                            steps.push(format!(
-                                r#"
-Write-Host "Downloading {}"
-New-Item -ItemType Directory -Force ./{}
-Invoke-WebRequest -Uri '{}' -OutFile {}
-                                "#,
-                                step.file,
-                                std::path::Path::new(&step.file)
-                                    .parent()
-                                    .unwrap()
-                                    .to_str()
-                                    .unwrap(),
-                                step.url,
-                                step.file
-                            ));
-                        }
-                        InstallationStep::Unzip(step) => {
-                            steps.push(format!(
-                                r#"
-Write-Host "Unzipping {}"
-New-Item -ItemType Directory -Force {}
-Expand-Archive -Path {} -DestinationPath {}
-                                "#,
-                                step.file, step.location, step.file, step.location
-                            ));
-                        }
-                    }
-                }
-
-                steps.push("}".to_string());
-
-                script.push_str(
-                    &steps
-                        .iter()
-                        .map(|s| s.trim())
-                        .collect::<Vec<&str>>()
-                        .join("\n"),
+        let options = super::super::plan::Options {
+            directory: query.directory,
+            java: query.java,
+            memory: query.memory,
+            eula: query.eula,
+            start: query.start,
+        };
+
+        if let Err(error) = options.validate() {
+            return (
+                StatusCode::BAD_REQUEST,
+                super::super::plan::fail(&super::PowerShell, &error),
+            );
+        }
+
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (
+                    err.status(),
+                    super::super::plan::fail(&super::PowerShell, err.message()),
                 );
             }
+        };
 
-            script.push_str(
-                r#"
-
-Write-Host "Installation complete"
-Write-Host "Use Java version: $env:JAVA_VERSION"
-exit 0
-                "#
-                .trim_end(),
-            );
+        if let Some((build, version)) = data {
+            let plan = super::super::plan::Plan::new(&build, version.java, options);
 
             (
                 StatusCode::OK,
-                script
-                    .lines()
-                    .filter(|l| !l.starts_with("Write-Host") || query.echo)
-                    .collect::<Vec<&str>>()
-                    .join("\n"),
+                super::super::plan::render(&plan, &super::PowerShell, query.echo),
             )
         } else {
             (
                 StatusCode::NOT_FOUND,
-                r#"
-Write-Host "Build not found"
-exit 1
-                "#
-                .trim()
-                .to_string(),
+                super::super::plan::fail(&super::PowerShell, "Build not found"),
             )
         }
     }
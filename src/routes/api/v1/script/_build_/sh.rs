@@ -0,0 +1,183 @@
+use super::{
+    State,
+    plan::{Command, Dialect},
+};
+use base64::Engine;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+pub fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+pub fn start_script(java: Option<&str>, version: i16, memory: Option<u32>, jar: &str) -> String {
+    format!(
+        r#"
+#!/bin/sh
+cd "$(dirname "$0")" || exit 1
+JAVA={}
+version=$("$JAVA" -version 2>&1 | sed -n 's/.*version "\([0-9]*\)\.\{{0,1\}}\([0-9]*\).*/\1 \2/p' | head -n 1 | awk '{{ print ($1 == 1) ? $2 : $1 }}')
+if [ "${{version:-0}}" -lt {} ]; then
+    echo "Java {} or newer is required, found ${{version:-none}}" >&2
+    exit 1
+fi
+exec "$JAVA"{} -jar {} nogui "$@"
+        "#,
+        java.map(quote)
+            .unwrap_or_else(|| r#""${JAVA_HOME:+$JAVA_HOME/bin/}java""#.to_string()),
+        version,
+        version,
+        memory
+            .map(|memory| format!(" -Xms{}M -Xmx{}M", memory, memory))
+            .unwrap_or_default(),
+        quote(jar)
+    )
+    .trim()
+    .to_string()
+        + "\n"
+}
+
+pub struct Sh;
+
+impl Dialect for Sh {
+    fn shebang(&self) -> Option<&'static str> {
+        Some("#!/bin/sh")
+    }
+
+    fn header(&self, java: i16) -> Vec<String> {
+        vec![
+            format!("export JAVA_VERSION={}", java),
+            "".to_string(),
+            r#"fail() { echo "$1" >&2; exit 1; }"#.to_string(),
+            r#"download() { if command -v curl >/dev/null 2>&1; then curl -fsSL -o "$1" "$2"; else wget -q -O "$1" "$2"; fi; }"#.to_string(),
+        ]
+    }
+
+    fn echo(&self, message: &str) -> String {
+        format!("echo {}", quote(message))
+    }
+
+    fn command(&self, command: &Command) -> String {
+        match command {
+            Command::Enter(location) => format!("cd {}", quote(location)),
+            Command::MakeDirectory(location) => format!("mkdir -p {}", quote(location)),
+            Command::Remove(location) => format!("rm -rf {}", quote(location)),
+            Command::Download { url, file } => format!("download {} {}", quote(file), quote(url)),
+            Command::Verify { file, sha256 } => format!(
+                r#"[ "$(sha256sum {} | cut -d ' ' -f 1)" = {} ]"#,
+                quote(file),
+                quote(sha256)
+            ),
+            Command::Unzip { file, location } => {
+                format!("unzip -oq {} -d {}", quote(file), quote(location))
+            }
+            Command::Extract { file, location } => {
+                format!("tar -xzf {} -C {}", quote(file), quote(location))
+            }
+            Command::Move { from, to } => format!("mv -f {} {}", quote(from), quote(to)),
+            Command::Write { file, content } => format!(
+                "printf '%s' {} | base64 -d > {}",
+                quote(&base64::engine::general_purpose::STANDARD.encode(content)),
+                quote(file)
+            ),
+            Command::Chmod { location, mode } => format!("chmod {} {}", mode, quote(location)),
+            Command::RunJava {
+                java,
+                file,
+                arguments,
+            } => std::iter::once(format!("{} -jar {}", quote(java), quote(file)))
+                .chain(arguments.iter().map(|argument| quote(argument)))
+                .collect::<Vec<String>>()
+                .join(" "),
+            Command::Start {
+                java,
+                version,
+                memory,
+                jar,
+            } => format!(
+                "{} && chmod 755 'start.sh'",
+                self.command(&Command::Write {
+                    file: "start.sh".to_string(),
+                    content: start_script(java.as_deref(), *version, *memory, jar),
+                })
+            ),
+        }
+    }
+
+    fn run(&self, command: &Command, failure: &str) -> String {
+        format!("{} || fail {}", self.command(command), quote(failure))
+    }
+
+    fn exit(&self, code: i32) -> String {
+        format!("exit {}", code)
+    }
+}
+
+mod get {
+    use crate::routes::GetState;
+    use axum::{
+        extract::{Path, Query},
+        http::StatusCode,
+    };
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        echo: bool,
+    }
+
+    #[utoipa::path(get, path = "/", responses(
+        (status = OK, body = String),
+        (status = NOT_FOUND, body = String),
+    ), params(
+        (
+            "build",
+            description = "The build number or hash to lookup",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+        (
+            "echo",
+            Query,
+            description = "Whether to echo inside the script",
+            example = "true",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        Path(identifier): Path<String>,
+        Query(query): Query<Params>,
+    ) -> (StatusCode, String) {
+        let data = match super::super::lookup(&state, &identifier).await {
+            Ok(data) => data,
+            Err(err) => {
+                err.report();
+
+                return (
+                    err.status(),
+                    super::super::plan::fail(&super::Sh, err.message()),
+                );
+            }
+        };
+
+        if let Some((build, version)) = data {
+            let plan = super::super::plan::Plan::new(&build, version.java, Default::default());
+
+            (
+                StatusCode::OK,
+                super::super::plan::render(&plan, &super::Sh, query.echo),
+            )
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                super::super::plan::fail(&super::Sh, "Build not found"),
+            )
+        }
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
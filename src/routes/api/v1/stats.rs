@@ -3,6 +3,7 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod get {
     use crate::routes::GetState;
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use utoipa::ToSchema;
@@ -42,10 +43,12 @@ mod get {
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
     ))]
-    pub async fn route(state: GetState) -> axum::Json<serde_json::Value> {
-        let stats = state
+    pub async fn route(state: GetState) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let stats = match state
             .cache
-            .cached("stats::all", 3600, || async {
+            .cached("stats::all", 3600, move || async move {
                 let (hashes, requests, builds) = tokio::join!(
                     sqlx::query(
                         r#"
@@ -54,7 +57,7 @@ mod get {
                         FROM build_hashes
                         "#
                     )
-                    .fetch_one(state.database.read()),
+                    .fetch_one(database.read()),
                     sqlx::query(
                         r#"
                         SELECT
@@ -63,7 +66,7 @@ mod get {
                         FROM requests
                         "#
                     )
-                    .fetch_one(state.database.read()),
+                    .fetch_one(database.read()),
                     sqlx::query(
                         r#"
                         SELECT
@@ -73,13 +76,13 @@ mod get {
                         FROM builds
                         "#
                     )
-                    .fetch_one(state.database.read())
+                    .fetch_one(database.read())
                 );
 
                 let (hashes, requests, builds) =
                     (hashes.unwrap(), requests.unwrap(), builds.unwrap());
 
-                Stats {
+                Ok(Stats {
                     builds: builds.get(0),
                     hashes: hashes.get(0),
                     requests: requests.get(0),
@@ -90,16 +93,23 @@ mod get {
                         jar_size: builds.get(1),
                         zip_size: builds.get(2),
                     },
-                }
+                })
             })
-            .await;
+            .await
+        {
+            Ok(stats) => stats,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                stats,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    stats,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
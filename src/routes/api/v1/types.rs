@@ -6,6 +6,7 @@ mod get {
         models::r#type::{ESTABLISHED_TYPES, ServerType, ServerTypeInfo},
         routes::GetState,
     };
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
@@ -19,15 +20,21 @@ mod get {
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
     ))]
-    pub async fn route(state: GetState) -> axum::Json<serde_json::Value> {
-        let data = ServerType::all(&state.database, &state.cache).await;
+    pub async fn route(state: GetState) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let data = match ServerType::all(&state.database, &state.cache).await {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                types: ServerType::extract(&data, &ESTABLISHED_TYPES),
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    types: ServerType::extract(&data, &ESTABLISHED_TYPES),
+                })
+                .unwrap(),
+            ),
         )
     }
 }
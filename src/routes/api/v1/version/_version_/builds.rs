@@ -32,23 +32,33 @@ mod get {
         state: GetState,
         Path(version): Path<String>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let data = state
+        let database = state.database.clone();
+
+        let data = match state
             .cache
-            .cached(&format!("version::{}::builds", version), 1800, || async {
-                let data = Build::all_for_minecraft_version(&state.database, &version).await;
+            .cached(
+                &format!("version::{}::builds", version),
+                1800,
+                move || async move {
+                    let data = Build::all_for_minecraft_version(&database, &version).await?;
 
-                let mut builds = IndexMap::new();
-                for r#type in ServerType::variants() {
-                    builds.insert(r#type, vec![]);
-                }
+                    let mut builds = IndexMap::new();
+                    for r#type in ServerType::variants() {
+                        builds.insert(r#type, vec![]);
+                    }
 
-                for build in data {
-                    builds[&build.r#type].push(build);
-                }
+                    for build in data {
+                        builds[&build.r#type].push(build);
+                    }
 
-                builds
-            })
-            .await;
+                    Ok(builds)
+                },
+            )
+            .await
+        {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
         if data.is_empty() {
             (
@@ -32,7 +32,11 @@ mod get {
         state: GetState,
         Path(version): Path<String>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let data = MinifiedVersionStats::by_id(&state.database, &state.cache, &version).await;
+        let data = match MinifiedVersionStats::by_id(&state.database, &state.cache, &version).await
+        {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
         if let Some(data) = data {
             (
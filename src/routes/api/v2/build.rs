@@ -4,11 +4,15 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 mod post {
     use crate::{
         models::{
-            BaseModel, build::Build, config::Format, r#type::ServerType, version::MinifiedVersion,
+            BaseModel,
+            build::{Build, BuildFields, BuildFieldsQuery},
+            config::Format,
+            r#type::ServerType,
+            version::MinifiedVersion,
         },
         routes::{ApiError, GetData, GetState},
     };
-    use axum::http::StatusCode;
+    use axum::{extract::Query, http::StatusCode};
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use serde_json::json;
@@ -88,10 +92,10 @@ mod post {
     }
 
     #[derive(ToSchema, Serialize, Deserialize)]
-    struct Result {
-        build: Build,
+    pub struct Result {
+        pub build: Build,
         latest: Build,
-        version: MinifiedVersion,
+        pub version: MinifiedVersion,
 
         #[schema(inline)]
         configs: IndexMap<String, ConfigValue>,
@@ -121,15 +125,32 @@ mod post {
         (status = MULTI_STATUS, body = inline(ResponseMany)),
         (status = PAYLOAD_TOO_LARGE, body = inline(ApiError)),
         (status = NOT_FOUND, body = inline(ApiError)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+    ), params(
+        (
+            "fields" = Option<String>,
+            Query,
+            description = "Comma separated list of build fields to return",
+            example = "id,buildNumber",
+        ),
     ), request_body = inline(Payload))]
     pub async fn route(
         state: GetState,
         request_data: GetData,
+        Query(BuildFieldsQuery { fields }): Query<BuildFieldsQuery>,
         axum::Json(data): axum::Json<Payload>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
         match data {
             Payload::One(search) => {
-                if let Some(result) = lookup_build(&state.database, &state.cache, *search).await {
+                let result =
+                    match lookup_build(&state.database, &state.cache, *search, fields.as_ref())
+                        .await
+                    {
+                        Ok(result) => result,
+                        Err(err) => return err.into(),
+                    };
+
+                if let Some(result) = result {
                     *request_data.lock().unwrap() = json!({
                         "type": "lookup",
                         "build": {
@@ -142,19 +163,21 @@ mod post {
                         }
                     });
 
-                    (
-                        StatusCode::OK,
-                        axum::Json(
-                            serde_json::to_value(&ResponseOne {
-                                success: true,
-                                build: result.build,
-                                latest: result.latest,
-                                version: result.version,
-                                configs: result.configs,
-                            })
-                            .unwrap(),
-                        ),
-                    )
+                    let mut response = serde_json::to_value(&ResponseOne {
+                        success: true,
+                        build: result.build,
+                        latest: result.latest,
+                        version: result.version,
+                        configs: result.configs,
+                    })
+                    .unwrap();
+
+                    if let Some(fields) = &fields {
+                        fields.project(&mut response["build"]);
+                        fields.project(&mut response["latest"]);
+                    }
+
+                    (StatusCode::OK, axum::Json(response))
                 } else {
                     (
                         StatusCode::NOT_FOUND,
@@ -175,10 +198,22 @@ mod post {
 
                 let mut results = Vec::with_capacity(searches.len());
                 for search in searches {
-                    results.push(lookup_build(&state.database, &state.cache, search));
+                    results.push(lookup_build(
+                        &state.database,
+                        &state.cache,
+                        search,
+                        fields.as_ref(),
+                    ));
                 }
 
-                let results = futures_util::future::join_all(results).await;
+                let results = match futures_util::future::join_all(results)
+                    .await
+                    .into_iter()
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                {
+                    Ok(results) => results,
+                    Err(err) => return err.into(),
+                };
 
                 if let Some(result) = results.iter().flatten().next() {
                     *request_data.lock().unwrap() = json!({
@@ -194,34 +229,53 @@ mod post {
                     });
                 }
 
-                (
-                    StatusCode::MULTI_STATUS,
-                    axum::Json(
-                        serde_json::to_value(&ResponseMany {
-                            success: true,
-                            builds: results,
-                        })
-                        .unwrap(),
-                    ),
-                )
+                let mut response = serde_json::to_value(&ResponseMany {
+                    success: true,
+                    builds: results,
+                })
+                .unwrap();
+
+                if let Some(fields) = &fields {
+                    for result in response["builds"].as_array_mut().unwrap() {
+                        if result.is_object() {
+                            fields.project(&mut result["build"]);
+                            fields.project(&mut result["latest"]);
+                        }
+                    }
+                }
+
+                (StatusCode::MULTI_STATUS, axum::Json(response))
             }
         }
     }
 
-    async fn lookup_build(
+    pub async fn lookup_build(
         database: &crate::database::Database,
         cache: &crate::cache::Cache,
         search: BuildSearch,
-    ) -> Option<Result> {
+        fields: Option<&BuildFields>,
+    ) -> std::result::Result<Option<Result>, crate::database::DatabaseError> {
         if !search.any() {
-            return None;
+            return Ok(None);
         }
 
+        let fields = fields.map(|fields| fields.with_lookup_columns());
         let mut sha1 = sha1::Sha1::new();
         sha1.update(serde_json::to_string(&search).unwrap().as_bytes());
+        if let Some(fields) = &fields {
+            sha1.update(fields.key().as_bytes());
+        }
         let identifier = format!("{:x}", sha1.finalize());
 
-        cache.cached(&format!("build::{}", identifier), 3600, || async {
+        let columns_sql = move |table: Option<&str>| match &fields {
+            Some(fields) => fields.columns_sql(None, table),
+            None => Build::columns_sql(None, table),
+        };
+        let database = database.clone();
+
+        cache.cached_tagged(&format!("build::{}", identifier), 3600, |result: &Option<Result>| {
+            result.iter().flat_map(|result| [Build::cache_tag(result.build.id), result.latest.line_cache_tag()]).collect()
+        }, move || async move {
             let mut where_clause: Vec<String> = Vec::new();
             let mut data: Vec<serde_json::Value> = Vec::new();
 
@@ -265,8 +319,8 @@ mod post {
                 where_clause.push(format!("builds.zip_size = ($1->>{})::int", data.len()));
                 data.push(serde_json::to_value(zip_size).unwrap());
             }
-            if let Some(hash) = &search.hash {
-                if hash.any() {
+            if let Some(hash) = &search.hash
+                && hash.any() {
                     if let Some(primary) = hash.primary {
                         where_clause.push(format!("build_hashes.primary = ($1->>{})::bool", data.len()));
                         data.push(serde_json::to_value(primary).unwrap());
@@ -296,7 +350,6 @@ mod post {
                         data.push(serde_json::to_value(md5).unwrap());
                     }
                 }
-            }
 
             let query = sqlx::query(&format!(
                 r#"
@@ -359,23 +412,22 @@ mod post {
                 ) x
                 LEFT JOIN minecraft_versions mv ON mv.id = x.version_id
                 "#,
-                Build::columns_sql(None, None),
+                columns_sql(None),
                 if search.hash.as_ref().map(|h| h.any()).unwrap_or(false) {
                     "build_hashes INNER JOIN builds ON builds.id = build_hashes.build_id"
                 } else {
                     "builds"
                 },
                 where_clause.join(" AND "),
-                Build::columns_sql(None, Some("b")),
-                Build::columns_sql(None, Some("b"))
+                columns_sql(Some("b")),
+                columns_sql(Some("b"))
             ))
             .bind(serde_json::to_value(data).unwrap())
             .fetch_all(database.read())
-            .await
-            .unwrap();
+            .await?;
 
             if query.len() != 2 {
-                return None;
+                return Ok(None);
             }
 
             let mut configs = IndexMap::new();
@@ -395,8 +447,7 @@ mod post {
             )
             .bind(query[0].get::<i32, _>("id"))
             .fetch_all(database.read())
-            .await
-            .unwrap()
+            .await?
             {
                 let r#type = serde_json::from_value(serde_json::Value::String(row.get("type"))).unwrap();
                 let format = serde_json::from_value(serde_json::Value::String(row.get("format"))).unwrap();
@@ -408,7 +459,7 @@ mod post {
                 );
             }
 
-            Some(Result {
+            Ok(Some(Result {
                 build: Build::map(None, &query[0]),
                 latest: Build::map(None, &query[1]),
                 version: crate::models::version::MinifiedVersion {
@@ -429,14 +480,127 @@ mod post {
                         .unwrap_or(query[1].try_get("version2_created").unwrap_or_default()),
                 },
                 configs
-            })
+            }))
         })
         .await
     }
 }
 
+mod upgrade {
+    use super::post::{BuildSearch, lookup_build};
+    use crate::{
+        models::build::{Build, BuildChange, JavaChange, UpgradePolicy},
+        routes::{ApiError, GetData, GetState},
+    };
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        #[schema(inline)]
+        search: BuildSearch,
+        policy: UpgradePolicy,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[schema(rename_all = "camelCase")]
+    struct Response {
+        success: bool,
+        up_to_date: bool,
+        build: Build,
+        target: Build,
+
+        #[schema(inline)]
+        java: JavaChange,
+        #[schema(inline)]
+        changes: Vec<BuildChange>,
+    }
+
+    #[utoipa::path(post, path = "/upgrade", responses(
+        (status = OK, body = inline(Response)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        request_data: GetData,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let result = match lookup_build(&state.database, &state.cache, data.search, None).await {
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["build not found"]).to_value()),
+                );
+            }
+            Err(err) => return err.into(),
+        };
+
+        *request_data.lock().unwrap() = json!({
+            "type": "lookup",
+            "build": {
+                "id": result.build.id,
+                "type": result.build.r#type,
+                "versionId": result.build.version_id,
+                "projectVersionId": result.build.project_version_id,
+                "buildNumber": result.build.build_number,
+                "java": result.version.java,
+            }
+        });
+
+        let current = (Some(result.version.created), result.build.build_number);
+        let java = result
+            .build
+            .version_id
+            .as_ref()
+            .map(|_| result.version.java);
+
+        let (target, target_java) =
+            match Build::upgrade_target(&state.database, &result.build, data.policy).await {
+                Ok(Some((target, target_java, created)))
+                    if (created, target.build_number) > current =>
+                {
+                    (target, target_java)
+                }
+                Ok(_) => (result.build.clone(), java),
+                Err(err) => return err.into(),
+            };
+
+        let changes = if target.id != result.build.id {
+            match Build::all_between(&state.database, &result.build, &target).await {
+                Ok(changes) => changes,
+                Err(err) => return err.into(),
+            }
+        } else {
+            Vec::new()
+        }
+        .into_iter()
+        .map(BuildChange::from)
+        .collect();
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    up_to_date: target.id == result.build.id,
+                    java: JavaChange::new(java, target_java),
+                    build: result.build,
+                    target,
+                    changes,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(post::route))
+        .routes(routes!(upgrade::route))
         .with_state(state.clone())
 }
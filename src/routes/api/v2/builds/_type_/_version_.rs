@@ -1,24 +1,61 @@
 use super::State;
+use crate::models::r#type::ServerType;
 use utoipa_axum::{router::OpenApiRouter, routes};
 
+pub fn next_link(
+    app_url: &str,
+    r#type: ServerType,
+    version: &str,
+    query: &[(&str, String)],
+) -> Option<String> {
+    let mut url = reqwest::Url::parse(app_url).ok()?;
+    url.path_segments_mut().ok()?.pop_if_empty().extend([
+        "api",
+        "v2",
+        "builds",
+        &r#type.to_string(),
+        version,
+    ]);
+    url.query_pairs_mut().extend_pairs(query);
+
+    Some(format!("<{}>; rel=\"next\"", url))
+}
+
 mod get {
     use crate::{
-        models::{build::Build, r#type::ServerType, version::Version},
+        models::{
+            build::{Build, BuildFieldsQuery, BuildFilter},
+            r#type::ServerType,
+            version::Version,
+        },
         routes::{ApiError, GetData, GetState},
     };
-    use axum::{extract::Path, http::StatusCode};
+    use axum::{
+        extract::{Path, Query},
+        http::{HeaderMap, StatusCode},
+    };
     use serde::{Deserialize, Serialize};
     use serde_json::json;
     use utoipa::ToSchema;
 
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Params {
+        limit: Option<i64>,
+        cursor: Option<i32>,
+        experimental: Option<bool>,
+        since: Option<i64>,
+    }
+
     #[derive(ToSchema, Serialize, Deserialize)]
     struct Response {
         success: bool,
         builds: Vec<Build>,
+        next: Option<i32>,
     }
 
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
         (status = NOT_FOUND, body = inline(ApiError)),
     ), params(
         (
@@ -31,25 +68,161 @@ mod get {
             description = "The server version",
             example = "1.17.1",
         ),
+        (
+            "limit" = Option<i64>,
+            Query,
+            description = "The maximum amount of builds to return (1-100), defaults to 100 when filtering or paging",
+            example = 25,
+        ),
+        (
+            "cursor" = Option<i32>,
+            Query,
+            description = "Only return builds with an id lower than this",
+            example = 1000,
+        ),
+        (
+            "experimental" = Option<bool>,
+            Query,
+            description = "Only return (non-)experimental builds",
+            example = false,
+        ),
+        (
+            "since" = Option<i64>,
+            Query,
+            description = "Only return builds created at or after this unix timestamp",
+            example = 1700000000,
+        ),
+        (
+            "fields" = Option<String>,
+            Query,
+            description = "Comma separated list of build fields to return",
+            example = "id,buildNumber",
+        ),
     ))]
     pub async fn route(
         state: GetState,
         request_data: GetData,
         Path((r#type, version)): Path<(ServerType, String)>,
-    ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let location = Version::location(&state.database, &state.cache, r#type, &version).await;
+        Query(params): Query<Params>,
+        Query(BuildFieldsQuery { fields }): Query<BuildFieldsQuery>,
+    ) -> (StatusCode, HeaderMap, axum::Json<serde_json::Value>) {
+        if params
+            .limit
+            .is_some_and(|limit| !(1..=100).contains(&limit))
+        {
+            return (
+                StatusCode::BAD_REQUEST,
+                HeaderMap::new(),
+                axum::Json(ApiError::new(&["limit must be between 1 and 100"]).to_value()),
+            );
+        }
+
+        let since = match params.since {
+            Some(since) => match chrono::DateTime::from_timestamp(since, 0) {
+                Some(since) => Some(since.naive_utc()),
+                None => {
+                    return (
+                        StatusCode::BAD_REQUEST,
+                        HeaderMap::new(),
+                        axum::Json(ApiError::new(&["invalid since timestamp"]).to_value()),
+                    );
+                }
+            },
+            None => None,
+        };
+
+        let location =
+            match Version::location(&state.database, &state.cache, r#type, &version).await {
+                Ok(location) => location,
+                Err(err) => {
+                    let (status, body): (StatusCode, axum::Json<serde_json::Value>) = err.into();
+
+                    return (status, HeaderMap::new(), body);
+                }
+            };
 
         if let Some(location) = location {
-            let data = state
-                .cache
-                .cached(
-                    &format!("builds::{}::{}", r#type, version),
-                    1800,
-                    || async {
-                        Build::all_for_version(&state.database, r#type, &location, &version).await
-                    },
+            let limit = params.limit.or_else(|| {
+                (params.cursor.is_some()
+                    || params.experimental.is_some()
+                    || params.since.is_some()
+                    || fields.is_some())
+                .then_some(100)
+            });
+
+            let filter = BuildFilter {
+                cursor: params.cursor,
+                limit: limit.map(|limit| limit + 1),
+                experimental: params.experimental,
+                since,
+            };
+
+            let data = if filter.cursor.is_none()
+                && filter.limit.is_none()
+                && filter.experimental.is_none()
+                && filter.since.is_none()
+                && fields.is_none()
+            {
+                let database = state.database.clone();
+                let version = version.clone();
+
+                state
+                    .cache
+                    .cached(
+                        &format!("builds::{}::{}", r#type, version),
+                        1800,
+                        move || async move {
+                            Build::all_for_version(&database, r#type, &location, &version).await
+                        },
+                    )
+                    .await
+            } else {
+                Build::page_for_version(
+                    &state.database,
+                    r#type,
+                    &location,
+                    &version,
+                    &filter,
+                    fields.as_ref(),
                 )
-                .await;
+                .await
+            };
+            let mut data = match data {
+                Ok(data) => data,
+                Err(err) => {
+                    let (status, body): (StatusCode, axum::Json<serde_json::Value>) = err.into();
+
+                    return (status, HeaderMap::new(), body);
+                }
+            };
+
+            let mut next = None;
+            if let Some(limit) = limit
+                && data.len() as i64 > limit
+            {
+                data.truncate(limit as usize);
+                next = data.last().map(|build| build.id);
+            }
+
+            let mut headers = HeaderMap::new();
+            if let (Some(next), Some(limit)) = (next, limit) {
+                let mut query = vec![("limit", limit.to_string()), ("cursor", next.to_string())];
+                if let Some(experimental) = params.experimental {
+                    query.push(("experimental", experimental.to_string()));
+                }
+                if let Some(since) = params.since {
+                    query.push(("since", since.to_string()));
+                }
+                if let Some(fields) = &fields {
+                    query.push(("fields", fields.to_string()));
+                }
+
+                if let Some(link) = super::next_link(&state.env.app_url, r#type, &version, &query)
+                    && let Ok(link) = link.parse()
+                {
+                    headers.insert("Link", link);
+                }
+            }
 
             *request_data.lock().unwrap() = json!({
                 "type": "builds",
@@ -59,19 +232,24 @@ mod get {
                 }
             });
 
-            (
-                StatusCode::OK,
-                axum::Json(
-                    serde_json::to_value(&Response {
-                        success: true,
-                        builds: data,
-                    })
-                    .unwrap(),
-                ),
-            )
+            let mut response = serde_json::to_value(&Response {
+                success: true,
+                builds: data,
+                next,
+            })
+            .unwrap();
+
+            if let Some(fields) = &fields {
+                for build in response["builds"].as_array_mut().unwrap() {
+                    fields.project(build);
+                }
+            }
+
+            (StatusCode::OK, headers, axum::Json(response))
         } else {
             (
                 StatusCode::NOT_FOUND,
+                HeaderMap::new(),
                 axum::Json(ApiError::new(&["version not found"]).to_value()),
             )
         }
@@ -83,3 +261,27 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         .routes(routes!(get::route))
         .with_state(state.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::models::r#type::ServerType;
+
+    #[test]
+    fn next_link_encodes_the_version_and_query() {
+        assert_eq!(
+            super::next_link(
+                "https://mcjars.app/",
+                ServerType::Vanilla,
+                "1.21 Pre-Release 1",
+                &[
+                    ("limit", "100".to_string()),
+                    ("fields", "id,buildNumber".to_string())
+                ],
+            )
+            .as_deref(),
+            Some(
+                "<https://mcjars.app/api/v2/builds/VANILLA/1.21%20Pre-Release%201?limit=100&fields=id%2CbuildNumber>; rel=\"next\""
+            )
+        );
+    }
+}
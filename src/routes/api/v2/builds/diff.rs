@@ -0,0 +1,178 @@
+use super::State;
+use utoipa_axum::{router::OpenApiRouter, routes};
+
+mod get {
+    use crate::{
+        models::build::{
+            Build, BuildChange, InstallationStep, InstallationStepDownload, JavaChange,
+        },
+        routes::{ApiError, GetState},
+    };
+    use axum::{extract::Path, http::StatusCode};
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct ChangedFile {
+        #[schema(inline)]
+        from: InstallationStepDownload,
+        #[schema(inline)]
+        to: InstallationStepDownload,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Files {
+        #[schema(inline)]
+        added: Vec<InstallationStepDownload>,
+        #[schema(inline)]
+        removed: Vec<InstallationStepDownload>,
+        #[schema(inline)]
+        changed: Vec<ChangedFile>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        from: Build,
+        to: Build,
+
+        #[schema(inline)]
+        changes: Vec<BuildChange>,
+        #[schema(inline)]
+        files: Files,
+        #[schema(inline)]
+        java: JavaChange,
+    }
+
+    fn downloads(build: &Build) -> IndexMap<String, InstallationStepDownload> {
+        build
+            .installation
+            .iter()
+            .flatten()
+            .filter_map(|step| match step {
+                InstallationStep::Download(step) => Some((step.file.clone(), step.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[utoipa::path(get, path = "/{a}/{b}", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "a",
+            description = "The build id or hash to diff from",
+            example = "1",
+        ),
+        (
+            "b",
+            description = "The build id or hash to diff to",
+            example = "b1f3eeac53355d9ba5cf19e36abe8b2a30278c0e60942f3d07ac9ac9e4564951",
+        ),
+    ))]
+    pub async fn route(
+        state: GetState,
+        Path((a, b)): Path<(String, String)>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let (from, _, from_version) =
+            match Build::by_v1_identifier(&state.database, &state.cache, &a, None).await {
+                Ok(Some(data)) => data,
+                Ok(None) => {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        axum::Json(ApiError::new(&["build a not found"]).to_value()),
+                    );
+                }
+                Err(err) => return err.into(),
+            };
+        let (to, _, to_version) =
+            match Build::by_v1_identifier(&state.database, &state.cache, &b, None).await {
+                Ok(Some(data)) => data,
+                Ok(None) => {
+                    return (
+                        StatusCode::NOT_FOUND,
+                        axum::Json(ApiError::new(&["build b not found"]).to_value()),
+                    );
+                }
+                Err(err) => return err.into(),
+            };
+
+        if from.r#type != to.r#type {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&["builds must be of the same type"]).to_value()),
+            );
+        }
+
+        if (from_version.created, from.build_number) > (to_version.created, to.build_number) {
+            return (
+                StatusCode::BAD_REQUEST,
+                axum::Json(ApiError::new(&["build a must not be newer than build b"]).to_value()),
+            );
+        }
+
+        let changes = match Build::all_between(&state.database, &from, &to).await {
+            Ok(changes) => changes.into_iter().map(BuildChange::from).collect(),
+            Err(err) => return err.into(),
+        };
+
+        let java = JavaChange::new(
+            from.version_id.as_ref().map(|_| from_version.java),
+            to.version_id.as_ref().map(|_| to_version.java),
+        );
+
+        let from_downloads = downloads(&from);
+        let mut to_downloads = downloads(&to);
+
+        let mut files = Files {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+
+        for (file, from_download) in from_downloads {
+            match to_downloads.shift_remove(&file) {
+                Some(to_download) => {
+                    if from_download.url != to_download.url
+                        || from_download.size != to_download.size
+                        || (from_download.sha256.is_some()
+                            && to_download.sha256.is_some()
+                            && from_download.sha256 != to_download.sha256)
+                    {
+                        files.changed.push(ChangedFile {
+                            from: from_download,
+                            to: to_download,
+                        });
+                    }
+                }
+                None => files.removed.push(from_download),
+            }
+        }
+
+        files.added.extend(to_downloads.into_values());
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    from,
+                    to,
+                    changes,
+                    files,
+                    java,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+pub fn router(state: &State) -> OpenApiRouter<State> {
+    OpenApiRouter::new()
+        .routes(routes!(get::route))
+        .with_state(state.clone())
+}
@@ -2,9 +2,11 @@ use super::State;
 use utoipa_axum::router::OpenApiRouter;
 
 mod _type_;
+mod diff;
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
+        .nest("/diff", diff::router(state))
         .nest("/{type}", _type_::router(state))
         .with_state(state.clone())
 }
@@ -3,15 +3,24 @@ use utoipa_axum::{router::OpenApiRouter, routes};
 
 mod post {
     use crate::{
-        models::{BaseModel, build::Build, config::Config, r#type::ServerType},
+        models::{
+            build::Build,
+            config::{Config, ConfigDiff},
+            r#type::ServerType,
+        },
         routes::{ApiError, GetState},
     };
-    use axum::http::StatusCode;
+    use axum::{extract::Query, http::StatusCode};
     use serde::{Deserialize, Serialize};
     use sha1::Digest;
-    use sqlx::Row;
     use utoipa::ToSchema;
 
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Params {
+        #[serde(default)]
+        diff: bool,
+    }
+
     #[derive(ToSchema, Serialize, Deserialize)]
     pub struct Payload {
         file: String,
@@ -32,14 +41,26 @@ mod post {
 
         #[schema(inline)]
         configs: Vec<Result>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schema(inline)]
+        diff: Option<ConfigDiff>,
     }
 
     #[utoipa::path(post, path = "/", responses(
         (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
         (status = NOT_FOUND, body = inline(ApiError)),
+    ), params(
+        (
+            "diff" = bool,
+            Query,
+            description = "Whether to diff the config against the most similar default",
+            example = "true",
+        ),
     ), request_body = inline(Payload))]
     pub async fn route(
         state: GetState,
+        Query(params): Query<Params>,
         axum::Json(data): axum::Json<Payload>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
         let config = Config::by_alias(&data.file);
@@ -66,89 +87,42 @@ mod post {
         hash.update(&data.file);
         let hash = format!("{:x}", hash.finalize());
 
-        let configs = state
+        let database = state.database.clone();
+        let similar = formatted.clone();
+
+        let configs = match state
             .cache
-            .cached(
-                &format!("config::{}", hash),
-                10800,
-                || async {
-                    let data = if let Some(contains) = contains {
-                        sqlx::query(&format!(
-                            r#"
-                            SELECT
-                                {},
-                                config_values.value
-                            FROM build_configs
-                            INNER JOIN config_values ON config_values.id = build_configs.config_value_id
-                            INNER JOIN configs ON configs.id = config_values.config_id
-                            INNER JOIN builds ON
-                                builds.id = build_configs.build_id
-                                AND builds.type = $1::server_type
-                            WHERE
-                                configs.type = $1::server_type
-                                AND configs.format = $2::format
-                                AND configs.location = $3
-                                AND config_values.value LIKE '%' || $4 || '%'
-                            GROUP BY config_values.id, builds.id
-                            LIMIT 3
-                            "#,
-                            Build::columns_sql(None, None)
-                        ))
-                        .bind(config.r#type.to_string())
-                        .bind(config.format.to_string())
-                        .bind(config.aliases[0].clone())
-                        .bind(contains)
-                        .fetch_all(state.database.read())
-                        .await
-                        .unwrap()
-                    } else {
-                        sqlx::query(&format!(
-                            r#"
-                            SELECT
-                                {},
-                                config_values.value,
-                                SIMILARITY(config_values.value, $4) AS similarity
-                            FROM build_configs
-                            INNER JOIN config_values ON config_values.id = build_configs.config_value_id
-                            INNER JOIN configs ON configs.id = config_values.config_id
-                            INNER JOIN builds ON
-                                builds.id = build_configs.build_id
-                                AND builds.type = $1::server_type
-                            WHERE
-                                configs.type = $1::server_type
-                                AND configs.format = $2::format
-                                AND configs.location = $3
-                            ORDER BY similarity DESC
-                            LIMIT 3
-                            "#,
-                            Build::columns_sql(None, None)
-                        ))
-                        .bind(config.r#type.to_string())
-                        .bind(config.format.to_string())
-                        .bind(config.aliases[0].clone())
-                        .bind(&formatted)
-                        .fetch_all(state.database.read())
-                        .await
-                        .unwrap()
-                    };
-
-                    let mut results = vec![];
-
-                    for row in data {
-                        let build = Build::map(None, &row);
-                        let value: String = row.get("value");
-
-                        results.push(Result {
-                            from: build.r#type,
-                            value,
-                            build: Some(build),
-                        });
-                    }
+            .cached(&format!("config::{}", hash), 10800, move || async move {
+                Ok(Config::similar(&database, config, &similar, contains)
+                    .await?
+                    .into_iter()
+                    .map(|(build, value)| Result {
+                        from: build.r#type,
+                        value,
+                        build: Some(build),
+                    })
+                    .collect::<Vec<_>>())
+            })
+            .await
+        {
+            Ok(configs) => configs,
+            Err(err) => return err.into(),
+        };
 
-                    results
-                },
-            )
-            .await;
+        let diff = match configs.first() {
+            Some(result) if params.diff => {
+                match Config::diff(config.format, &result.value, &formatted) {
+                    Ok(diff) => Some(diff),
+                    Err(_) => {
+                        return (
+                            StatusCode::BAD_REQUEST,
+                            axum::Json(ApiError::new(&["unable to diff config"]).to_value()),
+                        );
+                    }
+                }
+            }
+            _ => None,
+        };
 
         (
             StatusCode::OK,
@@ -157,15 +131,537 @@ mod post {
                     success: true,
                     formatted,
                     configs,
+                    diff,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+mod migrate {
+    use crate::{
+        models::{
+            build::Build,
+            config::{Config, ConfigDiffEntry, ConfigRename},
+        },
+        routes::{ApiError, GetState},
+    };
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        file: String,
+        config: String,
+        build: i32,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        from: Option<Build>,
+        config: String,
+
+        carried: Vec<String>,
+        #[schema(inline)]
+        renamed: Vec<ConfigRename>,
+        #[schema(inline)]
+        dropped: Vec<ConfigDiffEntry>,
+        cleared: Vec<String>,
+    }
+
+    #[utoipa::path(post, path = "/migrate", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let config = match Config::by_alias(&data.file) {
+            Some(config) => config,
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["config not found"]).to_value()),
+                );
+            }
+        };
+
+        let (formatted, contains) = match Config::format(&data.file, &data.config) {
+            Ok((formatted, contains)) => (formatted, contains),
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&["unable to format config"]).to_value()),
+                );
+            }
+        };
+
+        let target = match Config::value_for_build(&state.database, config, data.build).await {
+            Ok(Some(target)) => target,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["config not found for build"]).to_value()),
+                );
+            }
+            Err(err) => return err.into(),
+        };
+
+        let source = match Config::similar(&state.database, config, &formatted, contains).await {
+            Ok(similar) => similar.into_iter().next(),
+            Err(err) => return err.into(),
+        };
+
+        let (merged, migration) = match config.migrate(
+            source.as_ref().map(|(_, value)| value.as_str()),
+            &data.config,
+            &target,
+        ) {
+            Ok(data) => data,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&["unable to migrate config"]).to_value()),
+                );
+            }
+        };
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    from: source.map(|(build, _)| build),
+                    config: merged,
+                    carried: migration.carried,
+                    renamed: migration.renamed,
+                    dropped: migration.dropped,
+                    cleared: migration.cleared,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+mod validate {
+    use crate::{
+        models::config::{Config, ConfigDiffEntry, ConfigTypeMismatch},
+        routes::{ApiError, GetState},
+    };
+    use axum::http::StatusCode;
+    use serde::{Deserialize, Serialize};
+    use utoipa::ToSchema;
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    pub struct Payload {
+        file: String,
+        config: String,
+        build: i32,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+        valid: bool,
+
+        #[schema(inline)]
+        unknown: Vec<ConfigDiffEntry>,
+        #[schema(inline)]
+        mismatched: Vec<ConfigTypeMismatch>,
+        #[schema(inline)]
+        missing: Vec<ConfigDiffEntry>,
+    }
+
+    #[utoipa::path(post, path = "/validate", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), request_body = inline(Payload))]
+    pub async fn route(
+        state: GetState,
+        axum::Json(data): axum::Json<Payload>,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let config = match Config::by_alias(&data.file) {
+            Some(config) => config,
+            None => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["config not found"]).to_value()),
+                );
+            }
+        };
+
+        let default = match Config::value_for_build(&state.database, config, data.build).await {
+            Ok(Some(default)) => default,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    axum::Json(ApiError::new(&["config not found for build"]).to_value()),
+                );
+            }
+            Err(err) => return err.into(),
+        };
+
+        let validation = match config.validate(&default, &data.config) {
+            Ok(validation) => validation,
+            Err(_) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&["unable to parse config"]).to_value()),
+                );
+            }
+        };
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    valid: validation.unknown.is_empty() && validation.mismatched.is_empty(),
+                    unknown: validation.unknown,
+                    mismatched: validation.mismatched,
+                    missing: validation.missing,
+                })
+                .unwrap(),
+            ),
+        )
+    }
+}
+
+mod identify {
+    use crate::{
+        database::DatabaseError,
+        models::{build::Build, config::Config, r#type::ServerType},
+        routes::{ApiError, GetState},
+    };
+    use axum::{body::Bytes, http::StatusCode};
+    use indexmap::IndexMap;
+    use serde::{Deserialize, Serialize};
+    use sha1::Digest;
+    use std::io::Read;
+    use utoipa::ToSchema;
+
+    const MAX_ENTRIES: usize = 4096;
+    const MAX_FILE_SIZE: u64 = 1024 * 1024;
+    const MAX_ARCHIVE_SIZE: u64 = 64 * 1024 * 1024;
+
+    #[derive(ToSchema)]
+    #[schema(value_type = String, format = Binary)]
+    #[allow(dead_code)]
+    struct Archive(Vec<u8>);
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct File {
+        file: String,
+        from: ServerType,
+        builds: Vec<Build>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Guess {
+        r#type: ServerType,
+        version: Option<String>,
+        score: u32,
+        files: Vec<String>,
+    }
+
+    #[derive(ToSchema, Serialize, Deserialize)]
+    struct Response {
+        success: bool,
+
+        #[schema(inline)]
+        guesses: Vec<Guess>,
+        #[schema(inline)]
+        files: Vec<File>,
+    }
+
+    fn read_entry(
+        files: &mut IndexMap<&'static str, String>,
+        path: &str,
+        size: u64,
+        reader: impl Read,
+        remaining: &mut u64,
+    ) -> Result<(), String> {
+        let (location, _) = match Config::by_path(path) {
+            Some(config) => config,
+            None => return Ok(()),
+        };
+
+        if files.contains_key(location) {
+            return Ok(());
+        }
+
+        if size > MAX_FILE_SIZE {
+            return Err(format!("{} is too large", path));
+        }
+
+        if size > *remaining {
+            return Err("archive is too large".to_string());
+        }
+
+        let mut content = String::new();
+        reader
+            .take(MAX_FILE_SIZE.min(*remaining))
+            .read_to_string(&mut content)
+            .map_err(|_| format!("unable to read {}", path))?;
+
+        *remaining -= content.len() as u64;
+        files.insert(location, content);
+
+        Ok(())
+    }
+
+    fn read_archive(data: &[u8]) -> Result<IndexMap<&'static str, String>, String> {
+        let mut files = IndexMap::new();
+        let mut remaining = MAX_ARCHIVE_SIZE;
+
+        if data.starts_with(b"PK\x03\x04") {
+            let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data))
+                .map_err(|_| "invalid zip archive".to_string())?;
+
+            if archive.len() > MAX_ENTRIES {
+                return Err("archive contains too many entries".to_string());
+            }
+
+            for i in 0..archive.len() {
+                let entry = archive
+                    .by_index(i)
+                    .map_err(|_| "invalid zip archive".to_string())?;
+
+                if !entry.is_file() {
+                    continue;
+                }
+
+                let path = entry.name().to_string();
+                let size = entry.size();
+
+                read_entry(&mut files, &path, size, entry, &mut remaining)?;
+            }
+        } else {
+            let reader: Box<dyn Read> = if data.starts_with(&[0x1f, 0x8b]) {
+                Box::new(flate2::read::GzDecoder::new(data))
+            } else {
+                Box::new(data)
+            };
+
+            let mut archive = tar::Archive::new(reader.take(MAX_ARCHIVE_SIZE));
+            let result = archive
+                .entries()
+                .map_err(|_| "invalid tar archive".to_string())
+                .and_then(|entries| {
+                    for (i, entry) in entries.enumerate() {
+                        if i >= MAX_ENTRIES {
+                            return Err("archive contains too many entries".to_string());
+                        }
+
+                        let entry = entry.map_err(|_| "invalid tar archive".to_string())?;
+                        if !entry.header().entry_type().is_file() {
+                            continue;
+                        }
+
+                        let path = entry
+                            .path()
+                            .map_err(|_| "invalid tar archive".to_string())?
+                            .to_string_lossy()
+                            .to_string();
+                        let size = entry.size();
+
+                        read_entry(&mut files, &path, size, entry, &mut remaining)?;
+                    }
+
+                    Ok(())
+                });
+
+            if archive.into_inner().limit() == 0 {
+                return Err("archive is too large".to_string());
+            }
+
+            result?;
+        }
+
+        Ok(files)
+    }
+
+    #[utoipa::path(post, path = "/identify", responses(
+        (status = OK, body = inline(Response)),
+        (status = BAD_REQUEST, body = inline(ApiError)),
+        (status = NOT_FOUND, body = inline(ApiError)),
+    ), request_body(content = inline(Archive), content_type = "application/octet-stream"))]
+    pub async fn route(
+        state: GetState,
+        archive: Bytes,
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let files = match tokio::task::spawn_blocking(move || read_archive(&archive))
+            .await
+            .unwrap()
+        {
+            Ok(files) => files,
+            Err(err) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    axum::Json(ApiError::new(&[&err]).to_value()),
+                );
+            }
+        };
+
+        if files.is_empty() {
+            return (
+                StatusCode::NOT_FOUND,
+                axum::Json(ApiError::new(&["no recognized configs found"]).to_value()),
+            );
+        }
+
+        let files = futures_util::future::join_all(files.into_iter().map(|(location, content)| {
+            let state = state.clone();
+
+            async move {
+                let Some(config) = Config::by_alias(location) else {
+                    return Ok(None);
+                };
+                let Ok((formatted, contains)) = Config::format(location, &content) else {
+                    return Ok(None);
+                };
+
+                let mut hash = sha1::Sha1::new();
+                hash.update(&content);
+                hash.update(location);
+                let hash = format!("{:x}", hash.finalize());
+
+                let database = state.database.clone();
+
+                let builds = state
+                    .cache
+                    .cached(
+                        &format!("config::identify::{}", hash),
+                        10800,
+                        move || async move {
+                            Ok(Config::similar(&database, config, &formatted, contains)
+                                .await?
+                                .into_iter()
+                                .map(|(build, _)| build)
+                                .collect::<Vec<_>>())
+                        },
+                    )
+                    .await?;
+
+                Ok(Some(File {
+                    file: location.to_string(),
+                    from: config.r#type,
+                    builds,
+                }))
+            }
+        }))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, DatabaseError>>();
+        let files = match files {
+            Ok(files) => files.into_iter().flatten().collect::<Vec<_>>(),
+            Err(err) => return err.into(),
+        };
+
+        let mut guesses: IndexMap<(ServerType, Option<String>), Guess> = IndexMap::new();
+
+        for file in &files {
+            for (i, build) in file.builds.iter().enumerate() {
+                let guess = guesses
+                    .entry((build.r#type, build.version_id.clone()))
+                    .or_insert_with(|| Guess {
+                        r#type: build.r#type,
+                        version: build.version_id.clone(),
+                        score: 0,
+                        files: Vec::new(),
+                    });
+
+                guess.score += (file.builds.len() - i) as u32;
+                if !guess.files.contains(&file.file) {
+                    guess.files.push(file.file.clone());
+                }
+            }
+        }
+
+        let mut guesses = guesses.into_values().collect::<Vec<_>>();
+        guesses.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| b.files.len().cmp(&a.files.len()))
+        });
+
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    guesses,
+                    files,
                 })
                 .unwrap(),
             ),
         )
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{MAX_ARCHIVE_SIZE, read_archive};
+        use std::io::Write;
+
+        fn tar_gz(entries: &[(&str, &[u8])]) -> Vec<u8> {
+            let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+                Vec::new(),
+                flate2::Compression::fast(),
+            ));
+
+            for (path, content) in entries {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(content.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+
+                builder.append_data(&mut header, path, *content).unwrap();
+            }
+
+            let mut encoder = builder.into_inner().unwrap();
+            encoder.flush().unwrap();
+
+            encoder.finish().unwrap()
+        }
+
+        #[test]
+        fn reads_configs_from_tar_gz() {
+            let files = read_archive(&tar_gz(&[
+                ("world/level.dat", b"\0"),
+                ("server.properties", b"motd=Hello\n"),
+            ]))
+            .unwrap();
+
+            assert_eq!(files.get("server.properties").unwrap(), "motd=Hello\n");
+        }
+
+        #[test]
+        fn rejects_archives_over_the_decompressed_limit() {
+            let padding = vec![0; MAX_ARCHIVE_SIZE as usize];
+
+            assert_eq!(
+                read_archive(&tar_gz(&[
+                    ("padding.bin", &padding),
+                    ("server.properties", b"motd=Hello\n"),
+                ])),
+                Err("archive is too large".to_string())
+            );
+        }
+    }
 }
 
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .routes(routes!(post::route))
+        .routes(routes!(migrate::route))
+        .routes(routes!(validate::route))
+        .routes(routes!(identify::route))
         .with_state(state.clone())
 }
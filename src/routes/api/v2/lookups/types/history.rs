@@ -71,12 +71,14 @@ mod get {
             next_month.pred_opt().unwrap()
         };
 
-        let types = state
+        let database = state.database.clone();
+
+        let types = match state
             .cache
             .cached(
                 &format!("lookups::types::all::history::{}::{}", start, end),
                 10800,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -103,9 +105,8 @@ mod get {
                     )
                     .bind(start)
                     .bind(end)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut types = IndexMap::new();
                     for row in &data {
@@ -134,10 +135,14 @@ mod get {
                         entry.unique_ips = row.get("unique_ips");
                     }
 
-                    types
+                    Ok(types)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(types) => types,
+            Err(err) => return err.into(),
+        };
 
         (
             StatusCode::OK,
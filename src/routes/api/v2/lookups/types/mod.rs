@@ -5,6 +5,7 @@ mod history;
 
 mod get {
     use crate::routes::GetState;
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
@@ -28,10 +29,12 @@ mod get {
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
     ))]
-    pub async fn route(state: GetState) -> axum::Json<serde_json::Value> {
-        let types = state
+    pub async fn route(state: GetState) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let types = match state
             .cache
-            .cached("lookups::types::all", 10800, || async {
+            .cached("lookups::types::all", 10800, move || async move {
                 let data = sqlx::query(
                     r#"
                     SELECT
@@ -54,9 +57,8 @@ mod get {
                     ORDER BY total DESC
                     "#,
                 )
-                .fetch_all(state.database.read())
-                .await
-                .unwrap();
+                .fetch_all(database.read())
+                .await?;
 
                 let mut types = IndexMap::new();
 
@@ -70,16 +72,23 @@ mod get {
                     );
                 }
 
-                types
+                Ok(types)
             })
-            .await;
+            .await
+        {
+            Ok(types) => types,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                types,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    types,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
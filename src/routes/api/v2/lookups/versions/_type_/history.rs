@@ -79,12 +79,14 @@ mod get {
             next_month.pred_opt().unwrap()
         };
 
-        let versions = state
+        let database = state.database.clone();
+
+        let versions = match state
             .cache
             .cached(
                 &format!("lookups::versions::{}::history::{}::{}", r#type, start, end),
                 10800,
-                || async {
+                move || async move {
                     let column = if SERVER_TYPES_WITH_PROJECT_AS_IDENTIFIER.contains(&r#type) {
                         "project_version"
                     } else {
@@ -113,9 +115,8 @@ mod get {
                     .bind(r#type.to_string())
                     .bind(start)
                     .bind(end)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut versions = IndexMap::new();
                     for row in &data {
@@ -144,10 +145,14 @@ mod get {
                         entry.unique_ips = row.get("unique_ips");
                     }
 
-                    versions
+                    Ok(versions)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(versions) => versions,
+            Err(err) => return err.into(),
+        };
 
         (
             StatusCode::OK,
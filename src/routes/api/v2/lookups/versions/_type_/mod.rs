@@ -9,6 +9,7 @@ mod get {
         routes::GetState,
     };
     use axum::extract::Path;
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
@@ -41,18 +42,23 @@ mod get {
     pub async fn route(
         state: GetState,
         Path(r#type): Path<ServerType>,
-    ) -> axum::Json<serde_json::Value> {
-        let versions = state
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let versions = match state
             .cache
-            .cached(&format!("lookups::versions::{}", r#type), 10800, || async {
-                let column = if SERVER_TYPES_WITH_PROJECT_AS_IDENTIFIER.contains(&r#type) {
-                    "project_version"
-                } else {
-                    "version"
-                };
+            .cached(
+                &format!("lookups::versions::{}", r#type),
+                10800,
+                move || async move {
+                    let column = if SERVER_TYPES_WITH_PROJECT_AS_IDENTIFIER.contains(&r#type) {
+                        "project_version"
+                    } else {
+                        "version"
+                    };
 
-                let data = sqlx::query(&format!(
-                    r#"
+                    let data = sqlx::query(&format!(
+                        r#"
                     SELECT
                         build_{}_id AS version,
                         SUM(total_requests)::bigint AS total,
@@ -65,35 +71,42 @@ mod get {
                     GROUP BY build_{}_id
                     ORDER BY total DESC
                     "#,
-                    column, column, column
-                ))
-                .bind(r#type.to_string())
-                .fetch_all(state.database.read())
-                .await
-                .unwrap();
+                        column, column, column
+                    ))
+                    .bind(r#type.to_string())
+                    .fetch_all(database.read())
+                    .await?;
 
-                let mut versions = IndexMap::new();
+                    let mut versions = IndexMap::new();
 
-                for row in data {
-                    versions.insert(
-                        row.get("version"),
-                        VersionStats {
-                            total: row.get("total"),
-                            unique_ips: row.get("unique_ips"),
-                        },
-                    );
-                }
+                    for row in data {
+                        versions.insert(
+                            row.get("version"),
+                            VersionStats {
+                                total: row.get("total"),
+                                unique_ips: row.get("unique_ips"),
+                            },
+                        );
+                    }
 
-                versions
-            })
-            .await;
+                    Ok(versions)
+                },
+            )
+            .await
+        {
+            Ok(versions) => versions,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                versions,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    versions,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
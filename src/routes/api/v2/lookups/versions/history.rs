@@ -71,12 +71,14 @@ mod get {
             next_month.pred_opt().unwrap()
         };
 
-        let versions = state
+        let database = state.database.clone();
+
+        let versions = match state
             .cache
             .cached(
                 &format!("lookups::versions::all::history::{}::{}", start, end),
                 10800,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -96,9 +98,8 @@ mod get {
                     )
                     .bind(start)
                     .bind(end)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut versions = IndexMap::new();
                     for row in &data {
@@ -127,10 +128,14 @@ mod get {
                         entry.unique_ips = row.get("unique_ips");
                     }
 
-                    versions
+                    Ok(versions)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(versions) => versions,
+            Err(err) => return err.into(),
+        };
 
         (
             StatusCode::OK,
@@ -6,6 +6,7 @@ mod history;
 
 mod get {
     use crate::routes::GetState;
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
@@ -29,10 +30,12 @@ mod get {
     #[utoipa::path(get, path = "/", responses(
         (status = OK, body = inline(Response)),
     ))]
-    pub async fn route(state: GetState) -> axum::Json<serde_json::Value> {
-        let versions = state
+    pub async fn route(state: GetState) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let versions = match state
             .cache
-            .cached("lookups::versions::all", 10800, || async {
+            .cached("lookups::versions::all", 10800, move || async move {
                 let data = sqlx::query(
                     r#"
                     SELECT
@@ -47,9 +50,8 @@ mod get {
                     ORDER BY total DESC
                     "#,
                 )
-                .fetch_all(state.database.read())
-                .await
-                .unwrap();
+                .fetch_all(database.read())
+                .await?;
 
                 let mut versions = IndexMap::new();
 
@@ -63,16 +65,23 @@ mod get {
                     );
                 }
 
-                versions
+                Ok(versions)
             })
-            .await;
+            .await
+        {
+            Ok(versions) => versions,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                versions,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    versions,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
@@ -13,11 +13,6 @@ mod get {
     use sqlx::Row;
     use utoipa::ToSchema;
 
-    #[derive(sqlx::FromRow, Serialize, Deserialize)]
-    struct Id {
-        id: String,
-    }
-
     #[derive(ToSchema, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct TypeStats {
@@ -94,12 +89,14 @@ mod get {
             next_month.pred_opt().unwrap()
         };
 
-        let requests = state
+        let database = state.database.clone();
+
+        let requests = match state
             .cache
             .cached(
                 &format!("requests::types::{}::history::{}::{}", r#type, start, end),
                 10800,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -120,9 +117,8 @@ mod get {
                     .bind(r#type.to_string())
                     .bind(start)
                     .bind(end)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut requests: Vec<Requests> = Vec::with_capacity(end.day() as usize);
 
@@ -165,10 +161,14 @@ mod get {
                         }
                     }
 
-                    requests
+                    Ok(requests)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(requests) => requests,
+            Err(err) => return err.into(),
+        };
 
         (
             StatusCode::OK,
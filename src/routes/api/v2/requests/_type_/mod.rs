@@ -6,16 +6,12 @@ mod history;
 mod get {
     use crate::{models::r#type::ServerType, routes::GetState};
     use axum::extract::Path;
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use utoipa::ToSchema;
 
-    #[derive(sqlx::FromRow, Serialize, Deserialize)]
-    struct Id {
-        id: String,
-    }
-
     #[derive(ToSchema, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
     struct TypeStats {
@@ -52,12 +48,17 @@ mod get {
     pub async fn route(
         state: GetState,
         Path(r#type): Path<ServerType>,
-    ) -> axum::Json<serde_json::Value> {
-        let requests = state
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let requests = match state
             .cache
-            .cached(&format!("requests::types::{}", r#type), 10800, || async {
-                let data = sqlx::query(
-                    r#"
+            .cached(
+                &format!("requests::types::{}", r#type),
+                10800,
+                move || async move {
+                    let data = sqlx::query(
+                        r#"
                     SELECT
                         search_version AS version,
                         SUM(total_requests)::bigint AS total,
@@ -69,49 +70,56 @@ mod get {
                     GROUP BY search_version
                     ORDER BY total DESC
                     "#,
-                )
-                .bind(r#type.to_string())
-                .fetch_all(state.database.read())
-                .await
-                .unwrap();
+                    )
+                    .bind(r#type.to_string())
+                    .fetch_all(database.read())
+                    .await?;
 
-                let mut requests = Requests {
-                    root: TypeStats {
-                        total: 0,
-                        unique_ips: 0,
-                    },
-                    versions: IndexMap::new(),
-                };
+                    let mut requests = Requests {
+                        root: TypeStats {
+                            total: 0,
+                            unique_ips: 0,
+                        },
+                        versions: IndexMap::new(),
+                    };
 
-                for row in data {
-                    let version = row.get::<Option<String>, _>("version");
+                    for row in data {
+                        let version = row.get::<Option<String>, _>("version");
 
-                    if let Some(version) = version {
-                        requests.versions.insert(
-                            version,
-                            TypeStats {
+                        if let Some(version) = version {
+                            requests.versions.insert(
+                                version,
+                                TypeStats {
+                                    total: row.get("total"),
+                                    unique_ips: row.get("unique_ips"),
+                                },
+                            );
+                        } else {
+                            requests.root = TypeStats {
                                 total: row.get("total"),
                                 unique_ips: row.get("unique_ips"),
-                            },
-                        );
-                    } else {
-                        requests.root = TypeStats {
-                            total: row.get("total"),
-                            unique_ips: row.get("unique_ips"),
-                        };
+                            };
+                        }
                     }
-                }
 
-                requests
-            })
-            .await;
+                    Ok(requests)
+                },
+            )
+            .await
+        {
+            Ok(requests) => requests,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                requests,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    requests,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
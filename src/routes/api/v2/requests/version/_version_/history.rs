@@ -76,7 +76,9 @@ mod get {
             next_month.pred_opt().unwrap()
         };
 
-        let requests = state
+        let database = state.database.clone();
+
+        let requests = match state
             .cache
             .cached(
                 &format!(
@@ -84,7 +86,7 @@ mod get {
                     version, start, end
                 ),
                 10800,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -105,9 +107,8 @@ mod get {
                     .bind(version)
                     .bind(start)
                     .bind(end)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut requests = IndexMap::new();
                     for row in &data {
@@ -136,10 +137,14 @@ mod get {
                         entry.unique_ips = row.get("unique_ips");
                     }
 
-                    requests
+                    Ok(requests)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(requests) => requests,
+            Err(err) => return err.into(),
+        };
 
         (
             StatusCode::OK,
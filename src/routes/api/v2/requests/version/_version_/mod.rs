@@ -6,6 +6,7 @@ mod history;
 mod get {
     use crate::routes::GetState;
     use axum::extract::Path;
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
@@ -38,13 +39,15 @@ mod get {
     pub async fn route(
         state: GetState,
         Path(version): Path<String>,
-    ) -> axum::Json<serde_json::Value> {
-        let requests = state
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let requests = match state
             .cache
             .cached(
                 &format!("requests::versions::{}", version),
                 10800,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -60,9 +63,8 @@ mod get {
                         "#,
                     )
                     .bind(version)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut requests = IndexMap::new();
 
@@ -76,17 +78,24 @@ mod get {
                         );
                     }
 
-                    requests
+                    Ok(requests)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(requests) => requests,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                requests,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    requests,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
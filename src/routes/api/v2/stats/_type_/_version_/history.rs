@@ -95,7 +95,11 @@ mod get {
             );
         }
 
-        let location = Version::location(&state.database, &state.cache, r#type, &version).await;
+        let location =
+            match Version::location(&state.database, &state.cache, r#type, &version).await {
+                Ok(location) => location,
+                Err(err) => return err.into(),
+            };
 
         if let Some(location) = location {
             let start = chrono::NaiveDate::from_ymd_opt(year as i32, month as u32, 1).unwrap();
@@ -109,7 +113,9 @@ mod get {
                 next_month.pred_opt().unwrap()
             };
 
-            let stats = state
+            let database = state.database.clone();
+
+            let stats = match state
                 .cache
                 .cached(
                     &format!(
@@ -117,7 +123,7 @@ mod get {
                         r#type, version, start, end
                     ),
                     10800,
-                    || async {
+                    move || async move {
                         let data = sqlx::query(&format!(
                             r#"
                             SELECT
@@ -147,9 +153,8 @@ mod get {
                         .bind(version)
                         .bind(start)
                         .bind(end)
-                        .fetch_all(state.database.read())
-                        .await
-                        .unwrap();
+                        .fetch_all(database.read())
+                        .await?;
 
                         let mut stats = Vec::with_capacity(end.day() as usize);
 
@@ -174,10 +179,14 @@ mod get {
                             entry.size.average.zip = row.try_get("zip_average").unwrap_or_default();
                         }
 
-                        stats
+                        Ok(stats)
                     },
                 )
-                .await;
+                .await
+            {
+                Ok(stats) => stats,
+                Err(err) => return err.into(),
+            };
 
             (
                 StatusCode::OK,
@@ -69,15 +69,21 @@ mod get {
         state: GetState,
         Path((r#type, version)): Path<(ServerType, String)>,
     ) -> (StatusCode, axum::Json<serde_json::Value>) {
-        let location = Version::location(&state.database, &state.cache, r#type, &version).await;
+        let location =
+            match Version::location(&state.database, &state.cache, r#type, &version).await {
+                Ok(location) => location,
+                Err(err) => return err.into(),
+            };
 
         if let Some(location) = location {
-            let stats = state
+            let database = state.database.clone();
+
+            let stats = match state
                 .cache
                 .cached(
                     &format!("stats::types::{}::{}", r#type, version),
                     10800,
-                    || async {
+                    move || async move {
                         let data = sqlx::query(&format!(
                             r#"
                             SELECT
@@ -100,11 +106,10 @@ mod get {
                         ))
                         .bind(r#type.to_string())
                         .bind(version)
-                        .fetch_one(state.database.read())
-                        .await
-                        .unwrap();
+                        .fetch_one(database.read())
+                        .await?;
 
-                        Stats {
+                        Ok(Stats {
                             buids: data.get("builds"),
                             size: Size {
                                 total: TotalStats {
@@ -116,10 +121,14 @@ mod get {
                                     zip: data.try_get("zip_average").unwrap_or_default(),
                                 },
                             },
-                        }
+                        })
                     },
                 )
-                .await;
+                .await
+            {
+                Ok(stats) => stats,
+                Err(err) => return err.into(),
+            };
 
             (
                 StatusCode::OK,
@@ -7,6 +7,7 @@ mod history;
 mod get {
     use crate::{models::r#type::ServerType, routes::GetState};
     use axum::extract::Path;
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use utoipa::ToSchema;
@@ -60,12 +61,17 @@ mod get {
     pub async fn route(
         state: GetState,
         Path(r#type): Path<ServerType>,
-    ) -> axum::Json<serde_json::Value> {
-        let stats = state
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let stats = match state
             .cache
-            .cached(&format!("stats::types::{}::all", r#type), 10800, || async {
-                let data = sqlx::query(&format!(
-                    r#"
+            .cached(
+                &format!("stats::types::{}::all", r#type),
+                10800,
+                move || async move {
+                    let data = sqlx::query(&format!(
+                        r#"
                     SELECT
                         COUNT(*) AS builds,
                         SUM({} builds.jar_size) AS jar_total,
@@ -75,39 +81,46 @@ mod get {
                     FROM builds
                     WHERE builds.type = $1::server_type
                     "#,
-                    if r#type == ServerType::Fabric {
-                        ""
-                    } else {
-                        "DISTINCT"
-                    }
-                ))
-                .bind(r#type.to_string())
-                .fetch_one(state.database.read())
-                .await
-                .unwrap();
+                        if r#type == ServerType::Fabric {
+                            ""
+                        } else {
+                            "DISTINCT"
+                        }
+                    ))
+                    .bind(r#type.to_string())
+                    .fetch_one(database.read())
+                    .await?;
 
-                Stats {
-                    buids: data.get("builds"),
-                    size: Size {
-                        total: TotalStats {
-                            jar: data.try_get("jar_total").unwrap_or_default(),
-                            zip: data.try_get("zip_total").unwrap_or_default(),
-                        },
-                        average: AverageStats {
-                            jar: data.try_get("jar_average").unwrap_or_default(),
-                            zip: data.try_get("zip_average").unwrap_or_default(),
+                    Ok(Stats {
+                        buids: data.get("builds"),
+                        size: Size {
+                            total: TotalStats {
+                                jar: data.try_get("jar_total").unwrap_or_default(),
+                                zip: data.try_get("zip_total").unwrap_or_default(),
+                            },
+                            average: AverageStats {
+                                jar: data.try_get("jar_average").unwrap_or_default(),
+                                zip: data.try_get("zip_average").unwrap_or_default(),
+                            },
                         },
-                    },
-                }
-            })
-            .await;
+                    })
+                },
+            )
+            .await
+        {
+            Ok(stats) => stats,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                stats,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    stats,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
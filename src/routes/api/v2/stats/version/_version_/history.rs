@@ -97,12 +97,14 @@ mod get {
             next_month.pred_opt().unwrap()
         };
 
-        let stats = state
+        let database = state.database.clone();
+
+        let stats = match state
             .cache
             .cached(
                 &format!("stats::versions::{}::history::{}::{}", version, start, end),
                 10800,
-                || async {
+                move || async move {
                     let data = sqlx::query(
                         r#"
                         SELECT
@@ -124,9 +126,8 @@ mod get {
                     .bind(version)
                     .bind(start)
                     .bind(end)
-                    .fetch_all(state.database.read())
-                    .await
-                    .unwrap();
+                    .fetch_all(database.read())
+                    .await?;
 
                     let mut stats = Vec::with_capacity(end.day() as usize);
 
@@ -151,10 +152,14 @@ mod get {
                         entry.size.average.zip = row.try_get("zip_average").unwrap_or_default();
                     }
 
-                    stats
+                    Ok(stats)
                 },
             )
-            .await;
+            .await
+        {
+            Ok(stats) => stats,
+            Err(err) => return err.into(),
+        };
 
         (
             StatusCode::OK,
@@ -6,6 +6,7 @@ mod history;
 mod get {
     use crate::routes::GetState;
     use axum::extract::Path;
+    use axum::http::StatusCode;
     use serde::{Deserialize, Serialize};
     use sqlx::Row;
     use utoipa::ToSchema;
@@ -59,12 +60,17 @@ mod get {
     pub async fn route(
         state: GetState,
         Path(version): Path<String>,
-    ) -> axum::Json<serde_json::Value> {
-        let stats = state
+    ) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let database = state.database.clone();
+
+        let stats = match state
             .cache
-            .cached(&format!("stats::versions::{}", version), 10800, || async {
-                let data = sqlx::query(
-                    r#"
+            .cached(
+                &format!("stats::versions::{}", version),
+                10800,
+                move || async move {
+                    let data = sqlx::query(
+                        r#"
                     SELECT
                         COUNT(*) AS builds,
                         SUM(DISTINCT builds.jar_size) AS jar_total,
@@ -74,34 +80,41 @@ mod get {
                     FROM builds
                     WHERE builds.version_id = $1
                     "#,
-                )
-                .bind(version)
-                .fetch_one(state.database.read())
-                .await
-                .unwrap();
+                    )
+                    .bind(version)
+                    .fetch_one(database.read())
+                    .await?;
 
-                Stats {
-                    buids: data.get("builds"),
-                    size: Size {
-                        total: TotalStats {
-                            jar: data.try_get("jar_total").unwrap_or_default(),
-                            zip: data.try_get("zip_total").unwrap_or_default(),
-                        },
-                        average: AverageStats {
-                            jar: data.try_get("jar_average").unwrap_or_default(),
-                            zip: data.try_get("zip_average").unwrap_or_default(),
+                    Ok(Stats {
+                        buids: data.get("builds"),
+                        size: Size {
+                            total: TotalStats {
+                                jar: data.try_get("jar_total").unwrap_or_default(),
+                                zip: data.try_get("zip_total").unwrap_or_default(),
+                            },
+                            average: AverageStats {
+                                jar: data.try_get("jar_average").unwrap_or_default(),
+                                zip: data.try_get("zip_average").unwrap_or_default(),
+                            },
                         },
-                    },
-                }
-            })
-            .await;
+                    })
+                },
+            )
+            .await
+        {
+            Ok(stats) => stats,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                stats,
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    stats,
+                })
+                .unwrap(),
+            ),
         )
     }
 }
@@ -6,6 +6,7 @@ mod get {
         models::r#type::{ServerType, ServerTypeInfo},
         routes::{ApiError, GetState},
     };
+    use axum::http::StatusCode;
     use indexmap::IndexMap;
     use serde::{Deserialize, Serialize};
     use utoipa::ToSchema;
@@ -31,61 +32,67 @@ mod get {
         (status = OK, body = inline(Response)),
         (status = NOT_FOUND, body = inline(ApiError)),
     ))]
-    pub async fn route(state: GetState) -> axum::Json<serde_json::Value> {
-        let data = ServerType::all(&state.database, &state.cache).await;
+    pub async fn route(state: GetState) -> (StatusCode, axum::Json<serde_json::Value>) {
+        let data = match ServerType::all(&state.database, &state.cache).await {
+            Ok(data) => data,
+            Err(err) => return err.into(),
+        };
 
-        axum::Json(
-            serde_json::to_value(&Response {
-                success: true,
-                types: Types {
-                    recommended: ServerType::extract(
-                        &data,
-                        &[
-                            ServerType::Vanilla,
-                            ServerType::Paper,
-                            ServerType::Fabric,
-                            ServerType::Forge,
-                            ServerType::Neoforge,
-                            ServerType::Velocity,
-                        ],
-                    ),
-                    established: ServerType::extract(
-                        &data,
-                        &[
-                            ServerType::Purpur,
-                            ServerType::Pufferfish,
-                            ServerType::Sponge,
-                            ServerType::Spigot,
-                            ServerType::Bungeecord,
-                            ServerType::Waterfall,
-                        ],
-                    ),
-                    experimental: ServerType::extract(
-                        &data,
-                        &[
-                            ServerType::Folia,
-                            ServerType::Quilt,
-                            ServerType::Canvas,
-                            ServerType::Divinemc,
-                        ],
-                    ),
-                    miscellaneous: ServerType::extract(
-                        &data,
-                        &[
-                            ServerType::Arclight,
-                            ServerType::Mohist,
-                            ServerType::Leaves,
-                            ServerType::Aspaper,
-                            ServerType::LegacyFabric,
-                        ],
-                    ),
-                    limbos: ServerType::extract(
-                        &data,
-                        &[ServerType::LoohpLimbo, ServerType::Nanolimbo],
-                    ),
-                },
-            })
-            .unwrap(),
+        (
+            StatusCode::OK,
+            axum::Json(
+                serde_json::to_value(&Response {
+                    success: true,
+                    types: Types {
+                        recommended: ServerType::extract(
+                            &data,
+                            &[
+                                ServerType::Vanilla,
+                                ServerType::Paper,
+                                ServerType::Fabric,
+                                ServerType::Forge,
+                                ServerType::Neoforge,
+                                ServerType::Velocity,
+                            ],
+                        ),
+                        established: ServerType::extract(
+                            &data,
+                            &[
+                                ServerType::Purpur,
+                                ServerType::Pufferfish,
+                                ServerType::Sponge,
+                                ServerType::Spigot,
+                                ServerType::Bungeecord,
+                                ServerType::Waterfall,
+                            ],
+                        ),
+                        experimental: ServerType::extract(
+                            &data,
+                            &[
+                                ServerType::Folia,
+                                ServerType::Quilt,
+                                ServerType::Canvas,
+                                ServerType::Divinemc,
+                            ],
+                        ),
+                        miscellaneous: ServerType::extract(
+                            &data,
+                            &[
+                                ServerType::Arclight,
+                                ServerType::Mohist,
+                                ServerType::Leaves,
+                                ServerType::Aspaper,
+                                ServerType::LegacyFabric,
+                            ],
+                        ),
+                        limbos: ServerType::extract(
+                            &data,
+                            &[ServerType::LoohpLimbo, ServerType::Nanolimbo],
+                        ),
+                    },
+                })
+                .unwrap(),
+            ),
         )
     }
 }
@@ -12,7 +12,10 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
             "/",
             get(
                 |state: GetState, Path((r#type, version, identifier)): Path<(ServerType, String, String)>| async move {
-                    let build = Build::by_v1_identifier(&state.database, &state.cache, &identifier).await;
+                    let build = match Build::by_v1_identifier(&state.database, &state.cache, &identifier, None).await {
+                        Ok(build) => build,
+                        Err(err) => return crate::routes::index::database_error(err),
+                    };
 
                     if let Some((build, _, _)) = build {
                         let mut files = Vec::new();
@@ -41,6 +44,41 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
                                             href: Some("#".to_string()),
                                         });
                                     }
+                                    InstallationStep::Move(step) => {
+                                        files.push(IndexFile {
+                                            name: format!("move {} to {}", step.from, step.to),
+                                            size: "-".to_string(),
+                                            href: Some("#".to_string()),
+                                        });
+                                    }
+                                    InstallationStep::WriteFile(step) => {
+                                        files.push(IndexFile {
+                                            name: format!("write {}", step.file),
+                                            size: format!("{} bytes", step.content.len()),
+                                            href: Some("#".to_string()),
+                                        });
+                                    }
+                                    InstallationStep::Chmod(step) => {
+                                        files.push(IndexFile {
+                                            name: format!("chmod {} {}", step.mode, step.location),
+                                            size: "-".to_string(),
+                                            href: Some("#".to_string()),
+                                        });
+                                    }
+                                    InstallationStep::Extract(step) => {
+                                        files.push(IndexFile {
+                                            name: format!("extract {} in {}/", step.file, step.location),
+                                            size: "-".to_string(),
+                                            href: Some("#".to_string()),
+                                        });
+                                    }
+                                    InstallationStep::RunJava(step) => {
+                                        files.push(IndexFile {
+                                            name: format!("java -jar {} {}", step.file, step.arguments.join(" ")).trim_end().to_string(),
+                                            size: "-".to_string(),
+                                            href: Some("#".to_string()),
+                                        });
+                                    }
                                 }
                             }
                         }
@@ -12,16 +12,33 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
             get(
                 |state: GetState, Path((r#type, version)): Path<(ServerType, String)>| async move {
                     let location =
-                        Version::location(&state.database, &state.cache, r#type, &version).await;
+                        match Version::location(&state.database, &state.cache, r#type, &version)
+                            .await
+                        {
+                            Ok(location) => location,
+                            Err(err) => return crate::routes::index::database_error(err),
+                        };
 
                     let mut files = Vec::new();
                     if let Some(location) = location {
-                        let data = state
+                        let database = state.database.clone();
+                        let version = version.clone();
+
+                        let data = match state
                             .cache
-                            .cached(&format!("builds::{}::{}", r#type, version), 1800, || {
-                                Build::all_for_version(&state.database, r#type, &location, &version)
-                            })
-                            .await;
+                            .cached(
+                                &format!("builds::{}::{}", r#type, version),
+                                1800,
+                                move || async move {
+                                    Build::all_for_version(&database, r#type, &location, &version)
+                                        .await
+                                },
+                            )
+                            .await
+                        {
+                            Ok(data) => data,
+                            Err(err) => return crate::routes::index::database_error(err),
+                        };
 
                         files = data
                             .iter()
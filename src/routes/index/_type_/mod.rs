@@ -11,7 +11,10 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
             "/",
             get(
                 |state: GetState, Path(r#type): Path<ServerType>| async move {
-                    let versions = Version::all(&state.database, &state.cache, r#type).await;
+                    let versions = match Version::all(&state.database, &state.cache, r#type).await {
+                        Ok(versions) => versions,
+                        Err(err) => return crate::routes::index::database_error(err),
+                    };
 
                     let files = versions
                         .iter()
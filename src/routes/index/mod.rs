@@ -1,5 +1,5 @@
 use super::{GetState, State};
-use crate::models::r#type::ServerType;
+use crate::{database::DatabaseError, models::r#type::ServerType};
 use axum::{http::Response, routing::get};
 use utoipa_axum::router::OpenApiRouter;
 
@@ -51,12 +51,26 @@ pub fn render(state: &State, location: &str, files: &[IndexFile]) -> Response<St
         .unwrap()
 }
 
+pub fn database_error(err: DatabaseError) -> Response<String> {
+    err.report();
+
+    Response::builder()
+        .status(err.status())
+        .header("Content-Type", "text/plain")
+        .header("Cache-Control", "no-cache")
+        .body(err.message().to_string())
+        .unwrap()
+}
+
 pub fn router(state: &State) -> OpenApiRouter<State> {
     OpenApiRouter::new()
         .route(
             "/",
             get(|state: GetState| async move {
-                let types = ServerType::all(&state.database, &state.cache).await;
+                let types = match ServerType::all(&state.database, &state.cache).await {
+                    Ok(types) => types,
+                    Err(err) => return database_error(err),
+                };
 
                 let files = types
                     .iter()
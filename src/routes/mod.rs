@@ -36,6 +36,17 @@ impl<'a> ApiError<'a> {
     }
 }
 
+impl From<crate::database::DatabaseError> for (StatusCode, axum::Json<serde_json::Value>) {
+    fn from(err: crate::database::DatabaseError) -> Self {
+        err.report();
+
+        (
+            err.status(),
+            axum::Json(ApiError::new(&[err.message()]).to_value()),
+        )
+    }
+}
+
 pub struct AppState {
     pub start_time: Instant,
     pub version: String,
@@ -45,6 +56,7 @@ pub struct AppState {
     pub requests: crate::requests::RequestLogger,
     pub env: Arc<crate::env::Env>,
     pub s3: Arc<crate::s3::S3>,
+    pub client: reqwest::Client,
 }
 
 pub type State = Arc<AppState>;
@@ -53,13 +65,19 @@ pub type GetData = axum::extract::Extension<Arc<Mutex<serde_json::Value>>>;
 
 async fn handle_api_request(state: GetState, req: Request, next: Next) -> Response<Body> {
     let mut organization: Option<Organization> = None;
-    if let Some(authorization) = req.headers().get("Authorization") {
-        if let Ok(authorization) = authorization.to_str() {
-            if authorization.len() == 64 {
-                organization =
-                    Organization::by_key(&state.database, &state.cache, authorization).await;
-            }
-        }
+    if let Some(authorization) = req.headers().get("Authorization")
+        && let Ok(authorization) = authorization.to_str()
+        && authorization.len() == 64
+    {
+        organization =
+            match Organization::by_key(&state.database, &state.cache, authorization).await {
+                Ok(organization) => organization,
+                Err(err) => {
+                    err.report();
+
+                    None
+                }
+            };
     }
 
     let (parts, body) = req.into_parts();
@@ -167,3 +185,123 @@ pub fn router(state: &State) -> OpenApiRouter<State> {
         ))
         .with_state(state.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        cache::{Cache, MemoryBackend},
+        env::{Env, RedisMode},
+        models::r#type::{ServerType, ServerTypeInfo},
+    };
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use indexmap::IndexMap;
+    use std::{sync::Arc, time::Instant};
+    use tower::ServiceExt;
+
+    async fn state() -> super::State {
+        let env = Arc::new(Env {
+            redis_url: None,
+            redis_sentinels: None,
+            redis_mode: RedisMode::Memory,
+            cache_lock: true,
+
+            sentry_url: None,
+            database_migrate: false,
+            database_refresh: false,
+            database_url: "postgres://mcjars@127.0.0.1:1/mcjars".to_string(),
+            database_url_primary: None,
+
+            fetchers_enabled: false,
+            fetchers_vanilla_url: String::new(),
+            fetchers_paper_url: String::new(),
+            fetchers_purpur_url: String::new(),
+            fetchers_fabric_url: String::new(),
+            fetchers_legacy_fabric_url: String::new(),
+
+            github_client_id: String::new(),
+            github_client_secret: String::new(),
+
+            s3_url: "http://127.0.0.1:1".to_string(),
+            s3_path_style: true,
+            s3_endpoint: "http://127.0.0.1:1".to_string(),
+            s3_region: "us-east-1".to_string(),
+            s3_bucket: "mcjars".to_string(),
+            s3_access_key: "mcjars".to_string(),
+            s3_secret_key: "mcjars".to_string(),
+
+            bind: "127.0.0.1".to_string(),
+            port: 0,
+
+            app_url: "http://127.0.0.1".to_string(),
+            app_frontend_url: "http://127.0.0.1".to_string(),
+            app_cookie_domain: "127.0.0.1".to_string(),
+            server_name: None,
+        });
+        let database = Arc::new(crate::database::Database::lazy(&env.database_url));
+        let cache = Arc::new(Cache::with_backend(Arc::new(MemoryBackend::new()), true).await);
+
+        Arc::new(super::AppState {
+            start_time: Instant::now(),
+            version: "test".to_string(),
+
+            database: database.clone(),
+            cache: cache.clone(),
+            requests: crate::requests::RequestLogger::new(database, cache),
+            s3: Arc::new(crate::s3::S3::new(env.clone()).await),
+            env,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    async fn get(state: &super::State, uri: &str) -> (StatusCode, serde_json::Value) {
+        let (router, _) = super::router(state).split_for_parts();
+        let response = router
+            .with_state(state.clone())
+            .oneshot(
+                Request::get(uri)
+                    .header("x-real-ip", "127.0.0.1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn serves_cached_types_without_the_database() {
+        let state = state().await;
+
+        state
+            .cache
+            .cached("types::all", 1800, || async {
+                Ok(IndexMap::<ServerType, ServerTypeInfo>::new())
+            })
+            .await
+            .unwrap();
+
+        let (status, body) = get(&state, "/api/v1/types").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+    }
+
+    #[tokio::test]
+    async fn reports_an_unavailable_database() {
+        let state = state().await;
+
+        let (status, body) = get(&state, "/api/v1/types").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["errors"][0], "database unavailable");
+    }
+}